@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use anyhow::{anyhow, bail, Result};
 use reqwest::header::{HeaderMap, HeaderValue};
 use reqwest::Client;
@@ -6,7 +8,7 @@ use serde_json::Value;
 use slog::{debug, error, trace, Logger};
 
 use crate::game::{Branch, Game};
-use crate::net::http_client_builder;
+use crate::net::{http_client_builder, measure_head_request};
 
 use super::Account;
 
@@ -68,6 +70,11 @@ pub async fn login_with_steam(logger: &Logger, game: &Game, ticket: Vec<u8>) ->
     })
 }
 
+pub async fn test_connectivity(game: &Game) -> Result<Duration> {
+    let client = make_client(game)?;
+    Ok(measure_head_request(&client, &endpoint_url(game.branch(), "")).await?)
+}
+
 async fn post_request<R: Serialize>(game: &Game, endpoint: &str, request: R) -> Result<Value> {
     let client = make_client(game)?;
     Ok(client
@@ -4,13 +4,14 @@ use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
 use dynabus::Bus;
-use slog::{warn, Logger};
+use slog::{info, warn, Logger};
 
 use crate::auth::{Account, AuthState, CachedUser, CachedUsers, Capability, PlatformUser};
 use crate::bus::AppBus;
 use crate::game::platform::steam::{PlatformReady, SteamClient};
 use crate::game::Game;
 use crate::gui::UpdateAuthState;
+use crate::launcher::GameLaunched;
 use crate::util::weak_cb;
 use crate::workers::{FlsWorker, LoginComplete, TaskState};
 
@@ -68,11 +69,26 @@ impl AuthManager {
             bus.subscribe_consumer(weak_cb!(
                 [this] => |LoginComplete(payload)| this.login_complete(payload)
             ));
+            bus.subscribe_observer(weak_cb!(
+                [this] => |launched: &GameLaunched| this.game_launched(launched)
+            ));
         }
 
         this
     }
 
+    fn game_launched(&self, launched: &GameLaunched) {
+        let cached_user = self.cached_user();
+        info!(
+            self.logger,
+            "Game session started";
+            "mode" => ?launched.mode,
+            "map_id" => ?launched.map_id,
+            "server_addr" => ?launched.server_addr,
+            "user" => cached_user.as_ref().map(|user| user.account.display_name.as_str()),
+        );
+    }
+
     pub fn cached_user(&self) -> Option<Ref<CachedUser>> {
         let platform_user = self.steam.user()?;
         let cached_users = self.cached_users.borrow();
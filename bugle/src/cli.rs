@@ -0,0 +1,190 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use pico_args::Arguments;
+use serde::Serialize;
+use slog::{error, Logger};
+
+use crate::config::ConfigManager;
+use crate::game::platform::steam::Steam;
+use crate::game::{Game, ModRef};
+
+#[derive(Clone, Copy)]
+enum OutputFormat {
+    Plain,
+    Json,
+}
+
+#[derive(Serialize)]
+struct ModListEntry {
+    name: String,
+    steam_id: Option<u64>,
+    pak_path: Option<PathBuf>,
+    enabled: bool,
+}
+
+impl FromStr for OutputFormat {
+    type Err = &'static str;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Self::Plain),
+            "json" => Ok(Self::Json),
+            _ => Err("expected json or plain"),
+        }
+    }
+}
+
+pub fn run_headless(
+    logger: &Logger,
+    config: &ConfigManager,
+    game_path: Option<PathBuf>,
+    mut args: Arguments,
+) -> i32 {
+    let format = match args.opt_value_from_str("--format") {
+        Ok(format) => format.unwrap_or(OutputFormat::Plain),
+        Err(err) => return usage_error(&err.to_string()),
+    };
+    let command = match args.subcommand() {
+        Ok(Some(command)) => command,
+        Ok(None) => return usage_error("missing command"),
+        Err(err) => return usage_error(&err.to_string()),
+    };
+
+    let result = match command.as_str() {
+        "mod-list" => {
+            let set_ids: Option<String> = match args.opt_value_from_str("--set") {
+                Ok(ids) => ids,
+                Err(err) => return usage_error(&err.to_string()),
+            };
+            let print = args.contains("--print");
+            if set_ids.is_some() == print {
+                return usage_error("mod-list requires exactly one of --set or --print");
+            }
+            if let Some(unexpected) = args.finish().first() {
+                return usage_error(&format!("unexpected argument {:?}", unexpected));
+            }
+            let set_ids = match set_ids.map(|ids| parse_steam_ids(&ids)).transpose() {
+                Ok(ids) => ids,
+                Err(err) => return usage_error(&err.to_string()),
+            };
+            init_game(logger, config, game_path)
+                .and_then(|game| mod_list_command(&game, set_ids, format))
+        }
+        _ => return usage_error(&format!("unknown command {:?}", command)),
+    };
+
+    match result {
+        Ok(()) => EXIT_SUCCESS,
+        Err(err) => {
+            error!(logger, "Error running headless command"; "command" => &command, "error" => %err);
+            eprintln!("error: {:#}", err);
+            EXIT_FAILURE
+        }
+    }
+}
+
+fn init_game(logger: &Logger, config: &ConfigManager, game_path: Option<PathBuf>) -> Result<Game> {
+    let mut steam = Steam::locate(logger);
+    let branch = config.get().branch;
+    let location = match game_path {
+        Some(game_path) => steam.locate_game_at(game_path, branch)?,
+        None => steam.locate_game(branch)?,
+    };
+    steam.init_game(location)
+}
+
+fn mod_list_command(game: &Game, set_ids: Option<Vec<u64>>, format: OutputFormat) -> Result<()> {
+    let mod_list = match set_ids {
+        Some(ids) => {
+            let mods = game.installed_mods();
+            let mod_list = ids
+                .into_iter()
+                .map(|id| {
+                    mods.by_steam_id(id, game.branch())
+                        .ok_or_else(|| anyhow!("Mod {} is not installed", id))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            game.save_mod_list(&mod_list)?;
+            mod_list
+        }
+        None => game.load_mod_list()?,
+    };
+
+    let entries: Vec<ModListEntry> = mod_list
+        .iter()
+        .map(|mod_ref| mod_list_entry(game, mod_ref))
+        .collect();
+    match format {
+        OutputFormat::Plain => {
+            for entry in entries {
+                let steam_id = entry.steam_id.map(|id| id.to_string());
+                println!(
+                    "{}{}\t{}",
+                    if entry.enabled { "" } else { "# " },
+                    steam_id.as_deref().unwrap_or("-"),
+                    entry.name,
+                );
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&entries)?),
+    }
+
+    Ok(())
+}
+
+fn mod_list_entry(game: &Game, mod_ref: &ModRef) -> ModListEntry {
+    let enabled = !matches!(mod_ref, ModRef::Disabled(_));
+    match mod_ref {
+        ModRef::UnknownFolder(folder) => ModListEntry {
+            name: folder.clone(),
+            steam_id: None,
+            pak_path: None,
+            enabled,
+        },
+        ModRef::UnknownPakPath(path) => ModListEntry {
+            name: path.display().to_string(),
+            steam_id: None,
+            pak_path: Some(path.clone()),
+            enabled,
+        },
+        _ => {
+            let installed_mods = game.installed_mods();
+            let entry = installed_mods.get(mod_ref).unwrap();
+            let info = entry.info.as_ref().ok();
+            ModListEntry {
+                name: info
+                    .map(|info| info.name.clone())
+                    .unwrap_or_else(|| entry.pak_path.display().to_string()),
+                steam_id: info.and_then(|info| info.steam_file_id(game.branch())),
+                pak_path: Some(entry.pak_path.clone()),
+                enabled,
+            }
+        }
+    }
+}
+
+fn parse_steam_ids(ids: &str) -> Result<Vec<u64>> {
+    ids.split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| id.parse().map_err(|_| anyhow!("invalid Steam ID {:?}", id)))
+        .collect()
+}
+
+fn usage_error(message: &str) -> i32 {
+    eprintln!("error: {}\n\n{}", message, USAGE);
+    EXIT_USAGE
+}
+
+const USAGE: &str = "\
+Usage:
+    bugle --headless [--format json|plain] mod-list --print
+    bugle --headless [--format json|plain] mod-list --set <steam_id>,<steam_id>,...
+
+On Windows, use \"start /b /wait bugle --headless ...\" in the Command Prompt to wait for the
+command to finish and get its exit code in %ERRORLEVEL%.";
+
+const EXIT_SUCCESS: i32 = 0;
+const EXIT_FAILURE: i32 = 1;
+const EXIT_USAGE: i32 = 2;
@@ -1,18 +1,21 @@
 use std::cell::{Ref, RefCell};
+use std::collections::{BTreeMap, HashSet};
 use std::fs::{File, OpenOptions};
 use std::io::Read;
+use std::net::{IpAddr, SocketAddr};
 use std::ops::{Deref, DerefMut};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use anyhow::Result;
-use ini::{EscapePolicy, Ini, LineSeparator, ParseOption, WriteOption};
+use ini::{EscapePolicy, Ini, LineSeparator, ParseOption, Properties, WriteOption};
 use ini_persist::load::{IniLoad, LoadProperty, ParseProperty};
 use ini_persist::save::{DisplayProperty, IniSave, SaveProperty};
 use slog::{warn, Logger};
 
-use crate::env::current_exe_dir;
+use crate::env::{self, current_exe_dir};
 use crate::game::Branch;
 use crate::servers::{Filter, SortCriteria};
 
@@ -29,6 +32,9 @@ pub struct Config {
 
     #[ini(section = "ServerBrowser")]
     pub server_browser: ServerBrowserConfig,
+
+    #[ini(section = "extra_args_presets")]
+    pub extra_args_presets: ExtraArgsPresets,
 }
 
 #[derive(Debug, Default, LoadProperty, SaveProperty)]
@@ -45,6 +51,9 @@ pub struct GeneralConfig {
     #[ini(rename = "UseAllCores", ignore_errors)]
     pub use_all_cores: bool,
 
+    #[ini(rename = "Windowed", ignore_errors)]
+    pub windowed: bool,
+
     #[ini(rename = "ExtraArgs", ignore_errors)]
     pub extra_args: String,
 
@@ -53,6 +62,51 @@ pub struct GeneralConfig {
 
     #[ini(rename = "Theme", ignore_errors)]
     pub theme: ThemeChoice,
+
+    #[ini(rename = "NewsFeedURL", ignore_errors)]
+    pub news_feed_url: Option<String>,
+
+    #[ini(rename = "SecurePasswords", ignore_errors)]
+    pub secure_passwords: SecurePasswords,
+
+    #[ini(rename = "AutoJoinOnStartup", ignore_errors)]
+    pub auto_join_on_startup: bool,
+
+    #[ini(rename = "AutoJoinDelay", ignore_errors)]
+    pub auto_join_delay_secs: AutoJoinDelay,
+
+    #[ini(rename = "LastUsedSinglePlayerMap", ignore_errors)]
+    pub last_used_sp_map_id: Option<usize>,
+
+    #[ini(rename = "LastRunVersion", ignore_errors)]
+    pub last_run_version: Option<String>,
+
+    #[ini(rename = "WhatsNew", ignore_errors)]
+    pub whats_new: WhatsNew,
+
+    #[ini(rename = "FavoriteMods", ignore_errors)]
+    pub favorite_mods: FavoriteMods,
+
+    #[ini(rename = "ShowModUses", ignore_errors)]
+    pub show_mod_uses: bool,
+
+    #[ini(rename = "AutoActivateServerMods", ignore_errors)]
+    pub auto_activate_server_mods: bool,
+
+    #[ini(rename = "SessionCount", ignore_errors)]
+    pub session_count: u32,
+
+    #[ini(rename = "TotalLaunches", ignore_errors)]
+    pub total_launches: u32,
+
+    #[ini(rename = "CrashCount", ignore_errors)]
+    pub crash_count: u32,
+
+    #[ini(rename = "MinimizeOnLaunch", ignore_errors)]
+    pub minimize_on_launch: bool,
+
+    #[ini(rename = "MinFreeSpaceGB", ignore_errors)]
+    pub min_free_space_gb: MinFreeSpace,
 }
 
 #[derive(Debug, Default, LoadProperty, SaveProperty)]
@@ -65,6 +119,70 @@ pub struct ServerBrowserConfig {
 
     #[ini(rename = "ScrollLock")]
     pub scroll_lock: bool,
+
+    #[ini(rename = "MaintenanceStatusURL", ignore_errors)]
+    pub maintenance_status_url: Option<String>,
+
+    #[ini(rename = "MaintenanceCheckInterval", ignore_errors)]
+    pub maintenance_check_mins: MaintenanceCheckInterval,
+
+    #[ini(rename = "ServerListURL", ignore_errors)]
+    pub server_list_url: Option<String>,
+
+    #[ini(rename = "ServerListUser", ignore_errors)]
+    pub server_list_user: Option<String>,
+
+    #[ini(rename = "ServerListPassword", ignore_errors)]
+    pub server_list_password: Option<String>,
+
+    #[ini(rename = "BlockedServers", ignore_errors)]
+    pub blocked_servers: BlockedServers,
+
+    #[ini(rename = "AutoRemoveAfterTimeouts", ignore_errors)]
+    pub auto_remove_after_timeouts: Option<usize>,
+
+    #[ini(rename = "DismissedSettingsWarnings", ignore_errors)]
+    pub dismissed_settings_warnings: ServerAddrs,
+
+    #[ini(rename = "ShowHealth", ignore_errors)]
+    pub show_health: bool,
+}
+
+impl ServerBrowserConfig {
+    pub fn server_list_auth(&self) -> Result<Option<(String, String)>> {
+        let Some(user) = self.server_list_user.as_ref() else {
+            return Ok(None);
+        };
+        let password = match self.server_list_password.as_deref() {
+            Some(stored) => env::unprotect_password(&server_list_keyring_key(user), stored)?,
+            None => String::new(),
+        };
+        Ok(Some((user.clone(), password)))
+    }
+
+    // If the password cannot be encrypted, it is stored as plaintext and the error is returned
+    pub fn set_server_list_auth(
+        &mut self,
+        auth: Option<(String, String)>,
+        secure: bool,
+    ) -> Result<()> {
+        let Some((user, password)) = auth else {
+            self.server_list_user = None;
+            self.server_list_password = None;
+            return Ok(());
+        };
+        let (password, result) = if secure {
+            match env::protect_password(&server_list_keyring_key(&user), &password) {
+                Ok(protected) => (protected, Ok(())),
+                Err(err) => (password, Err(err)),
+            }
+        } else {
+            (password, Ok(()))
+        };
+        self.server_list_user = Some(user);
+        self.server_list_password = Some(password);
+        result
+    }
 }
 
 impl Deref for Config {
@@ -140,6 +258,229 @@ impl DisplayProperty for LogLevel {
     }
 }
 
+#[derive(Debug, Clone, Copy, LoadProperty, SaveProperty)]
+pub struct AutoJoinDelay(pub u8);
+
+impl Default for AutoJoinDelay {
+    fn default() -> Self {
+        Self(5)
+    }
+}
+
+#[derive(Debug, Clone, Copy, LoadProperty, SaveProperty)]
+pub struct MinFreeSpace(pub f32);
+
+impl Default for MinFreeSpace {
+    fn default() -> Self {
+        Self(2.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, LoadProperty, SaveProperty)]
+pub struct MaintenanceCheckInterval(pub u32);
+
+impl Default for MaintenanceCheckInterval {
+    fn default() -> Self {
+        Self(15)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FavoriteMods(pub HashSet<String>);
+
+impl Deref for FavoriteMods {
+    type Target = HashSet<String>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for FavoriteMods {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl ParseProperty for FavoriteMods {
+    fn parse(text: &str) -> ini_persist::Result<Self> {
+        Ok(Self(
+            text.split(',')
+                .map(str::trim)
+                .filter(|folder| !folder.is_empty())
+                .map(str::to_string)
+                .collect(),
+        ))
+    }
+}
+
+impl DisplayProperty for FavoriteMods {
+    fn display(&self) -> String {
+        let mut folders: Vec<&str> = self.0.iter().map(String::as_str).collect();
+        folders.sort();
+        folders.join(",")
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BlockedServers(pub Arc<HashSet<IpAddr>>);
+
+impl Deref for BlockedServers {
+    type Target = HashSet<IpAddr>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for BlockedServers {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        Arc::make_mut(&mut self.0)
+    }
+}
+
+impl ParseProperty for BlockedServers {
+    fn parse(text: &str) -> ini_persist::Result<Self> {
+        Ok(Self(Arc::new(
+            text.split(',')
+                .map(str::trim)
+                .filter(|ip| !ip.is_empty())
+                .filter_map(|ip| ip.parse().ok())
+                .collect(),
+        )))
+    }
+}
+
+impl DisplayProperty for BlockedServers {
+    fn display(&self) -> String {
+        let mut ips: Vec<&IpAddr> = self.0.iter().collect();
+        ips.sort();
+        ips.iter()
+            .map(|ip| ip.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ServerAddrs(pub HashSet<SocketAddr>);
+
+impl Deref for ServerAddrs {
+    type Target = HashSet<SocketAddr>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ServerAddrs {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl ParseProperty for ServerAddrs {
+    fn parse(text: &str) -> ini_persist::Result<Self> {
+        Ok(Self(
+            text.split(',')
+                .map(str::trim)
+                .filter(|addr| !addr.is_empty())
+                .filter_map(|addr| addr.parse().ok())
+                .collect(),
+        ))
+    }
+}
+
+impl DisplayProperty for ServerAddrs {
+    fn display(&self) -> String {
+        let mut addrs: Vec<&SocketAddr> = self.0.iter().collect();
+        addrs.sort();
+        addrs
+            .iter()
+            .map(|addr| addr.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ExtraArgsPresets(pub Vec<(String, String)>);
+
+impl Deref for ExtraArgsPresets {
+    type Target = Vec<(String, String)>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ExtraArgsPresets {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl LoadProperty for ExtraArgsPresets {
+    fn load_in(&mut self, section: &Properties, _key: &str) -> ini_persist::Result<()> {
+        let mut presets = BTreeMap::<usize, (Option<String>, Option<String>)>::new();
+        for (key, value) in section.iter() {
+            let Some((idx, field)) = parse_preset_key(key) else {
+                continue;
+            };
+            let entry = presets.entry(idx).or_default();
+            match field {
+                PRESET_NAME_SUFFIX => entry.0 = Some(value.to_string()),
+                PRESET_ARGS_SUFFIX => entry.1 = Some(value.to_string()),
+                _ => (),
+            }
+        }
+
+        // a preset without a name can't be shown or picked, so skip it
+        self.0 = presets
+            .into_values()
+            .filter_map(|(name, args)| Some((name?, args.unwrap_or_default())))
+            .collect();
+        Ok(())
+    }
+}
+
+impl SaveProperty for ExtraArgsPresets {
+    fn remove(section: &mut Properties, _key: &str) {
+        let keys: Vec<String> = section
+            .iter()
+            .filter(|(key, _)| parse_preset_key(key).is_some())
+            .map(|(key, _)| key.to_string())
+            .collect();
+        for key in keys {
+            let _ = section.remove_all(&key);
+        }
+    }
+
+    fn append(&self, section: &mut Properties, _key: &str) {
+        for (idx, (name, args)) in self.0.iter().enumerate() {
+            let idx = idx + 1;
+            section.append(
+                format!("{}{}{}", PRESET_PREFIX, idx, PRESET_NAME_SUFFIX),
+                name.clone(),
+            );
+            section.append(
+                format!("{}{}{}", PRESET_PREFIX, idx, PRESET_ARGS_SUFFIX),
+                args.clone(),
+            );
+        }
+    }
+}
+
+fn server_list_keyring_key(user: &str) -> String {
+    format!("server-list/{}", user)
+}
+
+fn parse_preset_key(key: &str) -> Option<(usize, &str)> {
+    let rest = key.strip_prefix(PRESET_PREFIX)?;
+    let suffix_start = rest.find('_')?;
+    let idx = rest[..suffix_start].parse().ok()?;
+    match &rest[suffix_start..] {
+        suffix @ (PRESET_NAME_SUFFIX | PRESET_ARGS_SUFFIX) => Some((idx, suffix)),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BattlEyeUsage {
     Auto,
@@ -186,11 +527,38 @@ impl Default for ModMismatchChecks {
     }
 }
 
-#[derive(Debug, Clone, Copy, LoadProperty, SaveProperty)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, LoadProperty, SaveProperty)]
+#[ini(ignore_case)]
+pub enum SecurePasswords {
+    Enabled,
+    Disabled,
+}
+
+impl Default for SecurePasswords {
+    fn default() -> Self {
+        Self::Enabled
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, LoadProperty, SaveProperty)]
+#[ini(ignore_case)]
+pub enum WhatsNew {
+    Enabled,
+    Disabled,
+}
+
+impl Default for WhatsNew {
+    fn default() -> Self {
+        Self::Enabled
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, LoadProperty, SaveProperty)]
 #[ini(ignore_case)]
 pub enum ThemeChoice {
     Light,
     Dark,
+    System,
 }
 
 impl Default for ThemeChoice {
@@ -324,3 +692,7 @@ fn load_text_lossy(mut file: File) -> std::io::Result<String> {
 const BATTLEYE_AUTO: &str = "auto";
 const BATTLEYE_ALWAYS: &str = "always";
 const BATTLEYE_NEVER: &str = "never";
+
+const PRESET_PREFIX: &str = "preset_";
+const PRESET_NAME_SUFFIX: &str = "_name";
+const PRESET_ARGS_SUFFIX: &str = "_args";
@@ -0,0 +1,114 @@
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+use anyhow::Result;
+use ini::{EscapePolicy, Ini, LineSeparator, WriteOption};
+use ini_persist::save::IniSave;
+
+use crate::config::Config;
+use crate::game::Game;
+
+pub fn write_report(
+    path: &Path,
+    game: &Game,
+    config: &Config,
+    log_path: Option<&Path>,
+    platform_user_id: Option<&str>,
+) -> Result<()> {
+    let mut report = String::new();
+
+    section(&mut report, "BUGLE");
+    writeln!(report, "Version: {}", env!("CARGO_PKG_VERSION"))?;
+
+    section(&mut report, "System");
+    writeln!(report, "OS: {}", std::env::consts::OS)?;
+    writeln!(report, "Family: {}", std::env::consts::FAMILY)?;
+    writeln!(report, "Architecture: {}", std::env::consts::ARCH)?;
+
+    section(&mut report, "Game");
+    let (revision, snapshot) = game.version();
+    writeln!(report, "Branch: {:?}", game.branch())?;
+    writeln!(report, "Version: {}/{}", revision, snapshot)?;
+    writeln!(report, "Build ID: {}", game.build_id())?;
+    writeln!(report, "Installed Mods: {}", game.installed_mods().len())?;
+
+    section(&mut report, "User");
+    let user_hash = platform_user_id.map(|id| format!("{:x}", md5::compute(id)));
+    writeln!(
+        report,
+        "Steam User ID (hashed): {}",
+        user_hash.as_deref().unwrap_or("<unknown>")
+    )?;
+
+    section(&mut report, "Configuration");
+    report.push_str(&redacted_config(config)?);
+
+    section(&mut report, &format!("Log (last {} lines)", LOG_TAIL_LINES));
+    match log_path {
+        Some(log_path) => match log_tail(log_path) {
+            Ok(lines) => {
+                for line in lines {
+                    writeln!(report, "{}", line)?;
+                }
+            }
+            Err(err) => writeln!(report, "<error reading log: {}>", err)?,
+        },
+        None => writeln!(report, "<logging disabled>")?,
+    }
+
+    let mut file = File::create(path)?;
+    file.write_all(report.as_bytes())?;
+    Ok(())
+}
+
+fn section(report: &mut String, title: &str) {
+    if !report.is_empty() {
+        report.push('\n');
+    }
+    let _ = writeln!(report, "===== {} =====", title);
+}
+
+fn redacted_config(config: &Config) -> Result<String> {
+    let mut ini = Ini::new();
+    config.save_to_ini(&mut ini);
+    for (_, props) in ini.iter_mut() {
+        let sensitive: Vec<String> = props
+            .iter()
+            .filter(|(key, _)| SENSITIVE_KEYS.contains(key))
+            .map(|(key, _)| key.to_string())
+            .collect();
+        for key in sensitive {
+            props.insert(key, REDACTED);
+        }
+    }
+
+    let mut buf = Vec::new();
+    ini.write_to_opt(
+        &mut buf,
+        WriteOption {
+            escape_policy: EscapePolicy::Nothing,
+            line_separator: LineSeparator::CR,
+        },
+    )?;
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+fn log_tail(log_path: &Path) -> Result<VecDeque<String>> {
+    let reader = BufReader::new(File::open(log_path)?);
+    let mut lines = VecDeque::with_capacity(LOG_TAIL_LINES);
+    for line in reader.split(b'\n') {
+        if lines.len() == LOG_TAIL_LINES {
+            lines.pop_front();
+        }
+        let line = line?;
+        lines.push_back(String::from_utf8_lossy(&line).trim_end().to_string());
+    }
+    Ok(lines)
+}
+
+const LOG_TAIL_LINES: usize = 200;
+const SENSITIVE_KEYS: &[&str] = &["ServerListUser", "ServerListPassword"];
+const REDACTED: &str = "<redacted>";
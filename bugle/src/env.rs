@@ -1,5 +1,5 @@
 use std::io::{Error, ErrorKind, Result};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Child;
 
 pub fn current_exe_dir() -> Result<PathBuf> {
@@ -17,6 +17,161 @@ pub fn restart_process() -> Result<Child> {
     cmd.spawn()
 }
 
+pub fn open_url(url: &str) -> Result<()> {
+    #[cfg(windows)]
+    let mut cmd = std::process::Command::new("explorer");
+    #[cfg(not(windows))]
+    let mut cmd = std::process::Command::new("xdg-open");
+    cmd.arg(url);
+    cmd.spawn()?;
+    Ok(())
+}
+
+pub fn open_folder(path: &Path) -> Result<()> {
+    #[cfg(windows)]
+    let mut cmd = std::process::Command::new("explorer");
+    #[cfg(target_os = "macos")]
+    let mut cmd = std::process::Command::new("open");
+    #[cfg(not(any(windows, target_os = "macos")))]
+    let mut cmd = std::process::Command::new("xdg-open");
+    cmd.arg(path);
+    cmd.spawn()?;
+    Ok(())
+}
+
+pub fn is_protected_password(stored: &str) -> bool {
+    stored.starts_with(DPAPI_PASSWORD_PREFIX) || stored == KEYRING_PASSWORD_MARKER
+}
+
+#[cfg(windows)]
+pub fn protect_password(_key: &str, password: &str) -> anyhow::Result<String> {
+    use base64::Engine;
+
+    let protected = protect_data(password.as_bytes())?;
+    Ok(format!(
+        "{}{}",
+        DPAPI_PASSWORD_PREFIX,
+        base64::engine::general_purpose::STANDARD.encode(protected)
+    ))
+}
+
+#[cfg(all(not(windows), feature = "secure-passwords"))]
+pub fn protect_password(key: &str, password: &str) -> anyhow::Result<String> {
+    keyring::Entry::new(KEYRING_SERVICE, key)?.set_password(password)?;
+    Ok(KEYRING_PASSWORD_MARKER.to_string())
+}
+
+#[cfg(all(not(windows), not(feature = "secure-passwords")))]
+pub fn protect_password(_key: &str, _password: &str) -> anyhow::Result<String> {
+    anyhow::bail!("Password encryption is not supported in this build")
+}
+
+#[cfg_attr(
+    any(windows, not(feature = "secure-passwords")),
+    allow(unused_variables)
+)]
+pub fn unprotect_password(key: &str, stored: &str) -> anyhow::Result<String> {
+    #[cfg(windows)]
+    if let Some(protected) = stored.strip_prefix(DPAPI_PASSWORD_PREFIX) {
+        use base64::Engine;
+
+        let password =
+            unprotect_data(&base64::engine::general_purpose::STANDARD.decode(protected)?)?;
+        return Ok(String::from_utf8(password)?);
+    }
+
+    #[cfg(all(not(windows), feature = "secure-passwords"))]
+    if stored == KEYRING_PASSWORD_MARKER {
+        return Ok(keyring::Entry::new(KEYRING_SERVICE, key)?.get_password()?);
+    }
+
+    if is_protected_password(stored) {
+        anyhow::bail!("Password was protected on a different platform");
+    }
+    Ok(stored.to_string())
+}
+
+#[cfg(windows)]
+pub fn protect_data(data: &[u8]) -> Result<Vec<u8>> {
+    use std::ptr::{null, null_mut};
+
+    use winapi::um::dpapi::{CryptProtectData, CRYPTPROTECT_UI_FORBIDDEN};
+
+    crypt_data(data, |input, output| unsafe {
+        CryptProtectData(
+            input,
+            null(),
+            null_mut(),
+            null_mut(),
+            null_mut(),
+            CRYPTPROTECT_UI_FORBIDDEN,
+            output,
+        )
+    })
+}
+
+#[cfg(windows)]
+pub fn unprotect_data(data: &[u8]) -> Result<Vec<u8>> {
+    use std::ptr::null_mut;
+
+    use winapi::um::dpapi::{CryptUnprotectData, CRYPTPROTECT_UI_FORBIDDEN};
+
+    crypt_data(data, |input, output| unsafe {
+        CryptUnprotectData(
+            input,
+            null_mut(),
+            null_mut(),
+            null_mut(),
+            null_mut(),
+            CRYPTPROTECT_UI_FORBIDDEN,
+            output,
+        )
+    })
+}
+
+#[cfg(windows)]
+fn crypt_data(
+    data: &[u8],
+    crypt_fn: impl FnOnce(
+        *mut winapi::um::wincrypt::DATA_BLOB,
+        *mut winapi::um::wincrypt::DATA_BLOB,
+    ) -> winapi::shared::minwindef::BOOL,
+) -> Result<Vec<u8>> {
+    use std::ptr::null_mut;
+
+    use winapi::um::winbase::LocalFree;
+    use winapi::um::wincrypt::DATA_BLOB;
+
+    let mut input = DATA_BLOB {
+        cbData: data.len() as _,
+        pbData: data.as_ptr() as _,
+    };
+    let mut output = DATA_BLOB {
+        cbData: 0,
+        pbData: null_mut(),
+    };
+    if crypt_fn(&mut input, &mut output) == 0 {
+        return Err(Error::last_os_error());
+    }
+    let result = unsafe {
+        let result = std::slice::from_raw_parts(output.pbData, output.cbData as _).to_vec();
+        LocalFree(output.pbData as _);
+        result
+    };
+    Ok(result)
+}
+
+#[cfg(windows)]
+pub fn attach_parent_console() -> bool {
+    use std::sync::OnceLock;
+
+    use winapi::um::wincon::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+    // BUGLE is a GUI subsystem app, so it has no console unless it attaches to the parent's
+    static ATTACHED: OnceLock<bool> = OnceLock::new();
+    *ATTACHED.get_or_init(|| unsafe { AttachConsole(ATTACH_PARENT_PROCESS) } != 0)
+}
+
 #[cfg(windows)]
 #[derive(Debug, Clone, Copy)]
 #[allow(dead_code)]
@@ -64,3 +219,8 @@ pub fn appdata_dir(folder: AppDataFolder) -> Result<PathBuf> {
     };
     Ok(PathBuf::from(dir))
 }
+
+const DPAPI_PASSWORD_PREFIX: &str = "DPAPI:";
+const KEYRING_PASSWORD_MARKER: &str = "KEYRING:";
+#[cfg(all(not(windows), feature = "secure-passwords"))]
+const KEYRING_SERVICE: &str = "bugle";
@@ -1,10 +1,11 @@
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::str::FromStr;
-use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
 
 use anyhow::Result;
 use ini::Properties;
@@ -22,15 +23,19 @@ pub mod settings;
 use crate::auth::{CachedUser, CachedUsers};
 use crate::battleye::is_battleye_installed;
 use crate::config;
+use crate::env;
 use crate::servers::{FavoriteServer, FavoriteServers, Server};
 use crate::util::PathExt;
 
-pub use self::engine::db::{create_empty_db, list_mod_controllers, GameDB};
+pub use self::engine::db::{
+    create_empty_db, db_metadata, db_metadata_path, list_mod_controllers, mod_controller_folder,
+    write_db_metadata, GameDB, GameDBMetadata,
+};
 use self::engine::map::MapExtractor;
 pub use self::engine::map::Maps;
 use self::engine::version::get_game_version;
-pub use self::launch::Launch;
-pub use self::mod_info::{ModEntry, ModLibraryBuilder, ModProvenance, ModRef, Mods};
+pub use self::launch::{GameExit, Launch};
+pub use self::mod_info::{ModEntry, ModLibraryBuilder, ModProvenance, ModRef, ModStats, Mods};
 use self::settings::server::ServerSettings;
 use self::settings::Nudity;
 
@@ -45,10 +50,11 @@ pub struct Game {
     game_ini_path: PathBuf,
     server_settings_path: PathBuf,
     mod_list_path: PathBuf,
-    installed_mods: Arc<Mods>,
+    installed_mods: RwLock<Arc<Mods>>,
     maps: Maps,
     last_session: Mutex<Option<Session>>,
     battleye_installed: Option<bool>,
+    dlc_ownership: RwLock<Option<HashMap<u32, bool>>>,
 }
 
 #[derive(Debug, Clone, Copy, LoadProperty, SaveProperty)]
@@ -83,10 +89,24 @@ pub enum ServerRef {
     Unknown(SocketAddr),
 }
 
+#[derive(Debug)]
+pub struct DlcInfo {
+    pub name: &'static str,
+    pub app_id: u32,
+    pub map_db_name: Option<&'static str>,
+}
+
+pub const KNOWN_DLCS: &[DlcInfo] = &[DlcInfo {
+    name: "Isle of Siptah",
+    app_id: 1158390,
+    map_db_name: Some("dlc_siptah.db"),
+}];
+
 #[derive(Debug)]
 pub struct LaunchOptions {
     pub enable_battleye: bool,
     pub use_all_cores: bool,
+    pub windowed: bool,
     pub extra_args: String,
 }
 
@@ -213,10 +233,11 @@ impl Game {
             game_ini_path,
             server_settings_path,
             mod_list_path,
-            installed_mods: Arc::new(installed_mods),
+            installed_mods: RwLock::new(Arc::new(installed_mods)),
             maps,
             last_session: Mutex::new(last_session),
             battleye_installed,
+            dlc_ownership: RwLock::new(None),
         })
     }
 
@@ -254,14 +275,60 @@ impl Game {
         self.save_path.join(&self.maps[map_id].db_name)
     }
 
-    pub fn installed_mods(&self) -> &Arc<Mods> {
-        &self.installed_mods
+    pub fn installed_mods(&self) -> Arc<Mods> {
+        Arc::clone(&self.installed_mods.read().unwrap())
+    }
+
+    pub fn add_installed_mods(&self, mod_dir: &Path, provenance: ModProvenance) -> Result<()> {
+        let mut pak_paths = Vec::new();
+        for pak_path in std::fs::read_dir(mod_dir)? {
+            let pak_path = pak_path?.path();
+            match pak_path.extension() {
+                Some(ext) if ext == "pak" => pak_paths.push(pak_path),
+                _ => (),
+            };
+        }
+        debug!(
+            self.logger,
+            "Adding installed mods";
+            "mod_dir" => mod_dir.display(),
+            "count" => pak_paths.len(),
+        );
+
+        let mut installed_mods = self.installed_mods.write().unwrap();
+        *installed_mods = Arc::new(installed_mods.with_added(pak_paths, provenance)?);
+        Ok(())
     }
 
     pub fn maps(&self) -> &Maps {
         &self.maps
     }
 
+    pub fn map_dlc(&self, map_id: usize) -> Option<&'static DlcInfo> {
+        let db_name = self.maps[map_id].db_name.to_str()?;
+        KNOWN_DLCS.iter().find(|dlc| {
+            dlc.map_db_name
+                .map(|name| name.eq_ignore_ascii_case(db_name))
+                .unwrap_or_default()
+        })
+    }
+
+    pub fn is_running(&self) -> Result<bool> {
+        launch::is_game_running(&self.logger)
+    }
+
+    pub fn dlc_owned(&self, app_id: u32) -> Option<bool> {
+        self.dlc_ownership
+            .read()
+            .unwrap()
+            .as_ref()
+            .and_then(|ownership| ownership.get(&app_id).copied())
+    }
+
+    pub fn set_dlc_ownership(&self, ownership: HashMap<u32, bool>) {
+        *self.dlc_ownership.write().unwrap() = Some(ownership);
+    }
+
     pub fn battleye_installed(&self) -> Option<bool> {
         self.battleye_installed
     }
@@ -348,7 +415,7 @@ impl Game {
         config::save_ini(&game_ini, &self.game_ini_path)
     }
 
-    pub fn load_server_password(&self, server_name: &str) -> Result<Option<String>> {
+    pub fn load_server_password(&self, server_name: &str, secure: bool) -> Result<Option<String>> {
         debug!(self.logger, "Loading server password"; "server" => server_name);
 
         let game_ini = config::load_ini(&self.game_ini_path)?;
@@ -356,13 +423,62 @@ impl Game {
         let Some(section) = game_ini.section(Some(SECTION_SAVED_SERVERS)) else {
             return Ok(None);
         };
+        let Some(stored) = section.get(server_name) else {
+            return Ok(None);
+        };
 
-        Ok(section.get(server_name).map(|s| s.to_string()))
+        let keyring_key = server_password_keyring_key(server_name);
+        if env::is_protected_password(stored) {
+            return Ok(Some(env::unprotect_password(&keyring_key, stored)?));
+        }
+
+        let password = stored.to_string();
+        if secure {
+            if let Ok(protected) = env::protect_password(&keyring_key, &password) {
+                debug!(self.logger, "Encrypting plaintext server password"; "server" => server_name);
+                if let Err(err) = self.store_server_password(server_name, &protected) {
+                    warn!(
+                        self.logger,
+                        "Error encrypting saved server password";
+                        "server" => server_name,
+                        "error" => %err,
+                    );
+                }
+            }
+        }
+
+        Ok(Some(password))
     }
 
-    pub fn save_server_password(&self, server_name: &str, password: &str) -> Result<()> {
+    pub fn save_server_password(
+        &self,
+        server_name: &str,
+        password: &str,
+        secure: bool,
+    ) -> Result<()> {
         debug!(self.logger, "Saving server password"; "server" => server_name);
 
+        let stored = if secure {
+            match env::protect_password(&server_password_keyring_key(server_name), password) {
+                Ok(protected) => protected,
+                Err(err) => {
+                    warn!(
+                        self.logger,
+                        "Error encrypting server password, saving it as plaintext";
+                        "server" => server_name,
+                        "error" => %err,
+                    );
+                    password.to_string()
+                }
+            }
+        } else {
+            password.to_string()
+        };
+
+        self.store_server_password(server_name, &stored)
+    }
+
+    fn store_server_password(&self, server_name: &str, stored: &str) -> Result<()> {
         if server_name == KEY_LAST_CONNECTED || server_name == KEY_LAST_PASSWORD {
             warn!(
                 self.logger,
@@ -378,12 +494,16 @@ impl Game {
             .entry(Some(SECTION_SAVED_SERVERS.to_string()))
             .or_insert_with(Properties::new);
         let _ = section.remove_all(server_name);
-        section.append(server_name, password);
+        section.append(server_name, stored);
 
         config::save_ini(&game_ini, &self.game_ini_path)
     }
 
     pub fn load_mod_list(&self) -> Result<Vec<ModRef>> {
+        Ok(valid_mod_list_entries(self.load_mod_list_lines()?))
+    }
+
+    pub fn load_mod_list_lines(&self) -> Result<Vec<Result<ModRef, (usize, String)>>> {
         if !self.mod_list_path.exists() {
             debug!(self.logger, "No modlist file"; "path" => self.mod_list_path.display());
             return Ok(Vec::new());
@@ -392,23 +512,70 @@ impl Game {
         self.load_mod_list_from(&self.mod_list_path)
     }
 
-    pub fn load_mod_list_from(&self, path: &Path) -> Result<Vec<ModRef>> {
+    pub fn load_mod_list_from(&self, path: &Path) -> Result<Vec<Result<ModRef, (usize, String)>>> {
+        Ok(self
+            .read_mod_list(path)?
+            .into_iter()
+            .map(|(line_num, entry)| entry.map_err(|line| (line_num, line)))
+            .collect())
+    }
+
+    pub fn load_numbered_mod_list(&self) -> Result<Vec<(usize, Result<ModRef, String>)>> {
+        if !self.mod_list_path.exists() {
+            debug!(self.logger, "No modlist file"; "path" => self.mod_list_path.display());
+            return Ok(Vec::new());
+        }
+
+        self.read_mod_list(&self.mod_list_path)
+    }
+
+    fn read_mod_list(&self, path: &Path) -> Result<Vec<(usize, Result<ModRef, String>)>> {
         debug!(self.logger, "Loading modlist"; "path" => path.display());
 
+        let installed_mods = self.installed_mods();
         let file = File::open(path)?;
         let mut mod_list = Vec::new();
-        for line in BufReader::new(file).lines() {
-            if let Ok(mod_path) = line {
-                if !mod_path.starts_with('#') {
-                    let mod_path: PathBuf = mod_path.trim().into();
-                    mod_list.push(self.installed_mods.by_pak_path(&mod_path));
+        for (idx, line) in BufReader::new(file).split(b'\n').enumerate() {
+            let line_num = idx + 1;
+            let line = match String::from_utf8(line?) {
+                Ok(line) => line,
+                Err(err) => {
+                    let line = String::from_utf8_lossy(err.as_bytes()).trim().to_string();
+                    warn!(self.logger, "Malformed modlist entry"; "line" => line_num, "text" => &line);
+                    mod_list.push((line_num, Err(line)));
+                    continue;
                 }
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(mod_path) = line.strip_prefix('#') {
+                // Lines that are commented out but don't name an installed mod are just comments
+                let mod_path: PathBuf = mod_path.trim().into();
+                if let ModRef::Installed(idx) = installed_mods.by_pak_path(&mod_path) {
+                    mod_list.push((line_num, Ok(ModRef::Disabled(idx))));
+                }
+            } else if is_valid_pak_path(line) {
+                mod_list.push((line_num, Ok(installed_mods.by_pak_path(Path::new(line)))));
+            } else {
+                warn!(self.logger, "Malformed modlist entry"; "line" => line_num, "text" => line);
+                mod_list.push((line_num, Err(line.to_string())));
             }
         }
 
         Ok(mod_list)
     }
 
+    pub fn mod_list_backup_path(&self) -> PathBuf {
+        self.mod_list_path.with_file_name("modlist.auto.txt")
+    }
+
+    pub fn last_launch_mod_list_path(&self) -> PathBuf {
+        self.mod_list_path.with_file_name("modlist.lastlaunch.txt")
+    }
+
     pub fn save_mod_list<'m>(&self, mod_list: impl IntoIterator<Item = &'m ModRef>) -> Result<()> {
         self.save_mod_list_to(&self.mod_list_path, mod_list)
     }
@@ -422,10 +589,16 @@ impl Game {
 
         debug!(self.logger, "Saving modlist"; "path" => path.display());
 
+        let installed_mods = self.installed_mods();
         let mut file = File::create(path)?;
         for mod_ref in mod_list {
             let pak_path = match mod_ref {
-                ModRef::Installed(_) => &self.installed_mods.get(mod_ref).unwrap().pak_path,
+                ModRef::Installed(_) => &installed_mods.get(mod_ref).unwrap().pak_path,
+                ModRef::Disabled(_) => {
+                    let pak_path = &installed_mods.get(mod_ref).unwrap().pak_path;
+                    writeln!(&mut file, "# {}", pak_path.display())?;
+                    continue;
+                }
                 ModRef::Custom(mod_info) => &mod_info.pak_path,
                 ModRef::UnknownPakPath(path) => path,
                 ModRef::UnknownFolder(_) => continue,
@@ -497,7 +670,7 @@ impl Game {
             "ConanSandbox.exe"
         });
 
-        let mut cmd = Command::new(exe_path);
+        let mut cmd = Command::new(&exe_path);
         cmd.args(args);
         if options.enable_battleye {
             cmd.arg("-BattlEye");
@@ -505,23 +678,40 @@ impl Game {
         if options.use_all_cores {
             cmd.arg("-USEALLAVAILABLECORES");
         }
+        if options.windowed {
+            cmd.arg("-windowed");
+        }
 
-        match shlex::split(&options.extra_args) {
-            Some(args) => {
-                cmd.args(args);
+        let extra_args = match shlex::split(&options.extra_args) {
+            Some(extra_args) => {
+                cmd.args(&extra_args);
+                extra_args
+            }
+            None => {
+                warn!(
+                    self.logger,
+                    "Error parsing extra launch args";
+                    "extra_args" => &options.extra_args
+                );
+                Vec::new()
             }
-            None => warn!(
-                self.logger,
-                "Error parsing extra launch args";
-                "extra_args" => &options.extra_args
-            ),
         };
 
-        info!(self.logger, "Launching Conan Exiles"; "command" => format!("{:?}", cmd));
+        info!(
+            self.logger,
+            "Launching Conan Exiles";
+            "executable" => exe_path.display(),
+            "args" => args.join(" "),
+            "battleye_enabled" => options.enable_battleye,
+            "use_all_cores" => options.use_all_cores,
+            "windowed" => options.windowed,
+            "extra_args" => extra_args.join(" "),
+        );
         Launch::new(&self.logger, cmd)
     }
 
     pub fn continue_session(&self, options: LaunchOptions) -> Result<Launch> {
+        info!(self.logger, "Continuing last session"; "operation" => "continue_session");
         self.launch(options, &["-continuesession"])
     }
 
@@ -531,6 +721,13 @@ impl Game {
         password: Option<String>,
         options: LaunchOptions,
     ) -> Result<Launch> {
+        info!(
+            self.logger,
+            "Joining server";
+            "operation" => "join_server",
+            "addr" => %addr,
+            "password_provided" => password.is_some(),
+        );
         let mut game_ini = config::load_ini(&self.game_ini_path)?;
         game_ini
             .with_section(Some(SECTION_SAVED_SERVERS))
@@ -545,8 +742,16 @@ impl Game {
     }
 
     pub fn launch_single_player(&self, map_id: usize, options: LaunchOptions) -> Result<Launch> {
-        let mut game_ini = config::load_ini(&self.game_ini_path)?;
         let map = &self.maps[map_id];
+        info!(
+            self.logger,
+            "Launching single player game";
+            "operation" => "launch_single_player",
+            "map_id" => map_id,
+            "map" => &map.display_name,
+            "asset_path" => &map.asset_path,
+        );
+        let mut game_ini = config::load_ini(&self.game_ini_path)?;
         game_ini
             .with_section(Some(SECTION_SAVED_COOP_DATA))
             .set(KEY_LAST_MAP, &map.asset_path)
@@ -571,6 +776,28 @@ const KEY_SERVERS_LIST: &str = "ServersList";
 const KEY_STARTED_LISTEN_SERVER_SESSION: &str = "StartedListenServerSession";
 const KEY_WAS_COOP_ENABLED: &str = "WasCoopEnabled";
 
+const INVALID_PATH_CHARS: &[char] = &['<', '>', '"', '|', '?', '*'];
+
+pub fn valid_mod_list_entries(
+    lines: impl IntoIterator<Item = Result<ModRef, (usize, String)>>,
+) -> Vec<ModRef> {
+    lines.into_iter().filter_map(Result::ok).collect()
+}
+
+fn server_password_keyring_key(server_name: &str) -> String {
+    format!("server/{}", server_name)
+}
+
+fn is_valid_pak_path(text: &str) -> bool {
+    if text.contains(INVALID_PATH_CHARS) {
+        return false;
+    }
+    Path::new(text)
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("pak"))
+        .unwrap_or_default()
+}
+
 fn collect_local_mods(game_path: &Path, mods: &mut ModLibraryBuilder) -> Result<()> {
     let root = game_path.join_all(["ConanSandbox", "Mods"]);
     if !root.exists() {
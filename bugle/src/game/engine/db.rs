@@ -1,13 +1,17 @@
 use std::fs::File;
+use std::io::{BufReader, BufWriter};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Result};
-use chrono::{DateTime, Local, NaiveDateTime};
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use lazy_static::lazy_static;
+use regex::Regex;
 use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ValueRef};
 use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct UnixTimestamp(NaiveDateTime);
 
 impl FromSql for UnixTimestamp {
@@ -35,7 +39,7 @@ pub struct GameDB {
     pub last_played_char: Option<Character>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Character {
     pub name: String,
     pub clan: Option<String>,
@@ -43,12 +47,35 @@ pub struct Character {
     pub last_played_timestamp: UnixTimestamp,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GameDBMetadata {
+    // Object name of the map, since map IDs are assigned at runtime and can change between runs
+    pub map_id: String,
+    pub file_size: u64,
+    pub created_at: DateTime<Utc>,
+    pub mod_count: usize,
+    pub mod_list_hash: String,
+    #[serde(default)]
+    pub mod_list: Vec<String>,
+    pub last_played_char: Option<Character>,
+}
+
 impl GameDB {
     pub(in crate::game) fn new<P: AsRef<Path>, F: Fn(&str) -> Option<usize>>(
         file_path: P,
         map_resolver: F,
     ) -> Result<Self> {
         let file_path = file_path.as_ref();
+        if let Ok(Some(metadata)) = read_db_metadata(file_path) {
+            if let Some(map_id) = map_resolver(&metadata.map_id) {
+                return Ok(Self {
+                    file_name: file_path.file_name().unwrap().into(),
+                    map_id,
+                    last_played_char: metadata.last_played_char,
+                });
+            }
+        }
+
         let db = Connection::open(file_path)?;
         let map_id = get_db_map_id(&db, map_resolver)?;
         let last_played_char = get_db_last_played_char(&db)?;
@@ -69,7 +96,77 @@ impl GameDB {
     }
 }
 
+pub fn write_db_metadata<P: AsRef<Path>>(db_path: P) -> Result<()> {
+    let db_path = db_path.as_ref();
+    let metadata = compute_db_metadata(db_path)?;
+    let file = File::create(db_metadata_path(db_path))?;
+    serde_json::to_writer_pretty(BufWriter::new(file), &metadata)?;
+    Ok(())
+}
+
+pub fn db_metadata<P: AsRef<Path>>(db_path: P) -> Result<GameDBMetadata> {
+    let db_path = db_path.as_ref();
+    match read_db_metadata(db_path) {
+        // Sidecars written before the full mod list was recorded can't be used for diffing
+        Ok(Some(metadata)) if metadata.mod_list.len() == metadata.mod_count => Ok(metadata),
+        _ => compute_db_metadata(db_path),
+    }
+}
+
+fn compute_db_metadata(db_path: &Path) -> Result<GameDBMetadata> {
+    let db = Connection::open(db_path)?;
+    let map_id = get_db_map_name(&db)?;
+    let last_played_char = get_db_last_played_char(&db)?;
+    let mut controllers = get_db_mod_controllers(&db)?;
+    drop(db);
+
+    controllers.sort();
+    Ok(GameDBMetadata {
+        map_id,
+        file_size: std::fs::metadata(db_path)?.len(),
+        created_at: Utc::now(),
+        mod_count: controllers.len(),
+        mod_list_hash: format!("{:x}", md5::compute(controllers.join("\n"))),
+        mod_list: controllers,
+        last_played_char,
+    })
+}
+
+fn read_db_metadata(db_path: &Path) -> Result<Option<GameDBMetadata>> {
+    let metadata_path = db_metadata_path(db_path);
+    let Ok(sidecar_stat) = std::fs::metadata(&metadata_path) else {
+        return Ok(None);
+    };
+    let db_stat = std::fs::metadata(db_path)?;
+    if sidecar_stat.modified()? < db_stat.modified()? {
+        return Ok(None);
+    }
+
+    let metadata: GameDBMetadata =
+        serde_json::from_reader(BufReader::new(File::open(&metadata_path)?))?;
+    if metadata.file_size != db_stat.len() {
+        return Ok(None);
+    }
+
+    Ok(Some(metadata))
+}
+
+pub fn db_metadata_path(db_path: &Path) -> PathBuf {
+    let mut file_name = db_path.file_name().unwrap_or_default().to_owned();
+    file_name.push(".meta.json");
+    db_path.with_file_name(file_name)
+}
+
 fn get_db_map_id<F: Fn(&str) -> Option<usize>>(db: &Connection, map_resolver: F) -> Result<usize> {
+    let map_obj_name = get_db_map_name(db)?;
+    if let Some(id) = map_resolver(&map_obj_name) {
+        Ok(id)
+    } else {
+        bail!("Unrecognized map found in game database.")
+    }
+}
+
+fn get_db_map_name(db: &Connection) -> Result<String> {
     let mut query = db.prepare("SELECT DISTINCT map FROM actor_position")?;
     let mut rows = query.query([])?;
 
@@ -80,21 +177,25 @@ fn get_db_map_id<F: Fn(&str) -> Option<usize>>(db: &Connection, map_resolver: F)
     };
 
     let map_obj_name: String = row.get(0)?;
-    let map_id = if let Some(id) = map_resolver(&map_obj_name) {
-        id
-    } else {
-        bail!("Unrecognized map found in game database.")
-    };
 
     if rows.next()?.is_some() {
         bail!("Multiple maps found in game database.");
     };
 
-    Ok(map_id)
+    Ok(map_obj_name)
 }
 
 pub fn list_mod_controllers<P: AsRef<Path>>(db_path: P) -> Result<Vec<String>> {
     let db = Connection::open(db_path.as_ref())?;
+    get_db_mod_controllers(&db)
+}
+
+pub fn mod_controller_folder(controller: &str) -> Option<&str> {
+    let captures = MOD_CTRL_FOLDER_REGEX.captures(controller)?;
+    Some(captures.get(1).unwrap().as_str())
+}
+
+fn get_db_mod_controllers(db: &Connection) -> Result<Vec<String>> {
     let mut query = db
         .prepare("SELECT class FROM actor_position WHERE id IN (SELECT id FROM mod_controllers)")?;
     let controllers: rusqlite::Result<_> = query.query_map([], |row| row.get(0))?.collect();
@@ -155,3 +256,7 @@ fn get_db_last_played_char(db: &Connection) -> Result<Option<Character>> {
         last_played_timestamp,
     }))
 }
+
+lazy_static! {
+    static ref MOD_CTRL_FOLDER_REGEX: Regex = Regex::new("/Game/Mods/([^/]+)/.*").unwrap();
+}
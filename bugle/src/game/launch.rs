@@ -1,14 +1,21 @@
 use std::cell::Cell;
 use std::process::{Child, Command};
+use std::time::{Duration, Instant};
 
 use anyhow::{bail, Result};
 use slog::{debug, trace, Logger};
 
 use crate::workers::TaskState;
 
+pub struct GameExit {
+    pub exit_code: Option<i32>,
+    pub crashed_early: bool,
+}
+
 pub struct Launch {
     logger: Logger,
     child: Child,
+    started: Instant,
     poll_impl: PollImpl,
 }
 
@@ -17,12 +24,14 @@ impl Launch {
         let logger = logger.clone();
 
         let child = cmd.spawn()?;
+        let started = Instant::now();
         debug!(logger, "Spawned the child process"; "pid" => child.id());
 
         let poll_impl = PollImpl::new(&logger, &cmd, &child);
         Ok(Self {
             logger,
             child,
+            started,
             poll_impl,
         })
     }
@@ -35,6 +44,38 @@ impl Launch {
         self.poll_impl.poll()
     }
 
+    pub fn crashed_early(&mut self) -> bool {
+        is_early_crash(self.started, self.exit_code())
+    }
+
+    pub fn exit_code(&mut self) -> Option<i32> {
+        match self.child.try_wait() {
+            Ok(Some(status)) => status.code(),
+            _ => None,
+        }
+    }
+
+    pub fn wait_for_exit(mut self) -> GameExit {
+        let exit_code = self.wait_for_exit_code();
+        GameExit {
+            exit_code,
+            crashed_early: is_early_crash(self.started, exit_code),
+        }
+    }
+
+    fn wait_for_exit_code(&mut self) -> Option<i32> {
+        // When the game is started through a launcher, the child is not the game process
+        #[cfg(windows)]
+        {
+            if let Some(pid) = self.poll_impl.pid.get() {
+                if pid != self.child.id() {
+                    return wait_for_process(pid);
+                }
+            }
+        }
+        self.child.wait().ok().and_then(|status| status.code())
+    }
+
     pub fn cancel(&mut self) {
         debug!(&self.logger, "Killing the child process"; "pid" => self.child.id());
         let _ = self.child.kill();
@@ -152,52 +193,103 @@ impl PollImpl {
     }
 
     fn find_game_pid(&self) -> Result<Option<u32>> {
-        use std::ffi::{OsStr, OsString};
-        use std::os::windows::ffi::OsStringExt;
-
-        use winapi::shared::minwindef::TRUE;
-        use winapi::um::errhandlingapi::GetLastError;
-        use winapi::um::handleapi::INVALID_HANDLE_VALUE;
-        use winapi::um::tlhelp32::{
-            CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
-            TH32CS_SNAPPROCESS,
-        };
+        find_process_pid(&self.logger, &[GAME_EXE])
+    }
+}
 
-        trace!(&self.logger, "Looking for game PID");
+fn is_early_crash(started: Instant, exit_code: Option<i32>) -> bool {
+    matches!(exit_code, Some(code) if code != 0) && (started.elapsed() < EARLY_CRASH_WINDOW)
+}
 
-        let snap = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) };
-        if snap == INVALID_HANDLE_VALUE {
-            let err = unsafe { GetLastError() };
-            bail!("Error enumerating processes, code: {}", err);
-        }
+#[cfg(not(windows))]
+pub fn is_game_running(_: &Logger) -> Result<bool> {
+    Ok(false)
+}
 
-        let mut proc_entry = PROCESSENTRY32W::default();
-        proc_entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>().try_into()?;
-
-        let mut keep_iterating = unsafe { Process32FirstW(snap, &mut proc_entry) };
-        while keep_iterating == TRUE {
-            let path_len = (0..).take_while(|&i| proc_entry.szExeFile[i] != 0).count();
-            let slice = &proc_entry.szExeFile[..path_len];
-            let exe_name = OsString::from_wide(slice);
-            let game_exe: &OsStr = GAME_EXE.as_ref();
-
-            trace!(
-                &self.logger,
-                "Checking process";
-                "pid" => proc_entry.th32ProcessID,
-                "exe_name" => ?exe_name,
-            );
-
-            if exe_name == game_exe {
-                return Ok(Some(proc_entry.th32ProcessID));
-            }
-            keep_iterating = unsafe { Process32NextW(snap, &mut proc_entry) };
-        }
+#[cfg(windows)]
+pub fn is_game_running(logger: &Logger) -> Result<bool> {
+    Ok(find_process_pid(logger, &[GAME_EXE, GAME_BE_EXE])?.is_some())
+}
 
-        trace!(&self.logger, "No matching process found");
+#[cfg(windows)]
+fn wait_for_process(pid: u32) -> Option<i32> {
+    use winapi::shared::minwindef::{DWORD, FALSE};
+    use winapi::um::handleapi::CloseHandle;
+    use winapi::um::processthreadsapi::{GetExitCodeProcess, OpenProcess};
+    use winapi::um::synchapi::WaitForSingleObject;
+    use winapi::um::winbase::{INFINITE, WAIT_OBJECT_0};
+    use winapi::um::winnt::{PROCESS_QUERY_LIMITED_INFORMATION, SYNCHRONIZE};
+
+    let handle =
+        unsafe { OpenProcess(SYNCHRONIZE | PROCESS_QUERY_LIMITED_INFORMATION, FALSE, pid) };
+    if handle.is_null() {
+        return None;
+    }
+    let mut exit_code: DWORD = 0;
+    let exited = unsafe {
+        (WaitForSingleObject(handle, INFINITE) == WAIT_OBJECT_0)
+            && (GetExitCodeProcess(handle, &mut exit_code) != 0)
+    };
+    unsafe { CloseHandle(handle) };
+    if exited {
+        Some(exit_code as i32)
+    } else {
+        None
+    }
+}
 
-        Ok(None)
+#[cfg(windows)]
+fn find_process_pid(logger: &Logger, exe_names: &[&str]) -> Result<Option<u32>> {
+    use std::ffi::{OsStr, OsString};
+    use std::os::windows::ffi::OsStringExt;
+
+    use winapi::shared::minwindef::TRUE;
+    use winapi::um::errhandlingapi::GetLastError;
+    use winapi::um::handleapi::INVALID_HANDLE_VALUE;
+    use winapi::um::tlhelp32::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+        TH32CS_SNAPPROCESS,
+    };
+
+    trace!(logger, "Looking for game PID");
+
+    let snap = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) };
+    if snap == INVALID_HANDLE_VALUE {
+        let err = unsafe { GetLastError() };
+        bail!("Error enumerating processes, code: {}", err);
     }
+
+    let mut proc_entry = PROCESSENTRY32W::default();
+    proc_entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>().try_into()?;
+
+    let mut keep_iterating = unsafe { Process32FirstW(snap, &mut proc_entry) };
+    while keep_iterating == TRUE {
+        let path_len = (0..).take_while(|&i| proc_entry.szExeFile[i] != 0).count();
+        let slice = &proc_entry.szExeFile[..path_len];
+        let exe_name = OsString::from_wide(slice);
+
+        trace!(
+            logger,
+            "Checking process";
+            "pid" => proc_entry.th32ProcessID,
+            "exe_name" => ?exe_name,
+        );
+
+        if exe_names
+            .iter()
+            .any(|name| exe_name.as_os_str() == OsStr::new(name))
+        {
+            return Ok(Some(proc_entry.th32ProcessID));
+        }
+        keep_iterating = unsafe { Process32NextW(snap, &mut proc_entry) };
+    }
+
+    trace!(logger, "No matching process found");
+
+    Ok(None)
 }
 
+const EARLY_CRASH_WINDOW: Duration = Duration::from_secs(30);
 const GAME_EXE: &str = "ConanSandbox.exe";
+#[cfg(windows)]
+const GAME_BE_EXE: &str = "ConanSandbox_BE.exe";
@@ -6,12 +6,15 @@ use std::io::Read;
 use std::ops::{Deref, Index};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use anyhow::{anyhow, bail, Result};
 use binread::{BinReaderExt, BinResult};
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
+use crate::util::edit_distance;
+
 use super::engine::pak::Archive;
 use super::Branch;
 
@@ -22,6 +25,7 @@ pub struct ModEntry {
     pub provenance: ModProvenance,
     pub info: Result<ModInfo>,
     needs_update: AtomicBool,
+    stats: Mutex<Option<ModStats>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -56,6 +60,10 @@ pub struct ModInfo {
     #[serde(rename = "foldername")]
     pub folder_name: String,
 
+    #[serde(rename = "requiredmods")]
+    #[serde(default)]
+    pub required_mods: Vec<String>,
+
     #[serde(rename = "revisionnumber")]
     pub devkit_revision: u32,
 
@@ -75,6 +83,13 @@ pub struct ModVersion {
     build: u64,
 }
 
+#[derive(Debug, Clone)]
+pub struct ModStats {
+    pub subscribers: u64,
+    pub favorites: u64,
+    pub last_updated: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ModProvenance {
     Local,
@@ -97,6 +112,7 @@ impl ModEntry {
             provenance,
             info,
             needs_update: AtomicBool::new(false),
+            stats: Mutex::new(None),
         })
     }
 
@@ -108,6 +124,14 @@ impl ModEntry {
         self.needs_update
             .store(value, std::sync::atomic::Ordering::Relaxed)
     }
+
+    pub fn stats(&self) -> Option<ModStats> {
+        self.stats.lock().unwrap().clone()
+    }
+
+    pub fn set_stats(&self, stats: ModStats) {
+        *self.stats.lock().unwrap() = Some(stats);
+    }
 }
 
 impl ModInfo {
@@ -170,6 +194,7 @@ impl ToString for ModVersion {
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub enum ModRef {
     Installed(usize),
+    Disabled(usize),
     Custom(CustomMod),
     UnknownFolder(String),
     UnknownPakPath(PathBuf),
@@ -238,7 +263,7 @@ impl ModLibraryBuilder {
 
 pub struct Mods {
     roots: HashMap<ModProvenance, PathBuf>,
-    mods: Vec<ModEntry>,
+    mods: Vec<Arc<ModEntry>>,
     by_pak_path: HashMap<PathBuf, usize>,
     by_folder: HashMap<String, usize>,
 }
@@ -248,24 +273,33 @@ impl Mods {
         let mut mods = builder.mods;
         mods.sort_by(mod_sort_cmp);
 
-        let mut by_pak_path = HashMap::with_capacity(mods.len());
-        for (idx, entry) in mods.iter().enumerate() {
-            by_pak_path.insert(entry.pak_path.clone(), idx);
+        let mut this = Self {
+            roots: builder.roots,
+            mods: Vec::with_capacity(mods.len()),
+            by_pak_path: HashMap::with_capacity(mods.len()),
+            by_folder: HashMap::with_capacity(mods.len()),
+        };
+        for entry in mods {
+            this.push(Arc::new(entry));
         }
+        this
+    }
 
-        let mut by_folder = HashMap::with_capacity(mods.len());
-        for (idx, entry) in mods.iter().enumerate() {
-            if let Ok(info) = &entry.info {
-                by_folder.insert(info.folder_name.clone(), idx);
+    // New mods are appended rather than sorted in, so that the indices in any existing ModRef
+    // remain valid
+    pub fn with_added(&self, pak_paths: Vec<PathBuf>, provenance: ModProvenance) -> Result<Self> {
+        let mut this = Self {
+            roots: self.roots.clone(),
+            mods: self.mods.clone(),
+            by_pak_path: self.by_pak_path.clone(),
+            by_folder: self.by_folder.clone(),
+        };
+        for pak_path in pak_paths {
+            if !this.by_pak_path.contains_key(&pak_path) {
+                this.push(Arc::new(ModEntry::new(pak_path, provenance)?));
             }
         }
-
-        Self {
-            roots: builder.roots,
-            mods,
-            by_pak_path,
-            by_folder,
-        }
+        Ok(this)
     }
 
     pub fn len(&self) -> usize {
@@ -274,7 +308,7 @@ impl Mods {
 
     pub fn get<'s: 'r, 'm: 'r, 'r>(&'s self, mod_ref: &'m ModRef) -> Option<&'r ModEntry> {
         match mod_ref {
-            ModRef::Installed(idx) => self.mods.get(*idx),
+            ModRef::Installed(idx) | ModRef::Disabled(idx) => self.mods.get(*idx).map(|m| &**m),
             ModRef::Custom(mod_info) => Some(&*mod_info),
             _ => None,
         }
@@ -302,13 +336,44 @@ impl Mods {
         }
     }
 
+    pub fn by_folder_fuzzy(&self, folder: &str, max_distance: usize) -> Option<ModRef> {
+        self.by_folder
+            .iter()
+            .map(|(name, &idx)| (edit_distance(name, folder), idx))
+            .filter(|&(distance, _)| distance <= max_distance)
+            .min_by_key(|&(distance, _)| distance)
+            .map(|(_, idx)| ModRef::Installed(idx))
+    }
+
+    pub fn by_steam_id(&self, id: u64, branch: Branch) -> Option<ModRef> {
+        self.mods
+            .iter()
+            .position(|entry| {
+                entry
+                    .info
+                    .as_ref()
+                    .map(|info| info.steam_file_id(branch) == Some(id))
+                    .unwrap_or_default()
+            })
+            .map(ModRef::Installed)
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &ModEntry> {
-        self.mods.iter()
+        self.mods.iter().map(|entry| &**entry)
     }
 
     pub fn root_for(&self, provenance: ModProvenance) -> Option<&Path> {
         self.roots.get(&provenance).map(|p| p.as_path())
     }
+
+    fn push(&mut self, entry: Arc<ModEntry>) {
+        let idx = self.mods.len();
+        self.by_pak_path.insert(entry.pak_path.clone(), idx);
+        if let Ok(info) = &entry.info {
+            self.by_folder.insert(info.folder_name.clone(), idx);
+        }
+        self.mods.push(entry);
+    }
 }
 
 impl Index<usize> for Mods {
@@ -336,3 +401,67 @@ fn mod_sort_cmp(lhs: &ModEntry, rhs: &ModEntry) -> Ordering {
         (Err(_), Err(_)) => lhs.pak_path.cmp(&rhs.pak_path),
     }
 }
+
+#[cfg(test)]
+impl Mods {
+    pub fn for_test(mods: &[(&str, &str)]) -> Self {
+        let mut builder = ModLibraryBuilder::new();
+        for &(name, folder_name) in mods {
+            builder.mods.push(ModEntry {
+                pak_path: PathBuf::from(format!("{}.pak", folder_name)),
+                pak_size: 0,
+                provenance: ModProvenance::Local,
+                info: Ok(ModInfo {
+                    name: name.to_string(),
+                    description: String::new(),
+                    change_notes: String::new(),
+                    author: String::new(),
+                    author_url: None,
+                    version: ModVersion {
+                        major: 1,
+                        minor: 0,
+                        build: 0,
+                    },
+                    requires_load_on_startup: false,
+                    live_steam_file_id: None,
+                    testlive_steam_file_id: None,
+                    folder_name: folder_name.to_string(),
+                    required_mods: Vec::new(),
+                    devkit_revision: 0,
+                    devkit_snapshot: 0,
+                }),
+                needs_update: AtomicBool::new(false),
+                stats: Mutex::new(None),
+            });
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn by_folder_fuzzy_prefers_closest_folder() {
+        let mods = Mods::for_test(&[("Alpha", "AlphaMod"), ("Beta", "AlphaMods2")]);
+        assert_eq!(
+            mods.by_folder_fuzzy("AlphaMod_", 2),
+            Some(mods.by_folder("AlphaMod"))
+        );
+        assert_eq!(
+            mods.by_folder_fuzzy("AlphaMods2", 2),
+            Some(mods.by_folder("AlphaMods2"))
+        );
+    }
+
+    #[test]
+    fn by_folder_fuzzy_respects_max_distance() {
+        let mods = Mods::for_test(&[("Alpha", "AlphaMod")]);
+        assert_eq!(mods.by_folder_fuzzy("AlphaMod123", 2), None);
+        assert_eq!(
+            mods.by_folder_fuzzy("AlphaMod12", 2),
+            Some(mods.by_folder("AlphaMod"))
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::rc::Rc;
 
 use anyhow::Result;
@@ -8,11 +9,16 @@ use super::ModEntry;
 
 pub mod steam;
 
+pub type SubscribeCallback = Rc<dyn Fn(Result<PathBuf>)>;
+
 pub trait ModDirectory {
     fn resolve(self: Rc<Self>, mods: &mut [(u64, Option<String>)]);
     fn needs_update(self: Rc<Self>, entry: &ModEntry) -> Result<bool>;
     fn can_update(self: Rc<Self>) -> bool;
     fn start_update(self: Rc<Self>, entry: &ModEntry) -> Result<Rc<dyn ModUpdate>>;
+    fn subscribe(self: Rc<Self>, mod_id: u64, callback: SubscribeCallback) -> Result<()>;
+    fn download(self: Rc<Self>, mod_id: u64, callback: SubscribeCallback) -> Result<()>;
+    fn pending_downloads(self: Rc<Self>) -> Vec<u64>;
 }
 
 pub trait ModUpdate {
@@ -2,23 +2,26 @@ use std::cell::RefCell;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use keyvalues_parser::Vdf;
-use slog::{debug, o, Logger};
+use slog::{debug, info, o, Logger};
 use steamlocate::SteamDir;
 
 mod client;
 mod mod_directory;
+mod workshop;
 
 pub use self::client::{PlatformReady, SteamClient, SteamTicket};
 pub use self::mod_directory::SteamModDirectory;
+pub use self::workshop::fetch_mod_stats;
 use crate::bus::AppBus;
 use crate::game::{Branch, Game, ModLibraryBuilder, ModProvenance};
 use crate::util::PathExt;
 
 pub struct Steam {
     logger: Logger,
-    installation: SteamDir,
+    installation: Option<SteamDir>,
+    fallback_libraries: Vec<PathBuf>,
 }
 
 pub struct SteamGameLocation {
@@ -29,25 +32,85 @@ pub struct SteamGameLocation {
 }
 
 impl Steam {
-    pub fn locate(logger: &Logger) -> Option<Self> {
+    pub fn locate(logger: &Logger) -> Self {
         debug!(logger, "Locating Steam installation");
-        let installation = SteamDir::locate()?;
+        let installation = SteamDir::locate();
+        let fallback_libraries = fallback_library_paths();
+        debug!(logger, "Found fallback Steam libraries"; "paths" => ?fallback_libraries);
 
-        Some(Self {
+        Self {
             logger: logger.new(o!("platform" => "steam")),
             installation,
-        })
+            fallback_libraries,
+        }
+    }
+
+    pub fn is_installed(&self) -> bool {
+        self.installation.is_some() || !self.fallback_libraries.is_empty()
     }
 
     pub fn locate_game(&mut self, branch: Branch) -> Result<SteamGameLocation> {
         debug!(self.logger, "Locating game installation");
-        let app = self.installation.app(&app_id(branch)).ok_or_else(|| {
-            anyhow!(
+        let location = self
+            .locate_steam_app(branch)
+            .or_else(|| self.locate_fallback_game(branch));
+        let Some(location) = location else {
+            bail!(
                 "Cannot locate Conan Exiles installation. Please verify that you have Conan \
                     Exiles installed in a Steam library and try again."
-            )
-        })?;
+            );
+        };
+
+        info!(
+            self.logger,
+            "Located game installation";
+            "branch" => ?branch,
+            "path" => location.game_path.display(),
+        );
+        Ok(location)
+    }
+
+    pub fn locate_game_at(&self, game_path: PathBuf, branch: Branch) -> Result<SteamGameLocation> {
+        if !is_game_dir(&game_path) {
+            bail!(
+                "The game path {} does not contain a Conan Exiles installation.",
+                game_path.display()
+            );
+        }
+
+        // Steam keeps the workshop next to the "common" folder in the same library
+        let workshop_path = game_path
+            .parent()
+            .filter(|common| common.file_name() == Some("common".as_ref()))
+            .and_then(Path::parent)
+            .map(|steamapps| steamapps.join("workshop"));
+
+        info!(
+            self.logger,
+            "Using game installation from the command line";
+            "branch" => ?branch,
+            "path" => game_path.display(),
+        );
+        Ok(SteamGameLocation {
+            game_path,
+            workshop_path,
+            branch,
+            needs_update: false,
+        })
+    }
+
+    fn locate_steam_app(&mut self, branch: Branch) -> Option<SteamGameLocation> {
+        let installation = self.installation.as_mut()?;
+        let app = installation.app(&app_id(branch))?;
         let game_path = app.path.clone();
+        if !is_game_dir(&game_path) {
+            debug!(
+                self.logger,
+                "Steam reported a game path without the game executable";
+                "path" => game_path.display(),
+            );
+            return None;
+        }
         let needs_update = match &app.state_flags {
             None => false,
             Some(flags) => flags.into_iter().any(|flag| match flag {
@@ -57,15 +120,14 @@ impl Steam {
         };
 
         debug!(self.logger, "Determining the workshop path");
-        let workshop_path = self
-            .installation
+        let workshop_path = installation
             .libraryfolders()
             .paths
             .iter()
             .find(|path| game_path.starts_with(path))
             .map(|path| path.join("workshop"));
 
-        Ok(SteamGameLocation {
+        Some(SteamGameLocation {
             game_path,
             workshop_path,
             branch,
@@ -73,6 +135,33 @@ impl Steam {
         })
     }
 
+    fn locate_fallback_game(&self, branch: Branch) -> Option<SteamGameLocation> {
+        for steamapps in self.fallback_libraries.iter() {
+            debug!(self.logger, "Searching fallback Steam library"; "path" => steamapps.display());
+            let manifest_path = steamapps.join(format!("appmanifest_{}.acf", app_id(branch)));
+            let Ok(manifest) = std::fs::read_to_string(manifest_path) else {
+                continue;
+            };
+            let Ok(manifest) = Vdf::parse(&manifest) else {
+                continue;
+            };
+            let Some(install_dir) = manifest_install_dir(&manifest) else {
+                continue;
+            };
+
+            let game_path = steamapps.join_all(["common", install_dir]);
+            if is_game_dir(&game_path) {
+                return Some(SteamGameLocation {
+                    game_path,
+                    workshop_path: Some(steamapps.join("workshop")),
+                    branch,
+                    needs_update: false,
+                });
+            }
+        }
+        None
+    }
+
     pub fn init_game(&mut self, location: SteamGameLocation) -> Result<Game> {
         debug!(
             self.logger,
@@ -101,6 +190,28 @@ impl Steam {
     }
 }
 
+pub fn game_update_url(branch: Branch) -> String {
+    format!("steam://install/{}", app_id(branch))
+}
+
+pub fn game_validate_url(branch: Branch) -> String {
+    format!("steam://validate/{}", app_id(branch))
+}
+
+pub fn workshop_url(branch: Branch) -> String {
+    format!(
+        "https://steamcommunity.com/app/{}/workshop/",
+        app_id(branch)
+    )
+}
+
+pub fn workshop_item_url(mod_id: u64) -> String {
+    format!(
+        "https://steamcommunity.com/sharedfiles/filedetails/?id={}",
+        mod_id
+    )
+}
+
 fn app_id(branch: Branch) -> u32 {
     match branch {
         Branch::Live => 440900,
@@ -108,6 +219,52 @@ fn app_id(branch: Branch) -> u32 {
     }
 }
 
+fn is_game_dir(game_path: &Path) -> bool {
+    game_path
+        .join_all(["ConanSandbox", "Binaries", "Win64", "ConanSandbox.exe"])
+        .is_file()
+}
+
+fn fallback_library_paths() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+    if let Some(home) = home_dir() {
+        roots.push(home.join_all([".steam", "steam"]));
+        roots.push(home.join_all([".local", "share", "Steam"]));
+    }
+    #[cfg(windows)]
+    roots.push(PathBuf::from(r"C:\Program Files (x86)\Steam"));
+
+    roots
+        .into_iter()
+        .map(|root| root.join("steamapps"))
+        .filter(|path| path.is_dir())
+        .collect()
+}
+
+#[cfg(not(windows))]
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+// When running under Proton, HOME is inherited from the host, and the host file system is
+// visible through the Z: drive
+#[cfg(windows)]
+fn home_dir() -> Option<PathBuf> {
+    let home = PathBuf::from(std::env::var_os("HOME")?);
+    let relative = home.to_str()?.strip_prefix('/')?.to_owned();
+    Some(Path::new("Z:\\").join_all(relative.split('/')))
+}
+
+fn manifest_install_dir<'m>(manifest: &'m Vdf) -> Option<&'m str> {
+    manifest
+        .value
+        .get_obj()?
+        .get("installdir")?
+        .into_iter()
+        .next()?
+        .get_str()
+}
+
 fn collect_mods(workshop_path: &Path, branch: Branch) -> Result<ModLibraryBuilder> {
     let mut mods = ModLibraryBuilder::new();
     let manifest_path = workshop_path.join(format!("appworkshop_{}.acf", app_id(branch)));
@@ -1,6 +1,7 @@
 use std::cell::{RefCell, RefMut};
 use std::collections::HashMap;
 use std::marker::PhantomData;
+use std::path::PathBuf;
 use std::rc::Rc;
 
 use dynabus::mpsc::BusSender;
@@ -9,7 +10,7 @@ use fltk::app::{self, TimeoutHandle};
 use slog::{debug, o, trace, warn, Logger};
 use steamworks::networking_types::NetworkingIdentity;
 use steamworks::{
-    AuthTicket, CallbackHandle, Client, ClientManager, DownloadItemResult, ItemState,
+    AppId, AuthTicket, CallbackHandle, Client, ClientManager, DownloadItemResult, ItemState,
     PublishedFileId, SingleClient, SteamError, User,
 };
 use tokio::task::JoinHandle;
@@ -102,6 +103,20 @@ impl SteamClient {
         }
     }
 
+    pub fn dlc_ownership(
+        &self,
+        app_ids: impl IntoIterator<Item = u32>,
+    ) -> Option<HashMap<u32, bool>> {
+        let client = self.check_client()?;
+        let apps = client.apps();
+        Some(
+            app_ids
+                .into_iter()
+                .map(|app_id| (app_id, apps.is_subscribed_app(AppId(app_id))))
+                .collect(),
+        )
+    }
+
     pub fn user(&self) -> Option<PlatformUser> {
         self.check_client().as_ref().map(|client| PlatformUser {
             id: client.user().steam_id().raw().to_string(),
@@ -191,35 +206,84 @@ impl SteamClient {
         })
     }
 
+    pub fn subscribed_but_not_installed(&self) -> Option<Vec<u64>> {
+        let client = self.check_client()?;
+        let ugc = client.ugc();
+        Some(
+            ugc.subscribed_items()
+                .into_iter()
+                .filter(|&mod_id| !ugc.item_state(mod_id).contains(ItemState::INSTALLED))
+                .map(|mod_id| mod_id.0)
+                .collect(),
+        )
+    }
+
     pub fn start_mod_update(
         self: &Rc<Self>,
         mod_id: u64,
         callback: DownloadCallback,
+    ) -> Option<bool> {
+        self.start_download(mod_id, false, callback)
+    }
+
+    pub fn force_mod_download(
+        self: &Rc<Self>,
+        mod_id: u64,
+        callback: DownloadCallback,
+    ) -> Option<bool> {
+        self.start_download(mod_id, true, callback)
+    }
+
+    fn start_download(
+        self: &Rc<Self>,
+        mod_id: u64,
+        high_priority: bool,
+        callback: DownloadCallback,
     ) -> Option<bool> {
         let client = self.check_client()?;
         let mod_id = PublishedFileId(mod_id);
-        let success = client.ugc().download_item(mod_id, false);
+        let success = client.ugc().download_item(mod_id, high_priority);
         if success {
-            let mut downloads = self.downloads.borrow_mut();
-            downloads.dispatch_map.insert(mod_id, callback);
-            if downloads.api_callback_handle.is_none() {
-                let mut api_callback = CallbackWrapper(weak_cb!(
-                    [this = self] => |result| this.handle_download_result(result)
-                ));
-                downloads.api_callback_handle =
-                    Some(client.register_callback(move |result| api_callback.call_mut(result)));
-                self.callback_timer.borrow_mut().callback_pending();
-            }
+            self.register_download(&client, mod_id, callback);
         }
         Some(success)
     }
 
+    pub fn subscribe_mod(self: &Rc<Self>, mod_id: u64, callback: DownloadCallback) -> Option<()> {
+        let client = self.check_client()?;
+        let mod_id = PublishedFileId(mod_id);
+
+        // Steam starts downloading the item as soon as the subscription goes through, so the
+        // download result will be dispatched to the callback like for any other download.
+        self.register_download(&client, mod_id, callback);
+
+        let on_subscribed = CallbackWrapper(weak_cb!(
+            [this = self] => |result: Result<(), SteamError>| {
+                this.callback_timer.borrow_mut().callback_completed();
+                this.handle_subscribe_result(mod_id, result);
+            }
+        ));
+        client
+            .ugc()
+            .subscribe_item(mod_id, move |result| on_subscribed.call_once(result));
+        self.callback_timer.borrow_mut().callback_pending();
+
+        Some(())
+    }
+
     pub fn download_progress(&self, mod_id: u64) -> Option<(u64, u64)> {
         let file_id = PublishedFileId(mod_id);
         self.check_client()
             .and_then(|client| client.ugc().item_download_info(file_id))
     }
 
+    pub fn install_folder(&self, mod_id: u64) -> Option<PathBuf> {
+        let file_id = PublishedFileId(mod_id);
+        self.check_client()
+            .and_then(|client| client.ugc().item_install_info(file_id))
+            .map(|info| PathBuf::from(info.folder))
+    }
+
     pub fn run_callbacks(&self) {
         if let Some(api) = &*self.api.borrow() {
             api.cb_runner.run_callbacks();
@@ -242,11 +306,61 @@ impl SteamClient {
         RefMut::filter_map(api, |opt| opt.as_mut().map(|api| &mut api.client)).ok()
     }
 
-    fn handle_download_result(&self, result: DownloadItemResult) {
+    fn register_download(
+        self: &Rc<Self>,
+        client: &Client,
+        mod_id: PublishedFileId,
+        callback: DownloadCallback,
+    ) {
         let mut downloads = self.downloads.borrow_mut();
-        if let Some(callback) = downloads.dispatch_map.remove(&result.published_file_id) {
-            callback(result.error);
+        downloads.dispatch_map.insert(mod_id, callback);
+        if downloads.api_callback_handle.is_none() {
+            let mut api_callback = CallbackWrapper(weak_cb!(
+                [this = self] => |result| this.handle_download_result(result)
+            ));
+            downloads.api_callback_handle =
+                Some(client.register_callback(move |result| api_callback.call_mut(result)));
+            self.callback_timer.borrow_mut().callback_pending();
+        }
+    }
+
+    fn handle_subscribe_result(&self, mod_id: PublishedFileId, result: Result<(), SteamError>) {
+        if let Err(err) = result {
+            warn!(self.logger, "Error subscribing to mod"; "mod_id" => mod_id.0, "error" => %err);
+            self.dispatch_download_result(mod_id, Some(err));
+            return;
         }
+
+        // If the item was already installed and up to date, Steam won't download anything, so
+        // there will be no download result to wait for.
+        let up_to_date = match &*self.api.borrow() {
+            Some(api) => {
+                let state = api.client.ugc().item_state(mod_id);
+                state.contains(ItemState::INSTALLED)
+                    && !state.intersects(
+                        ItemState::NEEDS_UPDATE
+                            | ItemState::DOWNLOADING
+                            | ItemState::DOWNLOAD_PENDING,
+                    )
+            }
+            None => false,
+        };
+        if up_to_date {
+            self.dispatch_download_result(mod_id, None);
+        }
+    }
+
+    fn handle_download_result(&self, result: DownloadItemResult) {
+        self.dispatch_download_result(result.published_file_id, result.error);
+    }
+
+    fn dispatch_download_result(&self, mod_id: PublishedFileId, error: Option<SteamError>) {
+        let mut downloads = self.downloads.borrow_mut();
+        let callback = match downloads.dispatch_map.remove(&mod_id) {
+            Some(callback) => callback,
+            None => return,
+        };
+        callback(error);
         if downloads.dispatch_map.is_empty() {
             downloads.api_callback_handle = None;
             self.callback_timer.borrow_mut().callback_completed();
@@ -9,10 +9,11 @@ use steamworks::SteamError;
 
 use crate::bus::AppSender;
 use crate::game::platform::steam::client::DownloadCallback;
-use crate::game::platform::{ModDirectory, ModUpdate};
+use crate::game::platform::{ModDirectory, ModUpdate, SubscribeCallback};
 use crate::game::{ModEntry, ModProvenance, Mods};
 use crate::gui::RefreshServerDetails;
 use crate::logger::IteratorFormatter;
+use crate::mod_manager::ModNamesResolved;
 use crate::util::weak_cb;
 use crate::workers::TaskState;
 
@@ -78,10 +79,11 @@ impl ModDirectory for SteamModDirectory {
             let tx = self.tx.clone();
             self.client.query_mods(mod_ids, move |results| {
                 let mut map = this.map.borrow_mut();
-                for (id, name) in results {
-                    map.insert(id, name);
+                for (id, name) in results.iter() {
+                    map.insert(*id, name.clone());
                 }
                 tx.send(RefreshServerDetails).ok();
+                tx.send(ModNamesResolved(results)).ok();
             });
         }
     }
@@ -130,6 +132,29 @@ impl ModDirectory for SteamModDirectory {
             bail!("Error starting the mod update download");
         }
     }
+
+    fn subscribe(self: Rc<Self>, mod_id: u64, callback: SubscribeCallback) -> Result<()> {
+        let callback = install_callback(&self.client, mod_id, callback);
+        self.client
+            .subscribe_mod(mod_id, callback)
+            .ok_or_else(|| anyhow!("Steam not running"))
+    }
+
+    fn download(self: Rc<Self>, mod_id: u64, callback: SubscribeCallback) -> Result<()> {
+        let callback = install_callback(&self.client, mod_id, callback);
+        let success = self.client.force_mod_download(mod_id, callback);
+        if success.ok_or_else(|| anyhow!("Steam not running"))? {
+            Ok(())
+        } else {
+            bail!("Error starting the mod download");
+        }
+    }
+
+    fn pending_downloads(self: Rc<Self>) -> Vec<u64> {
+        self.client
+            .subscribed_but_not_installed()
+            .unwrap_or_default()
+    }
 }
 
 struct SteamModUpdate {
@@ -151,3 +176,19 @@ impl ModUpdate for SteamModUpdate {
         }
     }
 }
+
+fn install_callback(
+    client: &Rc<SteamClient>,
+    mod_id: u64,
+    callback: SubscribeCallback,
+) -> DownloadCallback {
+    let client = Rc::clone(client);
+    Rc::new(move |error: Option<SteamError>| {
+        callback(match error {
+            None => client
+                .install_folder(mod_id)
+                .ok_or_else(|| anyhow!("Mod is not installed")),
+            Some(err) => Err(err.into()),
+        })
+    })
+}
@@ -0,0 +1,65 @@
+use anyhow::{bail, Result};
+use chrono::DateTime;
+use serde::Deserialize;
+
+use crate::game::{Game, ModStats};
+use crate::net::http_client_builder;
+
+pub async fn fetch_mod_stats(game: &Game, mod_id: u64) -> Result<ModStats> {
+    let mod_id = mod_id.to_string();
+    let response = http_client_builder(game)
+        .build()?
+        .post(URL_PUBLISHED_FILE_DETAILS)
+        .form(&[("itemcount", "1"), ("publishedfileids[0]", mod_id.as_str())])
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<DetailsResponse>()
+        .await?;
+
+    let Some(details) = response.response.details.into_iter().next() else {
+        bail!("Missing details for workshop item {}", mod_id);
+    };
+    if details.result != RESULT_OK {
+        bail!(
+            "Details for workshop item {} not available (result {})",
+            mod_id,
+            details.result
+        );
+    }
+
+    Ok(ModStats {
+        subscribers: details.subscriptions,
+        favorites: details.favorited,
+        last_updated: DateTime::from_timestamp(details.time_updated, 0),
+    })
+}
+
+#[derive(Deserialize)]
+struct DetailsResponse {
+    response: DetailsList,
+}
+
+#[derive(Deserialize)]
+struct DetailsList {
+    #[serde(rename = "publishedfiledetails", default)]
+    details: Vec<FileDetails>,
+}
+
+#[derive(Deserialize)]
+struct FileDetails {
+    result: u32,
+
+    #[serde(default)]
+    subscriptions: u64,
+
+    #[serde(default)]
+    favorited: u64,
+
+    #[serde(default)]
+    time_updated: i64,
+}
+
+const URL_PUBLISHED_FILE_DETAILS: &str =
+    "https://api.steampowered.com/ISteamRemoteStorage/GetPublishedFileDetails/v1/";
+const RESULT_OK: u32 = 1;
@@ -21,6 +21,7 @@ mod dialog;
 pub mod glyph;
 mod home;
 mod launcher;
+mod log_viewer;
 mod main_menu;
 mod mod_manager;
 mod mod_update;
@@ -31,15 +32,21 @@ mod single_player;
 mod svg_symbol;
 mod task_progress_monitor;
 pub mod theme;
+mod tray;
+mod whats_new_dialog;
 mod widgets;
 
 pub use self::dialog::Dialog;
-pub use self::home::{UpdateAuthState, UpdateLastSession};
-pub use self::launcher::LauncherWindow;
+pub use self::home::{UpdateAuthState, UpdateLastSession, UpdateLaunchStats};
+pub use self::launcher::{LauncherServices, LauncherWindow, LogSettings};
 pub use self::mod_update::{ModUpdateProgressDialog, ModUpdateSelectionDialog};
-pub use self::server_browser::{PopulateServers, ProcessPongs, RefreshServerDetails, UpdateServer};
+pub use self::server_browser::{
+    AppendServers, PopulateServers, ProcessPongs, RefreshServerDetails, UpdateServer,
+};
 pub use self::single_player::PopulateSinglePlayerGames;
 pub use self::task_progress_monitor::{TaskProgressMonitor, TaskProgressUpdate};
+pub use self::tray::{is_minimized_to_tray, minimize_to_tray, tray_supported};
+pub use self::whats_new_dialog::WhatsNewDialog;
 
 pub fn alert_error(message: &str, err: &anyhow::Error) {
     fltk_dialog::alert_default(&format!("{}\n{}", message, err));
@@ -11,6 +11,7 @@ use super::wrapper_factory;
 
 pub struct Dialog<T: Copy + 'static> {
     window: Window,
+    message: Frame,
     result: Rc<Cell<Option<T>>>,
 }
 
@@ -46,7 +47,8 @@ impl<T: Copy + 'static> Dialog<T> {
             .with_stretch(1)
             .with_default_align(CellAlign::Stretch)
             .add();
-        grid.cell()
+        let message = grid
+            .cell()
             .unwrap()
             .wrap(Frame::default_fill())
             .with_label(message);
@@ -93,7 +95,15 @@ impl<T: Copy + 'static> Dialog<T> {
             parent.y() + (parent.h() - window.h()) / 2,
         );
 
-        Self { window, result }
+        Self {
+            window,
+            message,
+            result,
+        }
+    }
+
+    pub fn set_message(&self, message: &str) {
+        self.message.clone().set_label(message);
     }
 
     pub fn show(&self) {
@@ -15,6 +15,7 @@ pub fn add_symbols() {
     fltk::app::add_symbol("floppy_fill", true, draw_svg_symbol!(SVG_FLOPPY_FILL)).unwrap();
     fltk::app::add_symbol("folder", true, draw_svg_symbol!(SVG_FOLDER)).unwrap();
     fltk::app::add_symbol("folder_open", true, draw_svg_symbol!(SVG_FOLDER_OPEN)).unwrap();
+    fltk::app::add_symbol("funnel", true, draw_svg_symbol!(SVG_FUNNEL)).unwrap();
     fltk::app::add_symbol("heart", true, draw_svg_symbol!(SVG_HEART)).unwrap();
     fltk::app::add_symbol("info", true, draw_svg_symbol!(SVG_INFO)).unwrap();
     fltk::app::add_symbol("lock", true, draw_svg_symbol!(SVG_LOCK)).unwrap();
@@ -24,18 +25,24 @@ pub fn add_symbols() {
     fltk::app::add_symbol("sort_no", true, draw_svg_symbol!(SVG_SORT_NO)).unwrap();
     fltk::app::add_symbol("steam", true, draw_svg_symbol!(SVG_STEAM)).unwrap();
     fltk::app::add_symbol("tools", true, draw_svg_symbol!(SVG_TOOLS)).unwrap();
+    fltk::app::add_symbol("wrench", true, draw_svg_symbol!(SVG_WRENCH)).unwrap();
 }
 
 pub const BATTLEYE: &str = "@-1eye";
+pub const DOWNLOAD: &str = "@cloud_download";
 pub const ERROR: &str = "@-1error";
 pub const FAVORITE: &str = "@-1heart";
 pub const LOCK: &str = "@-1lock";
+pub const MAINTENANCE: &str = "@-1wrench";
 pub const OFFICIAL: &str = "@-1flag";
 pub const RELOAD: &str = "@arrow_repeat";
+pub const REVEAL: &str = "@-1eye";
 pub const SAVED: &str = "@-1floppy_fill";
 pub const SORT_ASC: &str = "@sort_asc";
 pub const SORT_DESC: &str = "@sort_desc";
 pub const TOOLS: &str = "@-1tools";
+pub const TREND_DOWN: &str = "@-22>";
+pub const TREND_UP: &str = "@-28>";
 pub const UNSORTED: &str = "@sort_no";
 
 mod bootstrap_icons {
@@ -148,6 +155,12 @@ mod bootstrap_icons {
     </svg>
     "###;
 
+    pub(super) const SVG_FUNNEL: &str = r###"
+    <svg xmlns="http://www.w3.org/2000/svg" width="16" height="16" fill="currentColor" class="bi bi-funnel-fill" viewBox="0 0 16 16">
+      <path d="M1.5 1.5A.5.5 0 0 1 2 1h12a.5.5 0 0 1 .5.5v2a.5.5 0 0 1-.128.334L10 8.692V13.5a.5.5 0 0 1-.342.474l-3 1A.5.5 0 0 1 6 14.5V8.692L1.628 3.834A.5.5 0 0 1 1.5 3.5z"/>
+    </svg>
+    "###;
+
     pub(super) const SVG_HEART: &str = r###"
     <svg xmlns="http://www.w3.org/2000/svg" width="16" height="16" fill="currentColor" class="bi bi-suit-heart-fill" viewBox="0 0 16 16">
       <path d="M4 1c2.21 0 4 1.755 4 3.92C8 2.755 9.79 1 12 1s4 1.755 4 3.92c0 3.263-3.234 4.414-7.608 9.608a.513.513 0 0 1-.784 0C3.234 9.334 0 8.183 0 4.92 0 2.755 1.79 1 4 1"/>
@@ -204,4 +217,10 @@ mod bootstrap_icons {
       <path d="M1 0 0 1l2.2 3.081a1 1 0 0 0 .815.419h.07a1 1 0 0 1 .708.293l2.675 2.675-2.617 2.654A3.003 3.003 0 0 0 0 13a3 3 0 1 0 5.878-.851l2.654-2.617.968.968-.305.914a1 1 0 0 0 .242 1.023l3.27 3.27a.997.997 0 0 0 1.414 0l1.586-1.586a.997.997 0 0 0 0-1.414l-3.27-3.27a1 1 0 0 0-1.023-.242L10.5 9.5l-.96-.96 2.68-2.643A3.005 3.005 0 0 0 16 3q0-.405-.102-.777l-2.14 2.141L12 4l-.364-1.757L13.777.102a3 3 0 0 0-3.675 3.68L7.462 6.46 4.793 3.793a1 1 0 0 1-.293-.707v-.071a1 1 0 0 0-.419-.814zm9.646 10.646a.5.5 0 0 1 .708 0l2.914 2.915a.5.5 0 0 1-.707.707l-2.915-2.914a.5.5 0 0 1 0-.708M3 11l.471.242.529.026.287.445.445.287.026.529L5 13l-.242.471-.026.529-.445.287-.287.445-.529.026L3 15l-.471-.242L2 14.732l-.287-.445L1.268 14l-.026-.529L1 13l.242-.471.026-.529.445-.287.287-.445.529-.026z"/>
     </svg>
     "###;
+
+    pub(super) const SVG_WRENCH: &str = r###"
+    <svg xmlns="http://www.w3.org/2000/svg" width="16" height="16" fill="currentColor" class="bi bi-wrench" viewBox="0 0 16 16">
+      <path d="M.102 2.223A3.004 3.004 0 0 0 3.78 5.897l6.341 6.252A3.003 3.003 0 0 0 13 16a3 3 0 1 0-.851-5.878L5.897 3.781A3.004 3.004 0 0 0 2.223.1l2.141 2.142L4 4l-1.757.364zm13.37 9.019.528.026.287.445.445.287.026.529L15 13l-.242.471-.026.529-.445.287-.287.445-.529.026L13 15l-.471-.242-.529-.026-.287-.445-.445-.287-.026-.529L11 13l.242-.471.026-.529.445-.287.287-.445.529-.026L13 11z"/>
+    </svg>
+    "###;
 }
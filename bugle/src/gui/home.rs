@@ -1,55 +1,107 @@
 use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
+use std::time::Duration;
 
+use anyhow::Result;
+use dynabus::mpsc::BusSender;
 use dynabus::Bus;
 use fltk::button::{Button, CheckButton, LightButton};
-use fltk::enums::{Align, CallbackTrigger, Color, Event, FrameType};
+use fltk::dialog::{FileDialogOptions, FileDialogType, NativeFileChooser};
+use fltk::enums::{Align, CallbackTrigger, Color, Event, FrameType, Shortcut};
 use fltk::frame::Frame;
 use fltk::group::Group;
-use fltk::input::Input;
+use fltk::input::{Input, SecretInput};
+use fltk::menu::{MenuButton, MenuFlag};
+use fltk::misc::{Spinner, SpinnerType};
 use fltk::prelude::*;
 use fltk_float::button::ButtonElement;
-use fltk_float::grid::Grid;
-use fltk_float::{LayoutElement, LayoutWidgetWrapper};
-use slog::{error, FilterLevel, Logger};
+use fltk_float::grid::{CellAlign, Grid};
+use fltk_float::{LayoutElement, LayoutWidgetWrapper, SimpleWrapper};
+use slog::{error, warn, FilterLevel, Logger};
 
-use crate::auth::AuthState;
+use crate::auth::{playfab, AuthState};
 use crate::auth_manager::AuthManager;
-use crate::bus::AppBus;
-use crate::config::{BattlEyeUsage, ConfigManager, LogLevel, ModMismatchChecks, ThemeChoice};
+use crate::bus::{AppBus, AppSender};
+use crate::config::{
+    BattlEyeUsage, ConfigManager, LogLevel, ModMismatchChecks, SecurePasswords, ThemeChoice,
+};
+use crate::diagnostics;
 use crate::env;
+use crate::game::platform::steam::game_validate_url;
 use crate::game::{Branch, Game, MapRef, Maps, ServerRef, Session};
+use crate::launch_history::{launch_history_path, load_launch_history};
 use crate::launcher::Launcher;
+use crate::net::{http_client_builder, measure_head_request};
 use crate::util::weak_cb;
 use crate::workers::TaskState;
 
 use super::assets::Assets;
 use super::prelude::*;
-use super::theme::Theme;
+use super::theme::{Theme, ThemePreviewDialog};
 use super::widgets::{DropDownList, ReadOnlyText};
-use super::{alert_error, wrapper_factory};
+use super::{alert_error, prompt_confirm, tray_supported, wrapper_factory};
+
+mod blocked_servers_dialog;
+mod changelog_window;
+mod extra_args_presets_dialog;
+mod launch_history_dialog;
+mod news_pane;
+
+use self::blocked_servers_dialog::BlockedServersDialog;
+use self::changelog_window::{
+    fetch_changelog_feed, render_changelog, ChangelogWindow, FALLBACK_HTML,
+};
+use self::extra_args_presets_dialog::ExtraArgsPresetsDialog;
+use self::launch_history_dialog::LaunchHistoryDialog;
+use self::news_pane::NewsPane;
 
 #[derive(dynabus::Event)]
 pub struct UpdateLastSession;
 
+#[derive(dynabus::Event)]
+pub struct UpdateLaunchStats;
+
 #[derive(dynabus::Event)]
 pub struct UpdateAuthState(pub AuthState);
 
+#[derive(dynabus::Event)]
+struct ChangelogLoaded(Result<String>);
+
+#[derive(dynabus::Event)]
+struct FLSConnectivityResult {
+    fls: Result<Duration>,
+    steam: Result<Duration>,
+}
+
 pub struct HomeTab {
+    logger: Logger,
     grid: Grid,
     root: Group,
     game: Arc<Game>,
+    config: Rc<ConfigManager>,
+    log_path: Option<PathBuf>,
+    platform_user_id: RefCell<Option<String>>,
+    steam_status_dot: Frame,
+    steam_status_label: Frame,
     platform_user_id_text: ReadOnlyText,
     platform_user_name_text: ReadOnlyText,
     refresh_platform_button: Button,
     fls_acct_id_text: ReadOnlyText,
     fls_acct_name_text: ReadOnlyText,
     refresh_fls_button: Button,
+    connection_test_label: Frame,
+    connection_test_text: ReadOnlyText,
+    test_connection_button: Button,
     online_play_text: ReadOnlyText,
     sp_play_text: ReadOnlyText,
     last_session_text: ReadOnlyText,
+    crash_rate_text: ReadOnlyText,
+    tx: BusSender<AppSender>,
+    game_updates_button: LightButton,
+    changelog_window: RefCell<Option<ChangelogWindow>>,
 }
 
 impl HomeTab {
@@ -59,6 +111,7 @@ impl HomeTab {
         game: Arc<Game>,
         config: Rc<ConfigManager>,
         log_level: Option<Arc<AtomicUsize>>,
+        log_path: Option<PathBuf>,
         auth: Rc<AuthManager>,
         launcher: Rc<Launcher>,
         can_switch_branch: bool,
@@ -106,9 +159,15 @@ impl HomeTab {
         grid.cell()
             .unwrap()
             .wrap(create_info_label("Conan Exiles Installation Path:"));
-        grid.span(1, 4).unwrap().wrap(ReadOnlyText::new(
+        grid.span(1, 3).unwrap().wrap(ReadOnlyText::new(
             game.installation_path().to_string_lossy().into_owned(),
         ));
+        let mut verify_button = grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Verify Files")
+            .with_tooltip("Ask Steam to verify the integrity of the game files");
 
         grid.row().add();
         grid.cell()
@@ -125,6 +184,36 @@ impl HomeTab {
             .unwrap()
             .wrap(ReadOnlyText::new(format!("{}", game.build_id())));
 
+        grid.row().add();
+        grid.cell()
+            .unwrap()
+            .wrap(create_info_label("Steam Status:"));
+        let mut steam_status_grid =
+            Grid::builder_with_factory(wrapper_factory()).with_col_spacing(6);
+        steam_status_grid.col().add();
+        steam_status_grid.col().with_stretch(1).add();
+        steam_status_grid.row().add();
+        let mut steam_status_dot = Frame::default();
+        steam_status_dot.set_frame(FrameType::OFlatBox);
+        steam_status_dot.set_color(STEAM_STATUS_OFFLINE_COLOR);
+        steam_status_grid
+            .cell()
+            .unwrap()
+            .with_vert_align(CellAlign::Center)
+            .add(SimpleWrapper::new(
+                steam_status_dot.clone(),
+                fltk_float::Size {
+                    width: STEAM_STATUS_DOT_SIZE,
+                    height: STEAM_STATUS_DOT_SIZE,
+                },
+            ));
+        let steam_status_label = steam_status_grid
+            .cell()
+            .unwrap()
+            .wrap(Frame::default())
+            .with_align(Align::Left | Align::Inside);
+        grid.span(1, 4).unwrap().add(steam_status_grid.end());
+
         grid.row().add();
         grid.cell()
             .unwrap()
@@ -165,6 +254,22 @@ impl HomeTab {
             .wrap(create_info_label("Can Play Singleplayer?"));
         let sp_play_text = grid.span(1, 2).unwrap().wrap(ReadOnlyText::default());
 
+        grid.row().add();
+        let mut connection_test_label = grid
+            .cell()
+            .unwrap()
+            .wrap(create_info_label("Connection Test:"));
+        let mut connection_test_text = grid.span(1, 3).unwrap().wrap(ReadOnlyText::default());
+        let mut test_connection_button = grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Test Connection")
+            .with_tooltip("Check whether FLS and Steam can be reached from this computer");
+        connection_test_label.hide();
+        connection_test_text.hide();
+        test_connection_button.hide();
+
         grid.row().add();
         grid.cell()
             .unwrap()
@@ -204,11 +309,70 @@ impl HomeTab {
         let mut use_all_cores_button = grid.span(1, 2).unwrap().wrap(CheckButton::default());
         use_all_cores_button.clear_visible_focus();
 
+        grid.row().add();
+        grid.span(1, 2).unwrap().skip();
+        grid.cell()
+            .unwrap()
+            .wrap(create_info_label("Windowed mode:"));
+        let mut windowed_button = grid.span(1, 2).unwrap().wrap(CheckButton::default());
+        windowed_button.clear_visible_focus();
+
         grid.row().add();
         grid.cell()
             .unwrap()
             .wrap(create_info_label("Additional Launch Options:"));
-        let mut extra_args_input = grid.span(1, 4).unwrap().wrap(Input::default());
+        let mut extra_args_input = grid.span(1, 3).unwrap().wrap(Input::default());
+        let mut extra_args_presets_button = grid
+            .cell()
+            .unwrap()
+            .wrap(MenuButton::default())
+            .with_label("Presets")
+            .with_tooltip("Use or manage saved launch options presets");
+        extra_args_presets_button.clear_visible_focus();
+
+        grid.row().add();
+        grid.cell()
+            .unwrap()
+            .wrap(create_info_label("News Feed URL:"));
+        let mut news_url_input = grid.span(1, 3).unwrap().wrap(Input::default());
+        let mut news_button = grid
+            .cell()
+            .unwrap()
+            .wrap(LightButton::default())
+            .with_label("Show News");
+        news_button.clear_visible_focus();
+
+        grid.row().add();
+        grid.span(1, 4).unwrap().skip();
+        let mut game_updates_button = grid
+            .cell()
+            .unwrap()
+            .wrap(LightButton::default())
+            .with_label("Game Updates")
+            .with_tooltip("Show the official update notes for Conan Exiles");
+        game_updates_button.clear_visible_focus();
+
+        grid.row().add();
+        grid.cell()
+            .unwrap()
+            .wrap(create_info_label("Server List URL:"));
+        let mut server_list_url_input = grid.span(1, 4).unwrap().wrap(Input::default());
+        server_list_url_input.set_tooltip(
+            "URL of a private server list to use instead of the official one. Leave empty to \
+             use the official server list.",
+        );
+
+        grid.row().add();
+        grid.cell()
+            .unwrap()
+            .wrap(create_info_label("Server List User:"));
+        let mut server_list_user_input = grid.cell().unwrap().wrap(Input::default());
+        server_list_user_input
+            .set_tooltip("User name for the private server list, if it requires authentication");
+        grid.cell()
+            .unwrap()
+            .wrap(create_info_label("Server List Password:"));
+        let mut server_list_password_input = grid.span(1, 2).unwrap().wrap(SecretInput::default());
 
         grid.row().add();
         grid.span(1, 5)
@@ -229,9 +393,16 @@ impl HomeTab {
         log_level_input.add("Error");
         log_level_input.add("Critical");
         grid.cell().unwrap().wrap(create_info_label("Theme:"));
-        let mut theme_input = grid.span(1, 2).unwrap().wrap(DropDownList::default_fill());
+        let mut theme_input = grid.cell().unwrap().wrap(DropDownList::default_fill());
         theme_input.add("Light");
         theme_input.add("Dark");
+        theme_input.add("System");
+        let mut theme_preview_button = grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Preview...")
+            .with_tooltip("Compare the available themes side by side");
 
         grid.row().add();
         grid.cell()
@@ -241,6 +412,70 @@ impl HomeTab {
         mod_mismatch_check_button.clear_visible_focus();
         grid.span(1, 3).unwrap().skip();
 
+        grid.row().add();
+        grid.cell()
+            .unwrap()
+            .wrap(create_info_label("Auto-activate Server Mods:"));
+        let mut auto_activate_mods_button = grid
+            .cell()
+            .unwrap()
+            .wrap(CheckButton::default())
+            .with_tooltip("Activate the mods required by a server when joining it");
+        auto_activate_mods_button.clear_visible_focus();
+        let minimize_on_launch_button = if tray_supported() {
+            grid.cell()
+                .unwrap()
+                .wrap(create_info_label("Minimize to tray on launch:"));
+            let mut button = grid.span(1, 2).unwrap().wrap(CheckButton::default());
+            button.clear_visible_focus();
+            Some(button)
+        } else {
+            grid.span(1, 3).unwrap().skip();
+            None
+        };
+
+        grid.row().add();
+        grid.cell()
+            .unwrap()
+            .wrap(create_info_label("Low Disk Space Warning (GB):"));
+        let mut min_free_space_input = Spinner::default();
+        min_free_space_input.set_type(SpinnerType::Float);
+        min_free_space_input.set_range(0.0, 1000.0);
+        min_free_space_input.set_step(0.5);
+        min_free_space_input.set_tooltip(
+            "Warn before launching or backing up a single player game if less than this much \
+                disk space is left for saved games",
+        );
+        grid.cell().unwrap().add(SimpleWrapper::new(
+            min_free_space_input.clone(),
+            Default::default(),
+        ));
+        grid.span(1, 2).unwrap().skip();
+        let mut blocked_servers_button = grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Blocked Servers...")
+            .with_tooltip("Manage the servers hidden from the server browser");
+
+        grid.row().add();
+        grid.span(1, 4).unwrap().skip();
+        let mut export_diagnostics_button = grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Export Diagnostics...")
+            .with_tooltip("Save a report with information useful for troubleshooting");
+
+        grid.row().add();
+        grid.span(1, 4).unwrap().skip();
+        let mut launch_history_button = grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Launch History...")
+            .with_tooltip("Show the recent game launches and the mods that were active");
+
         grid.row().with_stretch(1).add();
         grid.span(1, 5).unwrap().skip();
 
@@ -261,6 +496,24 @@ impl HomeTab {
             .cell()
             .unwrap()
             .wrap(ReadOnlyText::new(last_session_text(&*game)));
+        last_session_grid.row().add();
+        last_session_grid
+            .cell()
+            .unwrap()
+            .wrap(create_info_label("Sessions Started:"));
+        last_session_grid
+            .cell()
+            .unwrap()
+            .wrap(ReadOnlyText::new(config.get().session_count.to_string()));
+        last_session_grid.row().add();
+        last_session_grid
+            .cell()
+            .unwrap()
+            .wrap(create_info_label("Crash Rate:"));
+        let crash_rate_text = last_session_grid
+            .cell()
+            .unwrap()
+            .wrap(ReadOnlyText::default());
         let last_session_grid = last_session_grid.end();
 
         action_grid.row().add();
@@ -297,6 +550,13 @@ impl HomeTab {
         grid.row().add();
         grid.span(1, 5).unwrap().add(action_grid);
 
+        grid.row().with_stretch(1).add();
+        let news_pane = NewsPane::new();
+        grid.span(1, 5)
+            .unwrap()
+            .with_vert_align(CellAlign::Stretch)
+            .add_shared(Rc::<NewsPane>::clone(&news_pane));
+
         let grid = grid.end();
         grid.layout_children();
 
@@ -340,6 +600,14 @@ impl HomeTab {
             }
         });
 
+        windowed_button.set_checked(config.get().windowed);
+        windowed_button.set_callback({
+            let config = Rc::clone(&config);
+            move |input| {
+                config.update(|config| config.windowed = input.is_checked());
+            }
+        });
+
         extra_args_input.set_value(&config.get().extra_args);
         let extra_args_dirty = Rc::new(Cell::new(false));
         extra_args_input.set_trigger(CallbackTrigger::Changed);
@@ -359,6 +627,107 @@ impl HomeTab {
             }
         });
 
+        extra_args_presets_button.handle({
+            let config = Rc::clone(&config);
+            let extra_args_input = extra_args_input.clone();
+            move |menu, event| {
+                if let Event::Push = event {
+                    populate_extra_args_presets(menu, &config, &extra_args_input);
+                }
+                false
+            }
+        });
+
+        news_url_input.set_value(config.get().news_feed_url.as_deref().unwrap_or_default());
+        let news_url_dirty = Rc::new(Cell::new(false));
+        news_url_input.set_trigger(CallbackTrigger::Changed);
+        news_url_input.set_callback({
+            let news_url_dirty = Rc::clone(&news_url_dirty);
+            move |_| news_url_dirty.set(true)
+        });
+        news_url_input.handle({
+            let config = Rc::clone(&config);
+            move |input, event| {
+                if let Event::Unfocus | Event::Hide = event {
+                    if news_url_dirty.take() {
+                        let url = input.value();
+                        let url = if url.trim().is_empty() { None } else { Some(url) };
+                        config.update(|config| config.news_feed_url = url);
+                    }
+                }
+                false
+            }
+        });
+
+        server_list_url_input.set_value(
+            config
+                .get()
+                .server_browser
+                .server_list_url
+                .as_deref()
+                .unwrap_or_default(),
+        );
+        let server_list_url_dirty = Rc::new(Cell::new(false));
+        server_list_url_input.set_trigger(CallbackTrigger::Changed);
+        server_list_url_input.set_callback({
+            let server_list_url_dirty = Rc::clone(&server_list_url_dirty);
+            move |_| server_list_url_dirty.set(true)
+        });
+        server_list_url_input.handle({
+            let config = Rc::clone(&config);
+            move |input, event| {
+                if let Event::Unfocus | Event::Hide = event {
+                    if server_list_url_dirty.take() {
+                        let url = input.value();
+                        let url = if url.trim().is_empty() { None } else { Some(url) };
+                        config.update(|config| config.server_browser.server_list_url = url);
+                    }
+                }
+                false
+            }
+        });
+
+        let server_list_auth = match config.get().server_browser.server_list_auth() {
+            Ok(auth) => auth,
+            Err(err) => {
+                error!(logger, "Error reading server list credentials"; "error" => %err);
+                None
+            }
+        };
+        if let Some((user, password)) = server_list_auth {
+            server_list_user_input.set_value(&user);
+            server_list_password_input.set_value(&password);
+        }
+        let server_list_auth_dirty = Rc::new(Cell::new(false));
+        bind_server_list_auth_input(
+            logger,
+            &mut server_list_user_input.clone(),
+            &config,
+            &server_list_auth_dirty,
+            &server_list_user_input,
+            &server_list_password_input,
+        );
+        bind_server_list_auth_input(
+            logger,
+            &mut server_list_password_input.clone(),
+            &config,
+            &server_list_auth_dirty,
+            &server_list_user_input,
+            &server_list_password_input,
+        );
+
+        news_button.set_callback({
+            let config = Rc::clone(&config);
+            move |button| {
+                let url = config
+                    .get()
+                    .news_feed_url
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_NEWS_URL.to_string());
+                news_pane.set_expanded(button.value(), &url);
+            }
+        });
+
         mod_mismatch_check_button.set_checked(match config.get().mod_mismatch_checks {
             ModMismatchChecks::Enabled => true,
             ModMismatchChecks::Disabled => false,
@@ -375,6 +744,53 @@ impl HomeTab {
             }
         });
 
+        auto_activate_mods_button.set_checked(config.get().auto_activate_server_mods);
+        auto_activate_mods_button.set_callback({
+            let config = Rc::clone(&config);
+            move |input| {
+                config.update(|config| config.auto_activate_server_mods = input.is_checked());
+            }
+        });
+
+        if let Some(mut button) = minimize_on_launch_button {
+            button.set_checked(config.get().minimize_on_launch);
+            button.set_callback({
+                let config = Rc::clone(&config);
+                move |input| {
+                    config.update(|config| config.minimize_on_launch = input.is_checked());
+                }
+            });
+        }
+
+        min_free_space_input.set_value(config.get().min_free_space_gb.0 as _);
+        min_free_space_input.set_callback({
+            let config = Rc::clone(&config);
+            move |input| {
+                config.update(|config| config.min_free_space_gb.0 = input.value() as _);
+            }
+        });
+
+        blocked_servers_button.set_callback({
+            let config = Rc::clone(&config);
+            move |_| {
+                let parent = fltk::app::first_window().unwrap();
+                let blocked = config
+                    .get()
+                    .server_browser
+                    .blocked_servers
+                    .iter()
+                    .copied()
+                    .collect();
+                let dialog = BlockedServersDialog::new(&parent, blocked);
+                if let Some(blocked) = dialog.run() {
+                    config.update(|config| {
+                        config.server_browser.blocked_servers.0 =
+                            Arc::new(blocked.into_iter().collect())
+                    });
+                }
+            }
+        });
+
         log_level_input.set_value(log_level_to_index(&config.get().log_level));
         log_level_input.set_activated(log_level.is_some());
         log_level_input.set_callback({
@@ -394,6 +810,7 @@ impl HomeTab {
         theme_input.set_value(match config.get().theme {
             ThemeChoice::Light => 0,
             ThemeChoice::Dark => 1,
+            ThemeChoice::System => 2,
         });
         theme_input.set_callback({
             let config = Rc::clone(&config);
@@ -401,12 +818,30 @@ impl HomeTab {
                 let theme = match input.value() {
                     0 => ThemeChoice::Light,
                     1 => ThemeChoice::Dark,
+                    2 => ThemeChoice::System,
                     _ => unreachable!(),
                 };
                 Theme::from_config(theme).apply();
                 config.update(|config| config.theme = theme);
             }
         });
+        theme_preview_button.set_callback({
+            let config = Rc::clone(&config);
+            let mut theme_input = theme_input.clone();
+            move |_| {
+                let parent = fltk::app::first_window().unwrap();
+                let dialog = ThemePreviewDialog::new(&parent, config.get().theme);
+                if let Some(theme) = dialog.run() {
+                    theme_input.set_value(match theme {
+                        ThemeChoice::Light => 0,
+                        ThemeChoice::Dark => 1,
+                        ThemeChoice::System => 2,
+                    });
+                    Theme::from_config(theme).apply();
+                    config.update(|config| config.theme = theme);
+                }
+            }
+        });
 
         privacy_switch.clear_visible_focus();
 
@@ -436,6 +871,23 @@ impl HomeTab {
             }
         });
 
+        verify_button.set_callback({
+            let logger = logger.clone();
+            let branch = game.branch();
+            move |_| {
+                if !prompt_confirm(PROMPT_VERIFY_FILES) {
+                    return;
+                }
+                match env::open_url(&game_validate_url(branch)) {
+                    Ok(()) => fltk::dialog::message_default(MSG_VERIFY_REQUESTED),
+                    Err(err) => {
+                        error!(logger, "Error requesting game file verification"; "error" => %err);
+                        alert_error(ERR_VERIFYING_FILES, &err.into());
+                    }
+                }
+            }
+        });
+
         launch_button.set_callback({
             let launcher = Rc::clone(&launcher);
             let logger = logger.clone();
@@ -488,27 +940,57 @@ impl HomeTab {
         let _ = launch_button.take_focus();
 
         let this = Rc::new(Self {
+            logger: logger.clone(),
             grid,
             root,
             game,
+            config,
+            log_path,
+            platform_user_id: RefCell::new(None),
+            steam_status_dot,
+            steam_status_label,
             platform_user_id_text,
             platform_user_name_text,
             refresh_platform_button: refresh_platform_button.clone(),
             fls_acct_id_text,
             fls_acct_name_text,
             refresh_fls_button: refresh_fls_button.clone(),
+            connection_test_label,
+            connection_test_text,
+            test_connection_button: test_connection_button.clone(),
             online_play_text,
             sp_play_text,
             last_session_text,
+            crash_rate_text,
+            tx: bus.borrow().sender().clone(),
+            game_updates_button: game_updates_button.clone(),
+            changelog_window: RefCell::new(None),
         });
+        this.update_launch_stats();
+
+        game_updates_button.set_callback(weak_cb!([this] => |_| this.game_updates_toggled()));
+        test_connection_button.set_callback(weak_cb!([this] => |_| this.test_connection_clicked()));
+
+        export_diagnostics_button
+            .set_callback(weak_cb!([this] => |_| this.export_diagnostics_clicked()));
+        launch_history_button.set_callback(weak_cb!([this] => |_| this.launch_history_clicked()));
 
         {
             let mut bus = bus.borrow_mut();
             bus.subscribe_consumer(weak_cb!(
                 [this] => |UpdateLastSession| this.update_last_session()
             ));
+            bus.subscribe_consumer(weak_cb!(
+                [this] => |UpdateLaunchStats| this.update_launch_stats()
+            ));
             bus.subscribe_consumer(weak_cb!(
                 [this] => |UpdateAuthState(state)| this.update_auth_state(state)));
+            bus.subscribe_consumer(weak_cb!(
+                [this] => |ChangelogLoaded(result)| this.changelog_loaded(result)
+            ));
+            bus.subscribe_consumer(weak_cb!(
+                [this] => |FLSConnectivityResult { fls, steam }| this.connection_tested(fls, steam)
+            ));
         }
 
         this
@@ -523,7 +1005,183 @@ impl HomeTab {
             .set_value(last_session_text(&self.game));
     }
 
+    fn update_launch_stats(&self) {
+        let config = self.config.get();
+        let (crashes, launches) = (config.crash_count, config.total_launches);
+        let mut text = self.crash_rate_text.clone();
+        if launches == 0 {
+            text.set_value(String::new());
+            return;
+        }
+
+        let rate = 100.0 * crashes as f64 / launches as f64;
+        let mut value = format!(
+            "{:.0}% ({} crashes in {} launches)",
+            rate, crashes, launches
+        );
+        let color = if (crashes > CRASH_WARNING_MIN_COUNT) && (rate > CRASH_WARNING_MIN_RATE) {
+            value.push_str(". Consider verifying game files.");
+            Color::Red
+        } else {
+            Color::Foreground
+        };
+        text.set_value(value);
+        text.set_text_color(color);
+        text.redraw();
+    }
+
+    fn game_updates_toggled(&self) {
+        let mut button = self.game_updates_button.clone();
+        if let Some(changelog_window) = self.changelog_window.borrow_mut().as_mut() {
+            if button.value() {
+                changelog_window.window.show();
+            } else {
+                changelog_window.window.hide();
+            }
+            return;
+        }
+        if !button.value() {
+            return;
+        }
+
+        button.deactivate();
+        let game = Arc::clone(&self.game);
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            tx.send(ChangelogLoaded(fetch_changelog_feed(&game).await))
+                .ok();
+        });
+    }
+
+    fn changelog_loaded(&self, result: Result<String>) {
+        let mut button = self.game_updates_button.clone();
+        button.activate();
+
+        let cache_path = self.game.save_path().join(CHANGELOG_CACHE_FILENAME);
+        let (revision, _) = self.game.version();
+        let html = match result {
+            Ok(feed) => render_changelog(&feed, self.game.build_id(), revision),
+            Err(err) => {
+                warn!(self.logger, "Error fetching game update notes"; "error" => %err);
+                None
+            }
+        };
+        let html = match html {
+            Some(html) => {
+                if let Err(err) = std::fs::write(&cache_path, &html) {
+                    warn!(
+                        self.logger,
+                        "Error caching game update notes";
+                        "path" => cache_path.display(),
+                        "error" => %err,
+                    );
+                }
+                html
+            }
+            None => {
+                std::fs::read_to_string(&cache_path).unwrap_or_else(|_| FALLBACK_HTML.to_string())
+            }
+        };
+
+        *self.changelog_window.borrow_mut() = Some(ChangelogWindow::new(&html, button));
+    }
+
+    fn test_connection_clicked(&self) {
+        self.test_connection_button.clone().deactivate();
+        self.connection_test_text
+            .set_value("<Testing...>".to_string());
+
+        let game = Arc::clone(&self.game);
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let test_steam = async {
+                let client = http_client_builder(&game)
+                    .timeout(CONNECTION_TEST_TIMEOUT)
+                    .build()?;
+                measure_head_request(&client, STEAM_COMMUNITY_URL).await
+            };
+            let (fls, steam) = tokio::join!(playfab::test_connectivity(&game), test_steam);
+            tx.send(FLSConnectivityResult {
+                fls,
+                steam: steam.map_err(Into::into),
+            })
+            .ok();
+        });
+    }
+
+    fn connection_tested(&self, fls: Result<Duration>, steam: Result<Duration>) {
+        self.test_connection_button.clone().activate();
+
+        if let Err(err) = &fls {
+            warn!(self.logger, "FLS connection test failed"; "error" => %err);
+        }
+        if let Err(err) = &steam {
+            warn!(self.logger, "Steam connection test failed"; "error" => %err);
+        }
+
+        let mut text = connectivity_text("FLS", &fls);
+        text.push_str(", ");
+        text.push_str(&connectivity_text("Steam Community", &steam));
+        match (fls.is_ok(), steam.is_ok()) {
+            (false, false) => text.push_str(". Check your internet connection."),
+            (false, true) => text.push_str(". FLS may be experiencing an outage."),
+            _ => text.push('.'),
+        }
+        self.connection_test_text.set_value(text);
+    }
+
+    fn export_diagnostics_clicked(&self) {
+        let mut dialog = NativeFileChooser::new(FileDialogType::BrowseSaveFile);
+        dialog.set_filter(DLG_FILTER_DIAGNOSTICS);
+        dialog.set_option(FileDialogOptions::SaveAsConfirm);
+        dialog.set_preset_file(&format!(
+            "bugle_diagnostics_{}.txt",
+            chrono::Local::now().format("%Y%m%d_%H%M%S")
+        ));
+        dialog.show();
+
+        let mut path = dialog.filename();
+        if path.as_os_str().is_empty() {
+            return;
+        }
+        if path.extension().is_none() {
+            path.set_extension("txt");
+        }
+
+        let result = diagnostics::write_report(
+            &path,
+            &self.game,
+            &self.config.get(),
+            self.log_path.as_deref(),
+            self.platform_user_id.borrow().as_deref(),
+        );
+        if let Err(err) = result {
+            error!(self.logger, "Error exporting diagnostics"; "path" => path.display(), "error" => %err);
+            alert_error(ERR_EXPORTING_DIAGNOSTICS, &err);
+        }
+    }
+
+    fn launch_history_clicked(&self) {
+        let history = match load_launch_history(&launch_history_path(&self.game)) {
+            Ok(history) => history,
+            Err(err) => {
+                error!(self.logger, "Error loading launch history"; "error" => %err);
+                alert_error(ERR_LOADING_LAUNCH_HISTORY, &err);
+                return;
+            }
+        };
+        let parent = fltk::app::first_window().unwrap();
+        let dialog = LaunchHistoryDialog::new(&parent, history);
+        dialog.run();
+    }
+
     fn update_auth_state(&self, state: AuthState) {
+        *self.platform_user_id.borrow_mut() = state
+            .platform_user
+            .as_ref()
+            .ok()
+            .map(|user| user.id.clone());
+        self.update_steam_status(&state);
         let (id, name, can_refresh) = match state.platform_user {
             Ok(user) => (user.id, user.display_name, false),
             Err(err) => {
@@ -553,6 +1211,20 @@ impl HomeTab {
         self.fls_acct_name_text.set_value(name);
         self.refresh_fls_button.clone().set_activated(can_refresh);
 
+        let mut connection_test_label = self.connection_test_label.clone();
+        let mut connection_test_text = self.connection_test_text.clone();
+        let mut test_connection_button = self.test_connection_button.clone();
+        if can_refresh {
+            connection_test_label.show();
+            connection_test_text.show();
+            test_connection_button.show();
+        } else {
+            connection_test_label.hide();
+            connection_test_text.hide();
+            connection_test_text.set_value(String::new());
+            test_connection_button.hide();
+        }
+
         let online_play_str = match state.online_capability {
             TaskState::Pending => "<Checking...>".to_string(),
             TaskState::Ready(Ok(())) => "Yes".to_string(),
@@ -567,6 +1239,39 @@ impl HomeTab {
         };
         self.sp_play_text.set_value(sp_play_str);
     }
+
+    fn update_steam_status(&self, state: &AuthState) {
+        let (color, text, tooltip) = match (&state.platform_user, &state.online_capability) {
+            (Err(_), _) => (
+                STEAM_STATUS_NOT_RUNNING_COLOR,
+                "Not running",
+                "Steam is not running, or BUGLE could not connect to it.".to_string(),
+            ),
+            (Ok(_), TaskState::Ready(Ok(()))) => (
+                STEAM_STATUS_ONLINE_COLOR,
+                "Online",
+                "Steam is running and you are logged in.".to_string(),
+            ),
+            (Ok(_), TaskState::Pending) => (
+                STEAM_STATUS_OFFLINE_COLOR,
+                "Checking...",
+                "Steam is running. Checking whether online play is available.".to_string(),
+            ),
+            (Ok(_), TaskState::Ready(Err(err))) => (
+                STEAM_STATUS_OFFLINE_COLOR,
+                "Offline",
+                format!("Steam is running, but online play is unavailable: {}", err),
+            ),
+        };
+
+        let mut dot = self.steam_status_dot.clone();
+        dot.set_color(color);
+        dot.set_tooltip(&tooltip);
+        dot.redraw();
+        let mut label = self.steam_status_label.clone();
+        label.set_label(text);
+        label.set_tooltip(&tooltip);
+    }
 }
 
 impl LayoutElement for HomeTab {
@@ -603,9 +1308,38 @@ impl LayoutElement for BigButtonElement {
     }
 }
 
+const DEFAULT_NEWS_URL: &str = "https://www.conanexiles.com/news/";
+const CHANGELOG_CACHE_FILENAME: &str = "changelog.html";
+const STEAM_COMMUNITY_URL: &str = "https://steamcommunity.com/";
+const CONNECTION_TEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+const STEAM_STATUS_DOT_SIZE: i32 = 12;
+const STEAM_STATUS_ONLINE_COLOR: Color = Color::from_rgb(0x20, 0xa0, 0x20);
+const STEAM_STATUS_OFFLINE_COLOR: Color = Color::from_rgb(0xe0, 0xa0, 0x00);
+const STEAM_STATUS_NOT_RUNNING_COLOR: Color = Color::from_rgb(0xd0, 0x20, 0x20);
+
 const ERR_LAUNCHING_GAME: &str = "Error while trying to launch the game.";
 const ERR_SWITCHING_TO_MAIN: &str = "Error while trying to switch to Live.";
 const ERR_SWITCHING_TO_PUBLIC_BETA: &str = "Error while trying to switch to TestLive.";
+const ERR_VERIFYING_FILES: &str = "Error while trying to verify the game files.";
+const ERR_EXPORTING_DIAGNOSTICS: &str = "Error while trying to export the diagnostics report.";
+const ERR_LOADING_LAUNCH_HISTORY: &str = "Error while trying to load the launch history.";
+
+const DLG_FILTER_DIAGNOSTICS: &str = "Text Files\t*.txt";
+
+const CRASH_WARNING_MIN_COUNT: u32 = 3;
+const CRASH_WARNING_MIN_RATE: f64 = 20.0;
+
+const PROMPT_VERIFY_FILES: &str = "Steam will verify the Conan Exiles game files and redownload \
+    any that are corrupted.\nThis may take several minutes. Do you want to proceed?";
+const MSG_VERIFY_REQUESTED: &str = "Verification requested via Steam. Check Steam for progress.";
+
+fn connectivity_text(service: &str, result: &Result<Duration>) -> String {
+    match result {
+        Ok(elapsed) => format!("{} reachable ({}ms)", service, elapsed.as_millis()),
+        Err(_) => format!("{} unreachable", service),
+    }
+}
 
 fn create_info_label(text: &str) -> Frame {
     Frame::default()
@@ -613,6 +1347,51 @@ fn create_info_label(text: &str) -> Frame {
         .with_label(text)
 }
 
+fn bind_server_list_auth_input<I: InputExt>(
+    logger: &Logger,
+    input: &mut I,
+    config: &Rc<ConfigManager>,
+    dirty: &Rc<Cell<bool>>,
+    user_input: &Input,
+    password_input: &SecretInput,
+) {
+    input.set_trigger(CallbackTrigger::Changed);
+    input.set_callback({
+        let dirty = Rc::clone(dirty);
+        move |_| dirty.set(true)
+    });
+    input.handle({
+        let logger = logger.clone();
+        let config = Rc::clone(config);
+        let dirty = Rc::clone(dirty);
+        let user_input = user_input.clone();
+        let password_input = password_input.clone();
+        move |_, event| {
+            if let Event::Unfocus | Event::Hide = event {
+                if dirty.take() {
+                    let user = user_input.value();
+                    let auth = if user.trim().is_empty() {
+                        None
+                    } else {
+                        Some((user, password_input.value()))
+                    };
+                    let secure = config.get().secure_passwords == SecurePasswords::Enabled;
+                    config.update(|config| {
+                        if let Err(err) = config.server_browser.set_server_list_auth(auth, secure) {
+                            warn!(
+                                logger,
+                                "Error encrypting server list password, saving it as plaintext";
+                                "error" => %err,
+                            );
+                        }
+                    });
+                }
+            }
+            false
+        }
+    });
+}
+
 fn last_session_text(game: &Game) -> String {
     match &*game.last_session() {
         None => "<none>".to_string(),
@@ -662,3 +1441,45 @@ fn index_to_log_level(idx: i32) -> LogLevel {
         _ => unreachable!(),
     })
 }
+
+fn populate_extra_args_presets(
+    menu: &mut MenuButton,
+    config: &Rc<ConfigManager>,
+    extra_args_input: &Input,
+) {
+    menu.clear();
+
+    let presets = config.get().extra_args_presets.clone();
+    for (idx, (name, args)) in presets.iter().enumerate() {
+        let flags = if idx + 1 == presets.len() { MenuFlag::MenuDivider } else { MenuFlag::Normal };
+        menu.add(&menu_label(name), Shortcut::None, flags, {
+            let config = Rc::clone(config);
+            let mut extra_args_input = extra_args_input.clone();
+            let args = args.clone();
+            move |_| {
+                extra_args_input.set_value(&args);
+                config.update(|config| config.extra_args = args.clone());
+            }
+        });
+    }
+
+    menu.add("Manage Presets...", Shortcut::None, MenuFlag::Normal, {
+        let config = Rc::clone(config);
+        let extra_args_input = extra_args_input.clone();
+        move |_| {
+            let parent = fltk::app::first_window().unwrap();
+            let presets = config.get().extra_args_presets.to_vec();
+            let dialog = ExtraArgsPresetsDialog::new(&parent, presets, extra_args_input.value());
+            if let Some(presets) = dialog.run() {
+                config.update(|config| config.extra_args_presets.0 = presets);
+            }
+        }
+    });
+}
+
+fn menu_label(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('/', "\\/")
+        .replace('&', "&&")
+        .replace('_', "\\_")
+}
@@ -0,0 +1,155 @@
+use std::cell::RefCell;
+use std::net::IpAddr;
+use std::rc::Rc;
+
+use fltk::browser::HoldBrowser;
+use fltk::button::{Button, ReturnButton};
+use fltk::prelude::*;
+use fltk::window::Window;
+use fltk_float::grid::{CellAlign, Grid};
+use fltk_float::{LayoutElement, SimpleWrapper};
+
+use crate::gui::prelude::WidgetConvenienceExt;
+use crate::gui::wrapper_factory;
+
+pub struct BlockedServersDialog {
+    window: Window,
+    state: Rc<BlockedServersState>,
+}
+
+struct BlockedServersState {
+    blocked: RefCell<Vec<IpAddr>>,
+    result: RefCell<Option<Vec<IpAddr>>>,
+    blocked_list: HoldBrowser,
+    remove_button: Button,
+}
+
+impl BlockedServersDialog {
+    pub fn new(parent: &impl WindowExt, mut blocked: Vec<IpAddr>) -> Self {
+        blocked.sort();
+
+        let mut window = Window::default()
+            .with_size(360, 360)
+            .with_label("Blocked Servers");
+
+        let mut root = Grid::builder_with_factory(wrapper_factory())
+            .with_col_spacing(10)
+            .with_row_spacing(10)
+            .with_padding(10, 10, 10, 10);
+        root.col().with_stretch(1).add();
+        let btn_group = root.col_group().add();
+        root.extend_group(btn_group).batch(3);
+
+        root.row().with_stretch(1).add();
+        let mut blocked_list = HoldBrowser::default();
+        root.span(1, 4)
+            .unwrap()
+            .with_vert_align(CellAlign::Stretch)
+            .add(SimpleWrapper::new(
+                blocked_list.as_base_widget(),
+                Default::default(),
+            ));
+        for ip in blocked.iter() {
+            blocked_list.add(&ip.to_string());
+        }
+
+        root.row().add();
+        root.cell().unwrap().skip();
+        let mut remove_button = root
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Remove")
+            .with_tooltip("Unblock the selected server");
+        let mut ok_button = root
+            .cell()
+            .unwrap()
+            .wrap(ReturnButton::default())
+            .with_label("OK");
+        let mut cancel_button = root
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Cancel");
+
+        let root = root.end();
+        root.group().resize(0, 0, window.w(), window.h());
+        root.layout_children();
+        window.end();
+
+        window.set_pos(
+            parent.x() + (parent.w() - window.w()) / 2,
+            parent.y() + (parent.h() - window.h()) / 2,
+        );
+
+        remove_button.deactivate();
+
+        let state = Rc::new(BlockedServersState {
+            blocked: RefCell::new(blocked),
+            result: RefCell::new(None),
+            blocked_list: blocked_list.clone(),
+            remove_button: remove_button.clone(),
+        });
+
+        blocked_list.set_callback({
+            let state = Rc::clone(&state);
+            move |_| state.selection_changed()
+        });
+        remove_button.set_callback({
+            let state = Rc::clone(&state);
+            move |_| state.remove_selected()
+        });
+        ok_button.set_callback({
+            let state = Rc::clone(&state);
+            let mut window = window.clone();
+            move |_| {
+                state.result.replace(Some(state.blocked.borrow().clone()));
+                window.hide();
+            }
+        });
+        cancel_button.set_callback({
+            let mut window = window.clone();
+            move |_| window.hide()
+        });
+
+        Self { window, state }
+    }
+
+    pub fn run(&self) -> Option<Vec<IpAddr>> {
+        let mut window = self.window.clone();
+        window.make_modal(true);
+        window.show();
+
+        while window.shown() && !fltk::app::should_program_quit() {
+            fltk::app::wait();
+        }
+
+        self.state.result.take()
+    }
+}
+
+impl BlockedServersState {
+    fn selected_index(&self) -> Option<usize> {
+        let line = self.blocked_list.value();
+        if line > 0 {
+            Some((line - 1) as usize)
+        } else {
+            None
+        }
+    }
+
+    fn selection_changed(&self) {
+        self.remove_button
+            .clone()
+            .set_activated(self.selected_index().is_some());
+    }
+
+    fn remove_selected(&self) {
+        let Some(idx) = self.selected_index() else {
+            return;
+        };
+        self.blocked.borrow_mut().remove(idx);
+        self.blocked_list.clone().remove(idx as i32 + 1);
+        self.selection_changed();
+    }
+}
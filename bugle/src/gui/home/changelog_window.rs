@@ -0,0 +1,146 @@
+use anyhow::Result;
+use fltk::button::LightButton;
+use fltk::prelude::*;
+use fltk::window::Window;
+use fltk_webview::Webview;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::game::Game;
+use crate::net::http_client_builder;
+
+pub struct ChangelogWindow {
+    pub window: Window,
+    _webview: Webview,
+}
+
+impl ChangelogWindow {
+    pub fn new(html: &str, mut toggle: LightButton) -> Self {
+        let mut window = Window::default()
+            .with_label("Conan Exiles Game Updates")
+            .with_size(800, 600);
+        window.make_resizable(true);
+        window.end();
+        window.set_callback(move |window| {
+            window.hide();
+            toggle.set_value(false);
+        });
+        window.show();
+
+        let webview = Webview::create(false, &mut window);
+        webview.set_html(html);
+
+        Self {
+            window,
+            _webview: webview,
+        }
+    }
+}
+
+pub async fn fetch_changelog_feed(game: &Game) -> Result<String> {
+    Ok(http_client_builder(game)
+        .build()?
+        .get(CHANGELOG_FEED_URL)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?)
+}
+
+pub fn render_changelog(feed: &str, build_id: u32, revision: u32) -> Option<String> {
+    let mut entries = String::new();
+    for item in ITEM_REGEX.captures_iter(feed) {
+        let item = item.get(1).unwrap().as_str();
+        let title = item_field(item, "title").unwrap_or_default();
+        if !UPDATE_TITLE_REGEX.is_match(&title) {
+            continue;
+        }
+        let link = item_field(item, "link")
+            .filter(|link| link.starts_with("https://") || link.starts_with("http://"))
+            .unwrap_or_default();
+        let date = item_field(item, "pubDate").unwrap_or_default();
+        let description = item_field(item, "description").unwrap_or_default();
+
+        let current = mentions_number(&title, build_id)
+            || mentions_number(&title, revision)
+            || mentions_number(&description, build_id);
+        entries.push_str(&format!(
+            "<div class=\"entry{}\"><h2><a href=\"{}\">{}</a>{}</h2><p class=\"date\">{}</p>{}</div>",
+            if current { " current" } else { "" },
+            escape_html(&link),
+            escape_html(&title),
+            if current { " <span class=\"badge\">current version</span>" } else { "" },
+            escape_html(&date),
+            description,
+        ));
+    }
+
+    if entries.is_empty() {
+        return None;
+    }
+    Some(format!("{}{}{}", HTML_HEADER, entries, HTML_FOOTER))
+}
+
+fn mentions_number(text: &str, number: u32) -> bool {
+    NUMBER_REGEX
+        .find_iter(text)
+        .any(|found| found.as_str().parse::<u32>() == Ok(number))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn item_field(item: &str, name: &str) -> Option<String> {
+    let start_tag = format!("<{}>", name);
+    let end_tag = format!("</{}>", name);
+    let start = item.find(&start_tag)? + start_tag.len();
+    let end = start + item[start..].find(&end_tag)?;
+    let value = item[start..end].trim();
+
+    if let Some(cdata) = value
+        .strip_prefix("<![CDATA[")
+        .and_then(|value| value.strip_suffix("]]>"))
+    {
+        return Some(cdata.to_string());
+    }
+    Some(
+        value
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&"),
+    )
+}
+
+lazy_static! {
+    static ref ITEM_REGEX: Regex = Regex::new(r"(?s)<item>(.*?)</item>").unwrap();
+    static ref UPDATE_TITLE_REGEX: Regex =
+        Regex::new(r"(?i)patch|update|hotfix|changelog").unwrap();
+    static ref NUMBER_REGEX: Regex = Regex::new(r"\d+").unwrap();
+}
+
+const CHANGELOG_FEED_URL: &str = "https://store.steampowered.com/feeds/news/app/440900/";
+
+const HTML_HEADER: &str = "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><style>\
+    body { font-family: sans-serif; margin: 1em; }\
+    .entry { border-bottom: 1px solid #ccc; padding: 0.5em; }\
+    .entry.current { background: #fff6d5; border-left: 4px solid #e0a800; }\
+    .date { color: #666; font-size: smaller; }\
+    .badge { background: #e0a800; border-radius: 4px; color: #fff; font-size: small; \
+    padding: 2px 6px; }\
+    img { max-width: 100%; }\
+    </style></head><body>";
+const HTML_FOOTER: &str = "</body></html>";
+
+pub const FALLBACK_HTML: &str = "<!DOCTYPE html><html><head><meta charset=\"utf-8\"></head>\
+    <body style=\"font-family: sans-serif; margin: 1em;\"><h2>Game update notes are unavailable\
+    </h2><p>The update notes could not be retrieved. You can read them on the \
+    <a href=\"https://store.steampowered.com/news/app/440900\">Conan Exiles news page</a>.</p>\
+    </body></html>";
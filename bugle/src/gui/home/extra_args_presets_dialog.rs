@@ -0,0 +1,252 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use fltk::browser::HoldBrowser;
+use fltk::button::{Button, ReturnButton};
+use fltk::enums::CallbackTrigger;
+use fltk::frame::Frame;
+use fltk::input::Input;
+use fltk::prelude::*;
+use fltk::window::Window;
+use fltk_float::grid::{CellAlign, Grid};
+use fltk_float::{LayoutElement, SimpleWrapper};
+
+use crate::gui::wrapper_factory;
+
+pub struct ExtraArgsPresetsDialog {
+    window: Window,
+    state: Rc<PresetsState>,
+}
+
+struct PresetsState {
+    presets: RefCell<Vec<(String, String)>>,
+    result: RefCell<Option<Vec<(String, String)>>>,
+    presets_list: HoldBrowser,
+    name_input: Input,
+    args_input: Input,
+    delete_button: Button,
+}
+
+impl ExtraArgsPresetsDialog {
+    pub fn new(
+        parent: &impl WindowExt,
+        presets: Vec<(String, String)>,
+        current_args: String,
+    ) -> Self {
+        let mut window = Window::default()
+            .with_size(560, 360)
+            .with_label("Launch Options Presets");
+
+        let mut root = Grid::builder_with_factory(wrapper_factory())
+            .with_col_spacing(10)
+            .with_row_spacing(10)
+            .with_padding(10, 10, 10, 10);
+        root.col().with_default_align(CellAlign::End).add();
+        root.col().with_stretch(1).add();
+        let btn_group = root.col_group().add();
+        root.extend_group(btn_group).batch(4);
+
+        root.row().with_stretch(1).add();
+        let mut presets_list = HoldBrowser::default();
+        root.span(1, 6)
+            .unwrap()
+            .with_vert_align(CellAlign::Stretch)
+            .add(SimpleWrapper::new(
+                presets_list.as_base_widget(),
+                Default::default(),
+            ));
+        for (name, _) in presets.iter() {
+            presets_list.add(name);
+        }
+
+        root.row().add();
+        root.cell()
+            .unwrap()
+            .wrap(Frame::default())
+            .with_label("Name:");
+        let mut name_input = root.span(1, 5).unwrap().wrap(Input::default());
+
+        root.row().add();
+        root.cell()
+            .unwrap()
+            .wrap(Frame::default())
+            .with_label("Launch Options:");
+        let mut args_input = root.span(1, 5).unwrap().wrap(Input::default());
+
+        root.row().add();
+        root.span(1, 2).unwrap().skip();
+        let mut new_button = root
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("New")
+            .with_tooltip("Create a new preset from the current launch options");
+        let mut delete_button = root
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Delete")
+            .with_tooltip("Delete the selected preset");
+        let mut ok_button = root
+            .cell()
+            .unwrap()
+            .wrap(ReturnButton::default())
+            .with_label("OK");
+        let mut cancel_button = root
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Cancel");
+
+        let root = root.end();
+        root.group().resize(0, 0, window.w(), window.h());
+        root.layout_children();
+        window.end();
+
+        window.set_pos(
+            parent.x() + (parent.w() - window.w()) / 2,
+            parent.y() + (parent.h() - window.h()) / 2,
+        );
+
+        let state = Rc::new(PresetsState {
+            presets: RefCell::new(presets),
+            result: RefCell::new(None),
+            presets_list: presets_list.clone(),
+            name_input: name_input.clone(),
+            args_input: args_input.clone(),
+            delete_button: delete_button.clone(),
+        });
+        state.select(if state.presets.borrow().is_empty() { None } else { Some(0) });
+
+        presets_list.set_callback({
+            let state = Rc::clone(&state);
+            move |list| {
+                let line = list.value();
+                state.select(if line > 0 { Some((line - 1) as usize) } else { None });
+            }
+        });
+        name_input.set_trigger(CallbackTrigger::Changed);
+        name_input.set_callback({
+            let state = Rc::clone(&state);
+            move |input| state.name_changed(input.value())
+        });
+        args_input.set_trigger(CallbackTrigger::Changed);
+        args_input.set_callback({
+            let state = Rc::clone(&state);
+            move |input| state.args_changed(input.value())
+        });
+        new_button.set_callback({
+            let state = Rc::clone(&state);
+            move |_| state.new_preset(&current_args)
+        });
+        delete_button.set_callback({
+            let state = Rc::clone(&state);
+            move |_| state.delete_preset()
+        });
+        ok_button.set_callback({
+            let state = Rc::clone(&state);
+            let mut window = window.clone();
+            move |_| {
+                state.result.replace(Some(state.presets.borrow().clone()));
+                window.hide();
+            }
+        });
+        cancel_button.set_callback({
+            let mut window = window.clone();
+            move |_| window.hide()
+        });
+
+        Self { window, state }
+    }
+
+    pub fn run(&self) -> Option<Vec<(String, String)>> {
+        let mut window = self.window.clone();
+        window.make_modal(true);
+        window.show();
+
+        while window.shown() && !fltk::app::should_program_quit() {
+            fltk::app::wait();
+        }
+
+        self.state.result.take()
+    }
+}
+
+impl PresetsState {
+    fn selected_index(&self) -> Option<usize> {
+        let line = self.presets_list.value();
+        if line > 0 {
+            Some((line - 1) as usize)
+        } else {
+            None
+        }
+    }
+
+    fn select(&self, idx: Option<usize>) {
+        let mut presets_list = self.presets_list.clone();
+        let mut name_input = self.name_input.clone();
+        let mut args_input = self.args_input.clone();
+        let mut delete_button = self.delete_button.clone();
+
+        match idx.and_then(|idx| self.presets.borrow().get(idx).cloned()) {
+            Some((name, args)) => {
+                presets_list.select(idx.unwrap() as i32 + 1);
+                name_input.set_value(&name);
+                args_input.set_value(&args);
+                name_input.activate();
+                args_input.activate();
+                delete_button.activate();
+            }
+            None => {
+                name_input.set_value("");
+                args_input.set_value("");
+                name_input.deactivate();
+                args_input.deactivate();
+                delete_button.deactivate();
+            }
+        }
+    }
+
+    fn name_changed(&self, name: String) {
+        if let Some(idx) = self.selected_index() {
+            self.presets_list.clone().set_text(idx as i32 + 1, &name);
+            self.presets.borrow_mut()[idx].0 = name;
+        }
+    }
+
+    fn args_changed(&self, args: String) {
+        if let Some(idx) = self.selected_index() {
+            self.presets.borrow_mut()[idx].1 = args;
+        }
+    }
+
+    fn new_preset(&self, current_args: &str) {
+        let idx = {
+            let mut presets = self.presets.borrow_mut();
+            presets.push((NEW_PRESET_NAME.to_string(), current_args.to_string()));
+            presets.len() - 1
+        };
+        self.presets_list.clone().add(NEW_PRESET_NAME);
+        self.select(Some(idx));
+
+        let mut name_input = self.name_input.clone();
+        let _ = name_input.take_focus();
+        let _ = name_input.set_position(0);
+        let _ = name_input.set_mark(NEW_PRESET_NAME.len() as i32);
+    }
+
+    fn delete_preset(&self) {
+        let Some(idx) = self.selected_index() else {
+            return;
+        };
+        let remaining = {
+            let mut presets = self.presets.borrow_mut();
+            presets.remove(idx);
+            presets.len()
+        };
+        self.presets_list.clone().remove(idx as i32 + 1);
+        self.select(if remaining == 0 { None } else { Some(idx.min(remaining - 1)) });
+    }
+}
+
+const NEW_PRESET_NAME: &str = "New Preset";
@@ -0,0 +1,180 @@
+use std::rc::Rc;
+
+use chrono::Local;
+use fltk::browser::Browser;
+use fltk::button::Button;
+use fltk::enums::Align;
+use fltk::frame::Frame;
+use fltk::prelude::*;
+use fltk::table::TableContext;
+use fltk::window::Window;
+use fltk_float::grid::{CellAlign, Grid};
+use fltk_float::SimpleWrapper;
+
+use crate::gui::widgets::{DataTable, DataTableProperties, DataTableUpdate};
+use crate::gui::{is_table_nav_event, wrapper_factory};
+use crate::launch_history::LaunchRecord;
+use crate::launcher::LaunchMode;
+
+pub struct LaunchHistoryDialog {
+    window: Window,
+}
+
+impl LaunchHistoryDialog {
+    pub fn new(parent: &impl WindowExt, mut history: Vec<LaunchRecord>) -> Self {
+        let skip = history.len().saturating_sub(MAX_DISPLAYED_LAUNCHES);
+        history.drain(..skip);
+        history.reverse();
+        let history = Rc::new(history);
+
+        let mut window = Window::default()
+            .with_size(720, 520)
+            .with_label("Launch History");
+
+        let mut root = Grid::builder_with_factory(wrapper_factory())
+            .with_col_spacing(10)
+            .with_row_spacing(10)
+            .with_padding(10, 10, 10, 10);
+        root.col().with_stretch(1).add();
+
+        root.row().with_stretch(2).add();
+        let mut launches_table =
+            DataTable::<Vec<String>>::default().with_properties(DataTableProperties {
+                columns: vec![
+                    ("Time", 150).into(),
+                    ("Mode", 110).into(),
+                    ("Server / Map", Align::Left).into(),
+                    ("BattlEye", 70).into(),
+                    ("Mods", 50).into(),
+                    ("Exit Code", 70).into(),
+                ],
+                cell_padding: 4,
+                cell_selection_color: fltk::enums::Color::Free,
+                header_font_color: fltk::enums::Color::Gray0,
+                ..Default::default()
+            });
+        launches_table.set_row_header(false);
+        launches_table.set_col_header(true);
+        launches_table.set_col_resize(true);
+        launches_table.set_flex_col(2);
+        launches_table.end();
+        root.cell()
+            .unwrap()
+            .with_vert_align(CellAlign::Stretch)
+            .add(SimpleWrapper::new(
+                launches_table.as_base_widget(),
+                Default::default(),
+            ));
+        {
+            let data = launches_table.data();
+            let mut data = data.borrow_mut();
+            data.extend(history.iter().map(launch_row));
+        }
+        launches_table.updated(DataTableUpdate::DATA);
+
+        root.row().add();
+        let mut mods_label = root
+            .cell()
+            .unwrap()
+            .wrap(Frame::default())
+            .with_label(MSG_SELECT_LAUNCH)
+            .with_align(Align::Left | Align::Inside);
+
+        root.row().with_stretch(1).add();
+        let mods_list = Browser::default();
+        root.cell()
+            .unwrap()
+            .with_vert_align(CellAlign::Stretch)
+            .add(SimpleWrapper::new(mods_list.clone(), Default::default()));
+
+        root.row().add();
+        let mut close_button = root
+            .cell()
+            .unwrap()
+            .with_horz_align(CellAlign::End)
+            .wrap(Button::default())
+            .with_label("Close");
+
+        let root = root.end();
+        root.group().resize(0, 0, window.w(), window.h());
+        root.layout_children();
+        window.end();
+
+        window.set_pos(
+            parent.x() + (parent.w() - window.w()) / 2,
+            parent.y() + (parent.h() - window.h()) / 2,
+        );
+
+        launches_table.set_callback({
+            let history = Rc::clone(&history);
+            let mut mods_list = mods_list.clone();
+            move |table| {
+                if !is_table_nav_event() || (table.callback_context() != TableContext::Cell) {
+                    return;
+                }
+                let row = table.callback_row();
+                let Some(record) = history.get(row as usize) else {
+                    return;
+                };
+                table.set_selection(row, 0, row, table.cols() - 1);
+                mods_label.set_label(&format!(
+                    "Mods active at launch (list hash {}):",
+                    record.mod_list_hash
+                ));
+                mods_list.clear();
+                for name in record.mods.iter() {
+                    mods_list.add(name);
+                }
+            }
+        });
+        close_button.set_callback({
+            let mut window = window.clone();
+            move |_| window.hide()
+        });
+
+        Self { window }
+    }
+
+    pub fn run(&self) {
+        let mut window = self.window.clone();
+        window.make_modal(true);
+        window.show();
+
+        while window.shown() && !fltk::app::should_program_quit() {
+            fltk::app::wait();
+        }
+    }
+}
+
+fn launch_row(record: &LaunchRecord) -> Vec<String> {
+    let mode = match record.mode {
+        LaunchMode::Game => "Launch",
+        LaunchMode::ContinueSession => "Continue",
+        LaunchMode::JoinServer => "Online",
+        LaunchMode::SinglePlayer => "Singleplayer",
+    };
+    let target = match (record.server_addr, record.map_id) {
+        (Some(addr), _) => addr.to_string(),
+        (None, Some(map_id)) => format!("Map #{}", map_id),
+        (None, None) => String::new(),
+    };
+    vec![
+        record
+            .timestamp
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string(),
+        mode.to_string(),
+        target,
+        if record.battleye { "Yes" } else { "No" }.to_string(),
+        record.mods.len().to_string(),
+        record
+            .exit_code
+            .map(|code| code.to_string())
+            .unwrap_or_default(),
+    ]
+}
+
+const MAX_DISPLAYED_LAUNCHES: usize = 50;
+
+const MSG_SELECT_LAUNCH: &str = "Select a launch to see the mods that were active.";
@@ -0,0 +1,121 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use fltk::app;
+use fltk::enums::Align;
+use fltk::frame::Frame;
+use fltk::group::Group;
+use fltk::prelude::*;
+use fltk::window::Window;
+use fltk_float::grid::{CellAlign, Grid};
+use fltk_float::{LayoutElement, SimpleWrapper};
+use fltk_webview::Webview;
+
+use crate::gui::wrapper_factory;
+
+pub struct NewsPane {
+    grid: Grid,
+    group: Group,
+    host: Window,
+    spinner: Frame,
+    webview: RefCell<Option<Webview>>,
+    loading: Rc<Cell<bool>>,
+}
+
+impl NewsPane {
+    pub fn new() -> Rc<Self> {
+        let mut grid = Grid::builder_with_factory(wrapper_factory()).with_row_spacing(5);
+        grid.col().with_stretch(1).add();
+
+        grid.row().add();
+        let spinner = grid
+            .cell()
+            .unwrap()
+            .wrap(Frame::default())
+            .with_align(Align::Left | Align::Inside);
+
+        grid.row().with_stretch(1).add();
+        let mut host = Window::default();
+        host.end();
+        grid.cell()
+            .unwrap()
+            .with_vert_align(CellAlign::Stretch)
+            .add(SimpleWrapper::new(host.clone(), Default::default()));
+
+        let grid = grid.end();
+        let mut group = grid.group();
+        group.hide();
+
+        Rc::new(Self {
+            grid,
+            group,
+            host,
+            spinner,
+            webview: RefCell::new(None),
+            loading: Rc::new(Cell::new(false)),
+        })
+    }
+
+    pub fn set_expanded(&self, expanded: bool, url: &str) {
+        let mut group = self.group.clone();
+        if !expanded {
+            group.hide();
+            return;
+        }
+        group.show();
+
+        // The page is only loaded the first time the pane is expanded in a session
+        if self.webview.borrow().is_some() {
+            return;
+        }
+        let webview = Webview::create(false, &mut self.host.clone());
+        webview.bind(LOADED_CALLBACK, {
+            let loading = Rc::clone(&self.loading);
+            let mut spinner = self.spinner.clone();
+            move |_, _| {
+                loading.set(false);
+                spinner.set_label("");
+            }
+        });
+        webview.init(&format!(
+            "window.addEventListener('load', function() {{ window.{}(); }});",
+            LOADED_CALLBACK
+        ));
+        webview.navigate(url);
+        *self.webview.borrow_mut() = Some(webview);
+
+        self.loading.set(true);
+        self.animate_spinner();
+    }
+
+    fn animate_spinner(&self) {
+        let loading = Rc::clone(&self.loading);
+        let mut spinner = self.spinner.clone();
+        let mut frame = 0;
+        app::add_timeout3(SPINNER_INTERVAL, move |handle| {
+            if !loading.get() {
+                return;
+            }
+            spinner.set_label(&format!(
+                "@{}reload  Loading news...",
+                SPINNER_DIRECTIONS[frame % SPINNER_DIRECTIONS.len()]
+            ));
+            frame += 1;
+            app::repeat_timeout3(SPINNER_INTERVAL, handle);
+        });
+    }
+}
+
+impl LayoutElement for NewsPane {
+    fn min_size(&self) -> fltk_float::Size {
+        self.grid.min_size()
+    }
+
+    fn layout(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.grid.layout(x, y, width, height)
+    }
+}
+
+const LOADED_CALLBACK: &str = "bugleNewsLoaded";
+const SPINNER_INTERVAL: f64 = 0.1;
+const SPINNER_DIRECTIONS: &[char] = &['8', '9', '6', '3', '2', '1', '4', '7'];
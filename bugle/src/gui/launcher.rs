@@ -1,8 +1,10 @@
 use std::cell::RefCell;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 
+use dynabus::Bus;
 use fltk::app;
 use fltk::enums::{Event, FrameType};
 use fltk::group::Wizard;
@@ -16,13 +18,14 @@ use slog::Logger;
 use crate::auth_manager::AuthManager;
 use crate::bus::AppBus;
 use crate::config::ConfigManager;
-use crate::game::Game;
+use crate::game::{Branch, Game};
 use crate::launcher::Launcher;
-use crate::mod_manager::ModManager;
+use crate::mod_manager::{ModManager, ModUpdatesAvailable};
 use crate::saved_games_manager::SavedGamesManager;
 use crate::server_manager::ServerManager;
 
 use super::home::HomeTab;
+use super::log_viewer::LogViewer;
 use super::main_menu::MainMenu;
 use super::mod_manager::ModManagerTab;
 use super::server_browser::ServerBrowserTab;
@@ -33,21 +36,43 @@ pub struct LauncherWindow {
     window: Window,
 }
 
+pub struct LauncherServices {
+    pub bus: Rc<RefCell<AppBus>>,
+    pub game: Arc<Game>,
+    pub config: Rc<ConfigManager>,
+    pub auth: Rc<AuthManager>,
+    pub launcher: Rc<Launcher>,
+    pub servers: Rc<ServerManager>,
+    pub saves: Rc<SavedGamesManager>,
+    pub mod_manager: Rc<ModManager>,
+}
+
+pub struct LogSettings {
+    pub level: Option<Arc<AtomicUsize>>,
+    pub path: Option<PathBuf>,
+}
+
 impl LauncherWindow {
     pub fn new(
         logger: &Logger,
-        bus: Rc<RefCell<AppBus>>,
-        game: Arc<Game>,
-        config: Rc<ConfigManager>,
-        log_level: Option<Arc<AtomicUsize>>,
-        auth: Rc<AuthManager>,
-        launcher: Rc<Launcher>,
-        servers: Rc<ServerManager>,
-        saves: Rc<SavedGamesManager>,
-        mod_manager: Rc<ModManager>,
+        services: LauncherServices,
+        log: LogSettings,
         can_switch_branch: bool,
     ) -> Self {
-        let mut window = Window::default().with_size(1280, 760).with_label("BUGLE");
+        let LauncherServices {
+            bus,
+            game,
+            config,
+            auth,
+            launcher,
+            servers,
+            saves,
+            mod_manager,
+        } = services;
+
+        let mut window = Window::default()
+            .with_size(1280, 760)
+            .with_label(&window_title(&game));
 
         let root = Grid::builder_with_factory(wrapper_factory());
         let mut root = root
@@ -72,7 +97,8 @@ impl LauncherWindow {
             Rc::clone(&bus),
             Arc::clone(&game),
             Rc::clone(&config),
-            log_level,
+            log.level,
+            log.path.clone(),
             Rc::clone(&auth),
             Rc::clone(&launcher),
             can_switch_branch,
@@ -95,14 +121,20 @@ impl LauncherWindow {
                 logger,
                 Rc::clone(&bus),
                 Arc::clone(&game),
+                Rc::clone(&config),
                 Rc::clone(&launcher),
                 Rc::clone(&saves),
             )
         };
         content_overlay.add_shared(Rc::<SinglePlayerTab>::clone(&single_player_tab));
 
-        let mod_manager_tab =
-            ModManagerTab::new(logger, Arc::clone(&game), Rc::clone(&mod_manager));
+        let mod_manager_tab = ModManagerTab::new(
+            logger,
+            Rc::clone(&bus),
+            Arc::clone(&game),
+            Rc::clone(&config),
+            Rc::clone(&mod_manager),
+        );
         content_overlay.add_shared(Rc::<ModManagerTab>::clone(&mod_manager_tab));
 
         let content_overlay = content_overlay.end();
@@ -152,6 +184,31 @@ impl LauncherWindow {
             main_menu.set_on_mods(move || content_group.set_current_widget(mod_manager_tab.root()));
         }
 
+        if let Some(log_path) = log.path {
+            let window = window.clone();
+            main_menu.set_on_view_log(move || LogViewer::new(&window, log_path.clone()).run());
+        }
+
+        let main_menu = Rc::new(main_menu);
+        bus.borrow_mut().subscribe_consumer({
+            let main_menu = Rc::downgrade(&main_menu);
+            move |ModUpdatesAvailable(count)| {
+                if let Some(main_menu) = main_menu.upgrade() {
+                    main_menu.set_mods_badge(count);
+                }
+            }
+        });
+
+        window.handle(move |_, event| {
+            if let Event::Shortcut = event {
+                if let Some(tab) = MainMenu::tab_for_shortcut() {
+                    main_menu.select_tab(tab);
+                    return true;
+                }
+            }
+            false
+        });
+
         Self { window }
     }
 
@@ -163,3 +220,18 @@ impl LauncherWindow {
         &self.window
     }
 }
+
+fn window_title(game: &Game) -> String {
+    let (revision, snapshot) = game.version();
+    let branch_name = match game.branch() {
+        Branch::Live => "Live",
+        Branch::TestLive => "TestLive",
+    };
+    format!(
+        "BUGLE {} — Conan Exiles #{}/{} ({})",
+        env!("CARGO_PKG_VERSION"),
+        revision,
+        snapshot,
+        branch_name
+    )
+}
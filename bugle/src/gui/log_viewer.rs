@@ -0,0 +1,157 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use anyhow::Result;
+use fltk::app;
+use fltk::button::{Button, ReturnButton};
+use fltk::enums::Font;
+use fltk::prelude::*;
+use fltk::text::{TextBuffer, TextDisplay};
+use fltk::window::Window;
+use fltk_float::grid::{CellAlign, Grid};
+use fltk_float::{LayoutElement, SimpleWrapper};
+
+use super::{alert_error, wrapper_factory};
+
+pub struct LogViewer {
+    window: Window,
+}
+
+struct LogViewerState {
+    path: PathBuf,
+    buffer: TextBuffer,
+    display: TextDisplay,
+}
+
+impl LogViewer {
+    pub fn new(parent: &impl WindowExt, log_path: PathBuf) -> Self {
+        let mut window = Window::default()
+            .with_size(960, 600)
+            .with_label("BUGLE Log");
+
+        let mut root = Grid::builder_with_factory(wrapper_factory())
+            .with_col_spacing(10)
+            .with_row_spacing(10)
+            .with_padding(10, 10, 10, 10);
+        root.col().with_stretch(1).add();
+        let btn_group = root.col_group().add();
+        root.extend_group(btn_group).batch(3);
+
+        root.row().with_stretch(1).add();
+        let mut display = TextDisplay::default();
+        root.span(1, 4)
+            .unwrap()
+            .with_vert_align(CellAlign::Stretch)
+            .add(SimpleWrapper::new(
+                display.as_base_widget(),
+                Default::default(),
+            ));
+        let buffer = TextBuffer::default();
+        display.set_buffer(buffer.clone());
+        display.set_text_font(Font::Courier);
+
+        root.row().add();
+        root.cell().unwrap().skip();
+        let mut refresh_button = root
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Refresh")
+            .with_tooltip("Reload the log file");
+        let mut copy_button = root
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Copy to Clipboard")
+            .with_tooltip("Copy the log to the clipboard");
+        let mut close_button = root
+            .cell()
+            .unwrap()
+            .wrap(ReturnButton::default())
+            .with_label("Close");
+
+        let root = root.end();
+        root.group().resize(0, 0, window.w(), window.h());
+        root.layout_children();
+        window.end();
+        window.make_resizable(true);
+        window.resize_callback(move |_, _, _, _, _| root.layout_children());
+
+        window.set_pos(
+            parent.x() + (parent.w() - window.w()) / 2,
+            parent.y() + (parent.h() - window.h()) / 2,
+        );
+
+        let state = Rc::new(LogViewerState {
+            path: log_path,
+            buffer,
+            display,
+        });
+        state.refresh();
+
+        refresh_button.set_callback({
+            let state = Rc::clone(&state);
+            move |_| state.refresh()
+        });
+        copy_button.set_callback({
+            let state = Rc::clone(&state);
+            move |_| app::copy(&state.buffer.text())
+        });
+        close_button.set_callback({
+            let mut window = window.clone();
+            move |_| window.hide()
+        });
+
+        Self { window }
+    }
+
+    pub fn run(&self) {
+        let mut window = self.window.clone();
+        window.make_modal(true);
+        window.show();
+
+        while window.shown() && !app::should_program_quit() {
+            app::wait();
+        }
+    }
+}
+
+impl LogViewerState {
+    fn refresh(&self) {
+        match read_log_tail(&self.path) {
+            Ok(text) => {
+                self.buffer.clone().set_text(&text);
+                let mut display = self.display.clone();
+                let lines = display.count_lines(0, self.buffer.length(), true);
+                display.scroll(lines, 0);
+            }
+            Err(err) => alert_error(ERR_READING_LOG, &err),
+        }
+    }
+}
+
+fn read_log_tail(path: &Path) -> Result<String> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let skipped = len.saturating_sub(MAX_LOG_BYTES);
+    file.seek(SeekFrom::Start(skipped))?;
+
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let text = String::from_utf8_lossy(&bytes);
+    let text = if skipped > 0 {
+        match text.find('\n') {
+            Some(idx) => &text[idx + 1..],
+            None => &text,
+        }
+    } else {
+        &text
+    };
+    Ok(text.to_string())
+}
+
+const MAX_LOG_BYTES: u64 = 256 * 1024;
+
+const ERR_READING_LOG: &str = "Error while reading the log file.";
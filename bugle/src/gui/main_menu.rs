@@ -1,9 +1,13 @@
 use std::borrow::Borrow;
+use std::cell::Cell;
+use std::rc::Rc;
 
 use fltk::app;
 use fltk::button::{Button, RadioButton};
 use fltk::dialog;
-use fltk::enums::FrameType;
+use fltk::draw;
+use fltk::enums::{Align, Color, Font, FrameType, Key, Shortcut};
+use fltk::menu::MenuItem;
 use fltk::prelude::*;
 use fltk_float::grid::{CellAlign, Grid, GridBuilder};
 use fltk_float::WrapperFactory;
@@ -15,6 +19,8 @@ pub(super) struct MainMenu {
     online_btn: RadioButton,
     single_player_btn: RadioButton,
     mods_btn: RadioButton,
+    help_btn: Button,
+    mods_badge: Rc<Cell<usize>>,
 }
 
 impl MainMenu {
@@ -25,17 +31,35 @@ impl MainMenu {
         grid.col().with_stretch(1).add();
 
         let mut home_btn = make_button(&mut grid, RadioButton::default, "Launcher");
-        let online_btn = make_button(&mut grid, RadioButton::default, "Online");
-        let single_player_btn = make_button(&mut grid, RadioButton::default, "Singleplayer");
+        let mut online_btn = make_button(&mut grid, RadioButton::default, "Online");
+        let mut single_player_btn = make_button(&mut grid, RadioButton::default, "Singleplayer");
         let mut coop_btn = make_button(&mut grid, Button::default, "Co-op");
-        let mods_btn = make_button(&mut grid, RadioButton::default, "Mods");
+        let mut mods_btn = make_button(&mut grid, RadioButton::default, "Mods");
+        let mut help_btn = make_button(&mut grid, Button::default, "Help");
         let mut exit_btn = make_button(&mut grid, Button::default, "Exit");
 
         home_btn.toggle(true);
 
+        let tab_buttons = [
+            &mut home_btn,
+            &mut online_btn,
+            &mut single_player_btn,
+            &mut mods_btn,
+        ];
+        for (idx, button) in tab_buttons.into_iter().enumerate() {
+            button.set_tooltip(&format!("{}+{}", TAB_SHORTCUT_MODIFIER_NAME, idx + 1));
+        }
+
+        let mods_badge = Rc::new(Cell::new(0));
+        mods_btn.draw({
+            let mods_badge = Rc::clone(&mods_badge);
+            move |button| draw_badge(button, mods_badge.get())
+        });
+
         let grid = grid.end();
 
         coop_btn.set_callback(not_implemented_callback);
+        help_btn.deactivate();
         exit_btn.set_callback(|_| app::quit());
 
         let menu = Self {
@@ -43,6 +67,8 @@ impl MainMenu {
             online_btn,
             single_player_btn,
             mods_btn,
+            help_btn,
+            mods_badge,
         };
 
         (menu, grid)
@@ -62,10 +88,69 @@ impl MainMenu {
     }
 
     pub fn set_on_mods(&mut self, mut on_mods: impl FnMut() + 'static) {
-        self.mods_btn.set_callback(move |_| on_mods());
+        let mods_badge = Rc::clone(&self.mods_badge);
+        self.mods_btn.set_callback(move |button| {
+            mods_badge.set(0);
+            button.redraw();
+            on_mods();
+        });
+    }
+
+    pub fn set_on_view_log(&mut self, on_view_log: impl Fn() + 'static) {
+        self.help_btn.activate();
+        self.help_btn.set_callback(move |button| {
+            let menu = MenuItem::new(&[MENU_VIEW_LOG]);
+            if menu.popup(button.x(), button.y() + button.h()).is_some() {
+                on_view_log();
+            }
+        });
+    }
+
+    pub fn set_mods_badge(&self, count: usize) {
+        if self.mods_btn.value() {
+            return;
+        }
+        self.mods_badge.set(count);
+        self.mods_btn.clone().redraw();
+    }
+
+    pub fn select_tab(&self, n: usize) {
+        let mut button = match n {
+            0 => self.home_btn.clone(),
+            1 => self.online_btn.clone(),
+            2 => self.single_player_btn.clone(),
+            3 => self.mods_btn.clone(),
+            _ => return,
+        };
+        button.setonly();
+        button.do_callback();
+    }
+
+    pub fn tab_for_shortcut() -> Option<usize> {
+        if !app::event_state().contains(TAB_SHORTCUT_MODIFIER) {
+            return None;
+        }
+        let key = app::event_key();
+        (0..4).find(|&idx| key == Key::from_char((b'1' + idx as u8) as char))
     }
 }
 
+const MENU_VIEW_LOG: &str = "View Log...";
+
+const BADGE_SIZE: i32 = 18;
+const BADGE_MARGIN: i32 = 4;
+const BADGE_FONT_SIZE: i32 = 11;
+
+#[cfg(target_os = "macos")]
+const TAB_SHORTCUT_MODIFIER: Shortcut = Shortcut::Meta;
+#[cfg(not(target_os = "macos"))]
+const TAB_SHORTCUT_MODIFIER: Shortcut = Shortcut::Ctrl;
+
+#[cfg(target_os = "macos")]
+const TAB_SHORTCUT_MODIFIER_NAME: &str = "Cmd";
+#[cfg(not(target_os = "macos"))]
+const TAB_SHORTCUT_MODIFIER_NAME: &str = "Ctrl";
+
 fn make_button<G, F, B, C>(grid: &mut GridBuilder<G, F>, ctor: C, text: &str) -> B
 where
     G: GroupExt + Clone,
@@ -86,6 +171,20 @@ where
     button
 }
 
+fn draw_badge(button: &RadioButton, count: usize) {
+    if count == 0 {
+        return;
+    }
+    let text = if count > 9 { "9+".to_string() } else { count.to_string() };
+    let x = button.x() + button.w() - BADGE_SIZE - BADGE_MARGIN;
+    let y = button.y() + BADGE_MARGIN;
+    draw::set_draw_color(Color::Red);
+    draw::draw_pie(x, y, BADGE_SIZE, BADGE_SIZE, 0.0, 360.0);
+    draw::set_draw_color(Color::White);
+    draw::set_font(Font::HelveticaBold, BADGE_FONT_SIZE);
+    draw::draw_text2(&text, x, y, BADGE_SIZE, BADGE_SIZE, Align::Center);
+}
+
 fn not_implemented_callback(_: &mut impl WidgetExt) {
     dialog::alert_default("This feature is not yet implemented in the current release.");
 }
@@ -1,40 +1,56 @@
 use std::borrow::Cow;
-use std::cell::RefCell;
-use std::path::Path;
+use std::cell::{Cell, RefCell};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::Arc;
 
 use bbscope::{BBCode, BBCodeTagConfig};
 use bit_vec::BitVec;
-use fltk::app;
-use fltk::button::Button;
-use fltk::dialog::{alert_default, FileDialogOptions, FileDialogType, NativeFileChooser};
-use fltk::enums::{Align, Event, FrameType};
+use chrono::Local;
+use dynabus::Bus;
+use fltk::app::{self, TimeoutHandle};
+use fltk::button::{Button, ToggleButton};
+use fltk::dialog::{
+    alert_default, message_default, FileDialogOptions, FileDialogType, NativeFileChooser,
+};
+use fltk::enums::{Align, Color, Event, FrameType, Key, Shortcut};
+use fltk::frame::Frame;
 use fltk::group::{Group, Tile};
+use fltk::menu::MenuItem;
 use fltk::prelude::*;
 use fltk::table::TableContext;
+use fltk::text::{TextBuffer, TextEditor, WrapMode};
 use fltk::window::Window;
 use fltk_float::grid::{CellAlign, Grid, GridBuilder};
 use fltk_float::{EmptyElement, LayoutElement, SimpleWrapper};
 use fltk_webview::Webview;
 use lazy_static::lazy_static;
 use size::Size;
-use slog::{error, Logger};
-
-use crate::game::{Game, ModEntry, ModProvenance, ModRef, Mods};
-use crate::mod_manager::ModManager;
+use slog::{error, warn, Logger};
+
+use crate::bus::AppBus;
+use crate::config::ConfigManager;
+use crate::game::platform::steam::{workshop_item_url, workshop_url};
+use crate::game::{Game, ModEntry, ModProvenance, ModRef, ModStats, Mods};
+use crate::mod_manager::{InstalledModsChanged, ModManager, ModNamesResolved, ModStatsLoaded};
+use crate::mod_notes::{mod_notes_key, ModNotes};
+use crate::server_manager::{ServerMods, ServerModsSelected};
 use crate::util::weak_cb;
 
 use super::prelude::*;
 use super::widgets::{
-    use_inspector_macros, DataTable, DataTableProperties, DataTableUpdate, Inspector,
-    PropertiesTable, PropertyRow,
+    draw_table_cell, use_inspector_macros, DataTable, DataTableProperties, DataTableUpdate,
+    Inspector, PropertiesTable, PropertyRow,
 };
-use super::{alert_error, is_table_nav_event, prompt_confirm, wrapper_factory};
+use super::{alert_error, glyph, is_table_nav_event, prompt_confirm, wrapper_factory};
 
+#[derive(Clone, Copy)]
 enum Selection {
     Available(usize),
     Active(usize),
+    Pending(usize),
 }
 
 impl Selection {
@@ -51,7 +67,12 @@ struct ModListState {
     installed: Arc<Mods>,
     available: Vec<ModRef>,
     active: Vec<ModRef>,
+    pending: Vec<(u64, Option<String>)>,
     selection: Option<Selection>,
+    favorites_only: bool,
+    sort_by_uses: bool,
+    pre_clear_mods: Option<Vec<ModRef>>,
+    conflicts: BitVec,
 }
 
 impl ModListState {
@@ -60,7 +81,12 @@ impl ModListState {
             installed: mods,
             available: Vec::new(),
             active: Vec::new(),
+            pending: Vec::new(),
             selection: None,
+            favorites_only: false,
+            sort_by_uses: false,
+            pre_clear_mods: None,
+            conflicts: BitVec::new(),
         }
     }
 
@@ -80,29 +106,90 @@ impl ModListState {
         }
     }
 
+    fn selected_mod_idx(&self) -> Option<usize> {
+        let mod_ref = match self.selection {
+            Some(Selection::Available(idx)) => &self.available[idx],
+            Some(Selection::Active(idx)) => &self.active[idx],
+            _ => return None,
+        };
+        match mod_ref {
+            ModRef::Installed(idx) | ModRef::Disabled(idx) => Some(*idx),
+            _ => None,
+        }
+    }
+
     fn selected_mod(&self) -> Option<&ModEntry> {
         match self.selection {
             None => None,
             Some(Selection::Available(idx)) => self.installed.get(&self.available[idx]),
             Some(Selection::Active(idx)) => self.installed.get(&self.active[idx]),
+            Some(Selection::Pending(_)) => None,
         }
     }
+
+    fn refresh_conflicts(&mut self) {
+        let mut folders: HashMap<&str, usize> = HashMap::new();
+        let mut conflicts = BitVec::from_elem(self.active.len(), false);
+        for (idx, mod_ref) in self.active.iter().enumerate() {
+            let Some(folder_name) = self.active_folder_name(mod_ref) else {
+                continue;
+            };
+            if let Some(&other_idx) = folders.get(folder_name) {
+                conflicts.set(other_idx, true);
+                conflicts.set(idx, true);
+            } else {
+                folders.insert(folder_name, idx);
+            }
+        }
+        self.conflicts = conflicts;
+    }
+
+    fn conflicting_mod(&self, idx: usize) -> Option<&ModEntry> {
+        if !self.conflicts.get(idx).unwrap_or_default() {
+            return None;
+        }
+        let folder_name = self.active_folder_name(&self.active[idx])?;
+        self.active
+            .iter()
+            .enumerate()
+            .find(|&(other_idx, mod_ref)| {
+                other_idx != idx && self.active_folder_name(mod_ref) == Some(folder_name)
+            })
+            .and_then(|(_, mod_ref)| self.installed.get(mod_ref))
+    }
+
+    fn active_folder_name<'s>(&'s self, mod_ref: &'s ModRef) -> Option<&'s str> {
+        if let ModRef::Disabled(_) = mod_ref {
+            return None;
+        }
+        let entry = self.installed.get(mod_ref)?;
+        entry
+            .info
+            .as_ref()
+            .ok()
+            .map(|info| info.folder_name.as_str())
+    }
 }
 
-type ModRow = [String; 4];
+type ModRow = [String; 5];
 
 pub(super) struct ModManagerTab {
     logger: Logger,
     game: Arc<Game>,
+    config: Rc<ConfigManager>,
     mod_mgr: Rc<ModManager>,
     grid: Grid<Tile>,
     root: Tile,
     available_list: DataTable<ModRow>,
     active_list: DataTable<ModRow>,
     details_table: PropertiesTable<ModEntry, ()>,
+    notes_editor: TextEditor,
+    notes_buffer: TextBuffer,
     fix_errors_button: Button,
     activate_button: Button,
     deactivate_button: Button,
+    toggle_disabled_button: Button,
+    toggle_favorite_button: Button,
     move_top_button: Button,
     move_up_button: Button,
     move_down_button: Button,
@@ -110,11 +197,25 @@ pub(super) struct ModManagerTab {
     description_button: Button,
     change_notes_button: Button,
     update_mods_button: Button,
+    match_server_button: Button,
     state: RefCell<ModListState>,
+    server_mods: RefCell<Option<ServerMods>>,
+    undo_clear_button: Button,
+    undo_clear_timeout: Cell<Option<TimeoutHandle>>,
+    malformed_lines: RefCell<Vec<(usize, String)>>,
+    file_dropped: Cell<bool>,
+    mod_notes: RefCell<ModNotes>,
+    notes_key: RefCell<Option<String>>,
 }
 
 impl ModManagerTab {
-    pub fn new(logger: &Logger, game: Arc<Game>, mod_mgr: Rc<ModManager>) -> Rc<Self> {
+    pub fn new(
+        logger: &Logger,
+        bus: Rc<RefCell<AppBus>>,
+        game: Arc<Game>,
+        config: Rc<ConfigManager>,
+        mod_mgr: Rc<ModManager>,
+    ) -> Rc<Self> {
         let mut row_tiles = GridBuilder::with_factory(Tile::default_fill(), wrapper_factory());
         row_tiles.col().with_stretch(1).add();
 
@@ -136,6 +237,7 @@ impl ModManagerTab {
                 ("Available Mods", Align::Left).into(),
                 ("Version", Align::Left).into(),
                 ("Author", Align::Left).into(),
+                (uses_header(false), Align::Right).into(),
             ],
             cell_padding: 4,
             cell_selection_color: fltk::enums::Color::Free,
@@ -201,9 +303,16 @@ impl ModManagerTab {
             .unwrap()
             .wrap(Button::default())
             .with_label("@fix_errors")
-            .with_tooltip("Try to fix the errors in the mod list");
+            .with_tooltip(TIP_FIX_ERRORS);
         fix_errors_button.deactivate();
         button_grid.row().add();
+        let mut favorites_only_button = button_grid
+            .cell()
+            .unwrap()
+            .wrap(ToggleButton::default())
+            .with_label("@funnel")
+            .with_tooltip("Show only favorite mods in the available list");
+        button_grid.row().add();
         button_grid
             .cell()
             .unwrap()
@@ -215,14 +324,28 @@ impl ModManagerTab {
             .unwrap()
             .wrap(Button::default())
             .with_label("@>")
-            .with_tooltip("Activate the selected mod");
+            .with_tooltip(TIP_ACTIVATE);
         button_grid.row().add();
         let mut deactivate_button = button_grid
             .cell()
             .unwrap()
             .wrap(Button::default())
             .with_label("@<")
-            .with_tooltip("Deactivate the selected mod");
+            .with_tooltip(TIP_DEACTIVATE);
+        button_grid.row().add();
+        let mut toggle_disabled_button = button_grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("@||")
+            .with_tooltip(TIP_TOGGLE_DISABLED);
+        button_grid.row().add();
+        let mut toggle_favorite_button = button_grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("@heart")
+            .with_tooltip(TIP_TOGGLE_FAVORITE);
         button_grid.row().add();
         button_grid
             .cell()
@@ -235,28 +358,43 @@ impl ModManagerTab {
             .unwrap()
             .wrap(Button::default())
             .with_label("@#8>|")
-            .with_tooltip("Move the selected mod to top");
+            .with_tooltip(TIP_MOVE_TOP);
         button_grid.row().add();
         let mut move_up_button = button_grid
             .cell()
             .unwrap()
             .wrap(Button::default())
             .with_label("@#8>")
-            .with_tooltip("Move the selected mod up");
+            .with_tooltip(TIP_MOVE_UP);
         button_grid.row().add();
         let mut move_down_button = button_grid
             .cell()
             .unwrap()
             .wrap(Button::default())
             .with_label("@#2>")
-            .with_tooltip("Move the selected mod down");
+            .with_tooltip(TIP_MOVE_DOWN);
         button_grid.row().add();
         let mut move_bottom_button = button_grid
             .cell()
             .unwrap()
             .wrap(Button::default())
             .with_label("@#2>|")
-            .with_tooltip("Move the selected mod to the bottom");
+            .with_tooltip(TIP_MOVE_BOTTOM);
+        button_grid.row().add();
+        let mut auto_sort_button = button_grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("@menu")
+            .with_tooltip("Sort the active mods by their dependencies");
+        button_grid.row().add();
+        let mut match_server_button = button_grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("@arrow_repeat")
+            .with_tooltip(TIP_MATCH_SERVER_MODS);
+        match_server_button.deactivate();
         button_grid.row().add();
         button_grid
             .cell()
@@ -269,7 +407,7 @@ impl ModManagerTab {
             .unwrap()
             .wrap(Button::default())
             .with_label("@info")
-            .with_tooltip("Show selected mod's description");
+            .with_tooltip(TIP_DESCRIPTION);
         description_button.deactivate();
         button_grid.row().add();
         let mut change_notes_button = button_grid
@@ -277,7 +415,7 @@ impl ModManagerTab {
             .unwrap()
             .wrap(Button::default())
             .with_label("@news")
-            .with_tooltip("Show selected mod's change notes");
+            .with_tooltip(TIP_CHANGE_NOTES);
         change_notes_button.deactivate();
         button_grid.row().add();
         button_grid
@@ -293,6 +431,27 @@ impl ModManagerTab {
             .with_label("@cloud_download")
             .with_tooltip("Update outdated mods");
         update_mods_button.deactivate();
+        button_grid.row().add();
+        let mut find_by_id_button = button_grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("@search")
+            .with_tooltip("Find an installed mod by its Steam Workshop ID");
+        button_grid.row().add();
+        let mut subscribe_button = button_grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("@+")
+            .with_tooltip("Subscribe to a Steam Workshop mod by its ID");
+        button_grid.row().add();
+        let mut browse_workshop_button = button_grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("@steam")
+            .with_tooltip("Browse the Steam Workshop");
 
         button_grid.row().with_stretch(1).add();
         button_grid.cell().unwrap().skip();
@@ -346,6 +505,9 @@ impl ModManagerTab {
             .with_vert_align(CellAlign::Stretch)
             .add(col_tiles);
 
+        if !config.get().show_mod_uses {
+            available_list.set_col_width(USES_COLUMN, 0);
+        }
         available_list.set_flex_col(1);
         active_list.set_flex_col(1);
 
@@ -388,7 +550,8 @@ impl ModManagerTab {
         }
 
         row_tiles.row().with_stretch(1).add();
-        let details_table = PropertiesTable::new((), MOD_DETAILS_ROWS, "Mod Details");
+        let mut details_table = PropertiesTable::new((), MOD_DETAILS_ROWS, "Mod Details", true);
+        details_table.set_tooltip("Double-click a value to copy it to the clipboard");
         row_tiles
             .cell()
             .unwrap()
@@ -398,6 +561,35 @@ impl ModManagerTab {
                 Default::default(),
             ));
 
+        row_tiles.row().with_stretch(1).add();
+        let mut notes_grid = Grid::builder_with_factory(wrapper_factory());
+        notes_grid.col().with_stretch(1).add();
+        notes_grid.row().add();
+        notes_grid
+            .cell()
+            .unwrap()
+            .wrap(Frame::default())
+            .with_label("Mod Notes")
+            .with_align(Align::Left | Align::Inside);
+        notes_grid.row().with_stretch(1).add();
+        let notes_buffer = TextBuffer::default();
+        let mut notes_editor = TextEditor::default();
+        notes_editor.set_buffer(notes_buffer.clone());
+        notes_editor.wrap_mode(WrapMode::AtBounds, 0);
+        notes_editor
+            .set_tooltip("Private notes about the selected mod, saved when you leave the editor");
+        notes_editor.deactivate();
+        notes_grid
+            .cell()
+            .unwrap()
+            .with_vert_align(CellAlign::Stretch)
+            .add(SimpleWrapper::new(notes_editor.clone(), Default::default()));
+        row_tiles
+            .cell()
+            .unwrap()
+            .with_vert_align(CellAlign::Stretch)
+            .add(notes_grid.end());
+
         let grid = row_tiles.end();
         grid.layout_children();
         let mut root = grid.group();
@@ -410,22 +602,44 @@ impl ModManagerTab {
         );
         root.resizable(&row_tile_limits);
 
+        let mut undo_clear_button = Button::default()
+            .with_size(UNDO_CLEAR_WIDTH, UNDO_CLEAR_HEIGHT)
+            .with_label("Undo Clear")
+            .with_tooltip("Restore the mod list as it was before it was cleared");
+        undo_clear_button.hide();
+        root.add(&undo_clear_button);
+
         root.hide();
 
-        let state = RefCell::new(ModListState::new(Arc::clone(game.installed_mods())));
+        let state = RefCell::new(ModListState::new(game.installed_mods()));
+
+        let mut mod_notes = ModNotes::new(&game);
+        if let Err(err) = mod_notes.load() {
+            warn!(
+                logger,
+                "Error loading mod notes";
+                "path" => mod_notes.path().display(),
+                "error" => %err,
+            );
+        }
 
         let this = Rc::new(Self {
             logger: logger.clone(),
             game,
+            config,
             mod_mgr,
             grid,
             root: root.clone(),
             available_list: available_list.clone(),
             active_list: active_list.clone(),
             details_table,
+            notes_editor: notes_editor.clone(),
+            notes_buffer,
             fix_errors_button: fix_errors_button.clone(),
             activate_button: activate_button.clone(),
             deactivate_button: deactivate_button.clone(),
+            toggle_disabled_button: toggle_disabled_button.clone(),
+            toggle_favorite_button: toggle_favorite_button.clone(),
             move_top_button: move_top_button.clone(),
             move_up_button: move_up_button.clone(),
             move_down_button: move_down_button.clone(),
@@ -433,27 +647,91 @@ impl ModManagerTab {
             description_button: description_button.clone(),
             change_notes_button: change_notes_button.clone(),
             update_mods_button: update_mods_button.clone(),
+            match_server_button: match_server_button.clone(),
             state,
+            server_mods: RefCell::new(None),
+            undo_clear_button: undo_clear_button.clone(),
+            undo_clear_timeout: Cell::new(None),
+            malformed_lines: RefCell::new(Vec::new()),
+            file_dropped: Cell::new(false),
+            mod_notes: RefCell::new(mod_notes),
+            notes_key: RefCell::new(None),
         });
 
         this.update_actions();
 
+        let _ = available_list.clone().with_draw_fn(weak_cb!(
+            [this] => |table, row, col, x, y, w, h| this.draw_available_cell(table, row, col, x, y, w, h)
+        ));
+        let _ = active_list.clone().with_draw_fn(weak_cb!(
+            [this] => |table, row, col, x, y, w, h| this.draw_active_cell(table, row, col, x, y, w, h)
+        ));
+
         root.handle(weak_cb!([this] => |_, event| {
-            if let Event::Show = event {
-                this.on_show();
+            match event {
+                Event::Show => this.on_show(),
+                Event::Hide => this.dismiss_undo_clear(),
+                Event::DndEnter | Event::DndDrag => return true,
+                Event::DndRelease => {
+                    this.file_dropped.set(true);
+                    return true;
+                }
+                Event::Paste if this.file_dropped.replace(false) => {
+                    this.mod_list_dropped(&app::event_text());
+                    return true;
+                }
+                _ => (),
+            }
+        }; false));
+
+        bus.borrow_mut().subscribe_consumer(weak_cb!(
+            [this] => |ModStatsLoaded(idx, stats)| this.mod_stats_loaded(idx, stats)
+        ));
+        bus.borrow_mut().subscribe_observer(weak_cb!(
+            [this] => |&InstalledModsChanged| this.installed_mods_changed()
+        ));
+        bus.borrow_mut().subscribe_consumer(weak_cb!(
+            [this] => |ModNamesResolved(names)| this.mod_names_resolved(names)
+        ));
+        bus.borrow_mut().subscribe_consumer(weak_cb!(
+            [this] => |ServerModsSelected(server_mods)| this.server_mods_selected(server_mods)
+        ));
+
+        notes_editor.handle(weak_cb!([this] => |_, event| {
+            if event == Event::Unfocus {
+                this.save_mod_note();
+            }
+        }; false));
+
+        available_list.handle(weak_cb!([this] => |_, event| {
+            if this.list_key_pressed(true, event) {
+                return true;
+            }
+        }; false));
+        active_list.handle(weak_cb!([this] => |_, event| {
+            if this.list_key_pressed(false, event) {
+                return true;
             }
         }; false));
 
         available_list.set_callback(weak_cb!(
             [this] => |_| {
-                if is_table_nav_event()
-                    && this.available_list.callback_context() == TableContext::Cell
-                {
-                    if app::event_clicks() {
-                        this.activate_clicked();
-                    } else {
-                        this.available_clicked();
+                match this.available_list.callback_context() {
+                    TableContext::ColHeader => {
+                        if app::event_mouse_button() == app::MouseButton::Right {
+                            this.available_header_right_clicked();
+                        } else {
+                            this.available_header_clicked();
+                        }
+                    }
+                    TableContext::Cell if is_table_nav_event() => {
+                        if app::event_clicks() {
+                            this.available_double_clicked();
+                        } else {
+                            this.available_clicked();
+                        }
                     }
+                    _ => (),
                 }
             }
         ));
@@ -472,19 +750,30 @@ impl ModManagerTab {
         ));
 
         clear_button.set_callback(weak_cb!([this] => |_| this.clear_clicked()));
+        undo_clear_button.set_callback(weak_cb!([this] => |_| this.undo_clear_clicked()));
         import_button.set_callback(weak_cb!([this] => |_| this.import_clicked()));
         export_button.set_callback(weak_cb!([this] => |_| this.export_clicked()));
         copy_modlist_button.set_callback(weak_cb!([this] => |_| this.copy_modlist_clicked()));
         fix_errors_button.set_callback(weak_cb!([this] => |_| this.fix_errors_clicked()));
         activate_button.set_callback(weak_cb!([this] => |_| this.activate_clicked()));
         deactivate_button.set_callback(weak_cb!([this] => |_| this.deactivate_clicked()));
+        toggle_disabled_button.set_callback(weak_cb!([this] => |_| this.toggle_disabled_clicked()));
+        toggle_favorite_button.set_callback(weak_cb!([this] => |_| this.toggle_favorite_clicked()));
+        favorites_only_button.set_callback(weak_cb!(
+            [this] => |button| this.favorites_only_toggled(button.is_toggled())
+        ));
         move_top_button.set_callback(weak_cb!([this] => |_| this.move_top_clicked()));
         move_up_button.set_callback(weak_cb!([this] => |_| this.move_up_clicked()));
         move_down_button.set_callback(weak_cb!([this] => |_| this.move_down_clicked()));
         move_bottom_button.set_callback(weak_cb!([this] => |_| this.move_bottom_clicked()));
+        auto_sort_button.set_callback(weak_cb!([this] => |_| this.auto_sort_clicked()));
+        match_server_button.set_callback(weak_cb!([this] => |_| this.match_server_clicked()));
         update_mods_button.set_callback(weak_cb!([this] => |_| this.update_mods_clicked()));
         description_button.set_callback(weak_cb!([this] => |_| this.show_description()));
         change_notes_button.set_callback(weak_cb!([this] => |_| this.show_change_notes()));
+        find_by_id_button.set_callback(weak_cb!([this] => |_| this.find_by_id_clicked()));
+        subscribe_button.set_callback(weak_cb!([this] => |_| this.subscribe_clicked()));
+        browse_workshop_button.set_callback(weak_cb!([this] => |_| this.browse_workshop_clicked()));
 
         this
     }
@@ -495,17 +784,46 @@ impl ModManagerTab {
 
     fn on_show(&self) {
         self.mod_mgr.check_mod_updates();
-        let active_mods = match self.game.load_mod_list() {
-            Ok(mods) => mods,
+        let lines = match self.game.load_mod_list_lines() {
+            Ok(lines) => lines,
             Err(err) => {
                 error!(self.logger, "Error loading mod list"; "error" => %err);
                 alert_error(ERR_LOADING_MOD_LIST, &err);
                 return;
             }
         };
+        let mut active_mods = Vec::with_capacity(lines.len());
+        let mut malformed_lines = Vec::new();
+        for line in lines {
+            match line {
+                Ok(mod_ref) => active_mods.push(mod_ref),
+                Err(malformed) => malformed_lines.push(malformed),
+            }
+        }
+        *self.malformed_lines.borrow_mut() = malformed_lines;
         self.populate_state(active_mods);
     }
 
+    fn load_numbered_mod_list(&self) -> Option<(Vec<(usize, ModRef)>, Vec<(usize, String)>)> {
+        let lines = match self.game.load_numbered_mod_list() {
+            Ok(lines) => lines,
+            Err(err) => {
+                error!(self.logger, "Error loading mod list"; "error" => %err);
+                alert_error(ERR_LOADING_MOD_LIST, &err);
+                return None;
+            }
+        };
+        let mut active_mods = Vec::with_capacity(lines.len());
+        let mut malformed_lines = Vec::new();
+        for (line, entry) in lines {
+            match entry {
+                Ok(mod_ref) => active_mods.push((line, mod_ref)),
+                Err(text) => malformed_lines.push((line, text)),
+            }
+        }
+        Some((active_mods, malformed_lines))
+    }
+
     fn populate_state(&self, active_mods: Vec<ModRef>) {
         let mut state = self.state.borrow_mut();
         let mod_count = state.installed.len();
@@ -514,28 +832,57 @@ impl ModManagerTab {
         state.active = Vec::with_capacity(mod_count);
 
         let mut available_set = BitVec::from_elem(mod_count, true);
-        let mut errors_found = false;
         for mod_ref in active_mods {
-            if let ModRef::Installed(mod_idx) = mod_ref {
+            if let ModRef::Installed(mod_idx) | ModRef::Disabled(mod_idx) = mod_ref {
                 available_set.set(mod_idx, false);
             }
-            if let ModRef::UnknownPakPath(_) = mod_ref {
-                errors_found = true;
-            }
             state.active.push(mod_ref);
         }
+        state.refresh_conflicts();
 
         for mod_idx in 0..mod_count {
-            if available_set[mod_idx] {
+            if available_set[mod_idx]
+                && (!state.favorites_only || self.is_favorite(&state.installed[mod_idx]))
+            {
                 state.available.push(ModRef::Installed(mod_idx));
             }
         }
+        if state.sort_by_uses {
+            let mut available = std::mem::take(&mut state.available);
+            available
+                .sort_by_key(|mod_ref| self.available_order(&state, mod_ref.to_index().unwrap()));
+            state.available = available;
+        }
+
+        state.pending =
+            if state.favorites_only { Vec::new() } else { self.mod_mgr.pending_downloads() };
 
         drop(state);
 
         self.populate_tables();
+        self.update_fix_errors_button();
+    }
 
-        self.fix_errors_button.clone().set_activated(errors_found);
+    fn update_fix_errors_button(&self) {
+        let state = self.state.borrow();
+        let mut problems: Vec<String> = self
+            .malformed_lines
+            .borrow()
+            .iter()
+            .map(|(line, text)| format!("Line {}: invalid path '{}'", line, text))
+            .collect();
+        problems.extend(state.active.iter().filter_map(|mod_ref| match mod_ref {
+            ModRef::UnknownPakPath(path) => Some(format!("Mod not found: '{}'", path.display())),
+            _ => None,
+        }));
+
+        let mut button = self.fix_errors_button.clone();
+        button.set_activated(!problems.is_empty());
+        if problems.is_empty() {
+            button.set_tooltip(TIP_FIX_ERRORS);
+        } else {
+            button.set_tooltip(&format!("{}:\n{}", TIP_FIX_ERRORS, problems.join("\n")));
+        }
     }
 
     fn populate_tables(&self) {
@@ -544,31 +891,219 @@ impl ModManagerTab {
             .clone()
             .set_activated(state.installed.iter().any(|entry| entry.needs_update()));
 
+        let config = self.config.get();
         populate_table(
             &mut self.available_list.clone(),
+            &self.mod_mgr,
             &state.installed,
             &state.available,
+            &config.favorite_mods,
         );
+        mutate_table(&self.available_list, |data| {
+            data.extend(state.pending.iter().map(make_pending_row))
+        });
         populate_table(
             &mut self.active_list.clone(),
+            &self.mod_mgr,
             &state.installed,
             &state.active,
+            &config.favorite_mods,
         );
     }
 
+    fn mod_names_resolved(&self, names: Vec<(u64, String)>) {
+        let mut resolved = false;
+        {
+            let mut state = self.state.borrow_mut();
+            for (mod_id, name) in state.pending.iter_mut().filter(|(_, name)| name.is_none()) {
+                if let Some((_, resolved_name)) = names.iter().find(|(id, _)| id == mod_id) {
+                    *name = Some(resolved_name.clone());
+                    resolved = true;
+                }
+            }
+        }
+        if resolved {
+            self.populate_tables();
+        }
+    }
+
+    fn server_mods_selected(&self, server_mods: Option<ServerMods>) {
+        let mut button = self.match_server_button.clone();
+        match &server_mods {
+            Some(server_mods) => {
+                button.set_tooltip(&format!(
+                    "{}: {}",
+                    TIP_MATCH_SERVER_MODS, server_mods.server_name
+                ));
+                button.activate();
+            }
+            None => {
+                button.set_tooltip(TIP_MATCH_SERVER_MODS);
+                button.deactivate();
+            }
+        }
+        *self.server_mods.borrow_mut() = server_mods;
+    }
+
+    fn match_server_clicked(&self) {
+        let Some(server_mods) = self.server_mods.borrow().clone() else {
+            return;
+        };
+        match self.mod_mgr.match_server_mods(&server_mods) {
+            Ok(Some(active_mods)) => {
+                self.set_selection(None);
+                self.populate_state(active_mods);
+            }
+            Ok(None) => (),
+            Err(err) => {
+                error!(self.logger, "Error matching the server mods"; "error" => %err);
+                alert_error(ERR_MATCHING_SERVER_MODS, &err);
+            }
+        }
+    }
+
+    fn available_header_clicked(&self) {
+        if self.available_list.callback_col() != USES_COLUMN {
+            return;
+        }
+
+        let sort_by_uses = !self.state.borrow().sort_by_uses;
+        self.set_sort_by_uses(sort_by_uses);
+    }
+
+    fn available_header_right_clicked(&self) {
+        let show_uses = !self.config.get().show_mod_uses;
+        let menu = MenuItem::new(&[if show_uses { MENU_SHOW_USES } else { MENU_HIDE_USES }]);
+        if menu.popup(app::event_x(), app::event_y()).is_none() {
+            return;
+        }
+
+        self.config
+            .update(|config| config.show_mod_uses = show_uses);
+
+        let mut available_list = self.available_list.clone();
+        let old_width = available_list.col_width(USES_COLUMN);
+        let new_width = if show_uses { USES_COLUMN_WIDTH } else { 0 };
+        available_list.set_col_width(USES_COLUMN, new_width);
+        let flex_width = available_list.col_width(1) + old_width - new_width;
+        available_list.set_col_width(1, flex_width);
+
+        if !show_uses && self.state.borrow().sort_by_uses {
+            self.set_sort_by_uses(false);
+        }
+    }
+
+    fn set_sort_by_uses(&self, sort_by_uses: bool) {
+        self.state.borrow_mut().sort_by_uses = sort_by_uses;
+        {
+            let props = self.available_list.properties();
+            let mut props = props.borrow_mut();
+            props.columns[USES_COLUMN as usize].header = uses_header(sort_by_uses);
+        }
+        self.available_list.updated(DataTableUpdate::PROPERTIES);
+
+        let active_mods = self.state.borrow().active.clone();
+        self.set_selection(None);
+        self.populate_state(active_mods);
+    }
+
+    fn available_order(&self, state: &ModListState, mod_idx: usize) -> (Reverse<u32>, usize) {
+        let uses = if state.sort_by_uses {
+            self.mod_mgr.activation_count(&state.installed[mod_idx])
+        } else {
+            0
+        };
+        (Reverse(uses), mod_idx)
+    }
+
     fn available_clicked(&self) {
         let mut table = self.available_list.clone();
         let _ = table.take_focus();
 
-        let selection = Selection::from_row(Selection::Available, table.callback_row());
-        self.set_selection(selection);
+        self.select_available_row(table.callback_row());
     }
 
     fn active_clicked(&self) {
         let mut table = self.active_list.clone();
         let _ = table.take_focus();
 
-        let selection = Selection::from_row(Selection::Active, table.callback_row());
+        self.select_active_row(table.callback_row());
+    }
+
+    fn list_key_pressed(&self, from_available: bool, event: Event) -> bool {
+        if event != Event::KeyDown {
+            return false;
+        }
+        let (table, other_table) = if from_available {
+            (&self.available_list, &self.active_list)
+        } else {
+            (&self.active_list, &self.available_list)
+        };
+
+        match app::event_key() {
+            key @ (Key::Up | Key::Down) => {
+                let row_count = table.rows();
+                if row_count == 0 {
+                    return true;
+                }
+                let (current_row, _, _, _) = table.get_selection();
+                let row_idx = match (current_row, key) {
+                    (row, _) if row < 0 => 0,
+                    (row, Key::Up) => (row - 1).max(0),
+                    (row, _) => (row + 1).min(row_count - 1),
+                };
+                self.select_row(from_available, row_idx);
+                true
+            }
+            Key::Tab => {
+                // Tab only moves from the available list to the active one, and Shift+Tab back
+                let backward = app::event_state().contains(Shortcut::Shift);
+                if backward == from_available {
+                    return false;
+                }
+                let mut other_table = other_table.clone();
+                let _ = other_table.take_focus();
+                let (current_row, _, _, _) = other_table.get_selection();
+                if (current_row < 0) && (other_table.rows() > 0) {
+                    self.select_row(!from_available, 0);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn select_row(&self, available: bool, row_idx: i32) {
+        let mut table =
+            if available { self.available_list.clone() } else { self.active_list.clone() };
+        table.set_selection(row_idx, 0, row_idx, table.cols() - 1);
+        if let Some((top, bottom, _, _)) = table.try_visible_cells() {
+            if row_idx < top {
+                table.set_row_position(row_idx);
+            } else if row_idx > bottom {
+                table.set_row_position(row_idx - (bottom - top));
+            }
+        }
+
+        if available {
+            self.select_available_row(row_idx);
+        } else {
+            self.select_active_row(row_idx);
+        }
+    }
+
+    fn select_available_row(&self, row_idx: i32) {
+        let available_count = self.state.borrow().available.len();
+        let selection = if row_idx >= available_count as i32 {
+            Some(Selection::Pending(row_idx as usize - available_count))
+        } else {
+            Selection::from_row(Selection::Available, row_idx)
+        };
+        self.set_selection(selection);
+    }
+
+    fn select_active_row(&self, row_idx: i32) {
+        let selection = Selection::from_row(Selection::Active, row_idx);
         self.set_selection(selection);
     }
 
@@ -580,22 +1115,230 @@ impl ModManagerTab {
                 self.available_list.clone().unset_selection();
                 self.active_list.clone().unset_selection();
             }
-            Some(Selection::Available(_)) => self.active_list.clone().unset_selection(),
+            Some(Selection::Available(_)) | Some(Selection::Pending(_)) => {
+                self.active_list.clone().unset_selection()
+            }
             Some(Selection::Active(_)) => self.available_list.clone().unset_selection(),
         }
         self.details_table.populate(state.selected_mod());
+        let notes_key = state.selected_mod().and_then(mod_notes_key);
+        let selected_idx = state.selected_mod_idx();
         drop(state);
+        self.show_mod_note(notes_key);
+        if let Some(idx) = selected_idx {
+            self.mod_mgr.load_mod_stats(idx);
+        }
         self.update_actions();
+        self.update_conflict_tooltip();
+    }
+
+    fn show_mod_note(&self, key: Option<String>) {
+        self.save_mod_note();
+
+        let mut notes_editor = self.notes_editor.clone();
+        let note = key
+            .as_ref()
+            .and_then(|key| self.mod_notes.borrow().get(key).map(str::to_string));
+        self.notes_buffer
+            .clone()
+            .set_text(note.as_deref().unwrap_or_default());
+        notes_editor.set_activated(key.is_some());
+        *self.notes_key.borrow_mut() = key;
+    }
+
+    fn save_mod_note(&self) {
+        let Some(key) = self.notes_key.borrow().clone() else {
+            return;
+        };
+        let mut mod_notes = self.mod_notes.borrow_mut();
+        if !mod_notes.set(key, &self.notes_buffer.text()) {
+            return;
+        }
+        if let Err(err) = mod_notes.save() {
+            error!(
+                self.logger,
+                "Error saving mod notes";
+                "path" => mod_notes.path().display(),
+                "error" => %err,
+            );
+            alert_error(ERR_SAVING_MOD_NOTES, &err);
+        }
+    }
+
+    fn installed_mods_changed(&self) {
+        let active_mods = {
+            let mut state = self.state.borrow_mut();
+            state.installed = self.game.installed_mods();
+
+            // A newly installed mod might already be in the mod list under its pak path
+            let installed = Arc::clone(&state.installed);
+            state
+                .active
+                .iter()
+                .map(|mod_ref| match mod_ref {
+                    ModRef::UnknownPakPath(path) => installed.by_pak_path(path.as_path()),
+                    _ => mod_ref.clone(),
+                })
+                .collect()
+        };
+        self.populate_state(active_mods);
+    }
+
+    fn mod_stats_loaded(&self, idx: usize, stats: ModStats) {
+        self.game.installed_mods()[idx].set_stats(stats);
+        let state = self.state.borrow();
+        if state.selected_mod_idx() == Some(idx) {
+            self.details_table.populate(state.selected_mod());
+        }
+    }
+
+    fn draw_available_cell(
+        &self,
+        table: &DataTable<ModRow>,
+        row: i32,
+        col: i32,
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+    ) {
+        let pending = self
+            .state
+            .try_borrow()
+            .map(|state| row as usize >= state.available.len())
+            .unwrap_or_default();
+        if !pending {
+            table.default_draw_cell(row, col, x, y, w, h);
+            return;
+        }
+
+        let text = table.cell_text(row, col);
+        let props = table.properties();
+        let props = props.borrow();
+        let fill_color =
+            if table.is_selected(row, col) { props.cell_selection_color } else { props.cell_color };
+        draw_table_cell(
+            &*text,
+            x,
+            y,
+            w,
+            h,
+            props.columns[col as usize].align,
+            props.cell_border_color,
+            fill_color,
+            props.cell_font_color.inactive(),
+            props.cell_font,
+            props.cell_font_size,
+            props.cell_padding,
+        );
+    }
+
+    fn draw_active_cell(
+        &self,
+        table: &DataTable<ModRow>,
+        row: i32,
+        col: i32,
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+    ) {
+        let conflict = col == 1
+            && self
+                .state
+                .try_borrow()
+                .ok()
+                .and_then(|state| state.conflicts.get(row as usize))
+                .unwrap_or_default();
+        if conflict {
+            let text = table.cell_text(row, col);
+            let props = table.properties();
+            let props = props.borrow();
+            let fill_color = if table.is_selected(row, col) {
+                props.cell_selection_color
+            } else {
+                props.cell_color
+            };
+            draw_table_cell(
+                &format!("@error {}", &*text),
+                x,
+                y,
+                w,
+                h,
+                props.columns[col as usize].align,
+                props.cell_border_color,
+                fill_color,
+                CONFLICT_COLOR,
+                props.cell_font,
+                props.cell_font_size,
+                props.cell_padding,
+            );
+            return;
+        }
+
+        table.default_draw_cell(row, col, x, y, w, h);
+        if col == 0 {
+            return;
+        }
+
+        let disabled = self
+            .state
+            .try_borrow()
+            .ok()
+            .and_then(|state| {
+                state
+                    .active
+                    .get(row as usize)
+                    .map(|mod_ref| matches!(mod_ref, ModRef::Disabled(_)))
+            })
+            .unwrap_or_default();
+        if !disabled {
+            return;
+        }
+
+        let props = table.properties();
+        let props = props.borrow();
+        let text = table.cell_text(row, col);
+        fltk::draw::set_font(props.cell_font, props.cell_font_size);
+        let text_width = fltk::draw::width(&text) as i32;
+        let line_x = x + props.cell_padding;
+        let line_y = y + h / 2;
+        let line_width = text_width.min(w - props.cell_padding * 2);
+        fltk::draw::set_draw_color(props.cell_font_color);
+        fltk::draw::draw_line(line_x, line_y, line_x + line_width, line_y);
+    }
+
+    fn update_conflict_tooltip(&self) {
+        let state = self.state.borrow();
+        let conflicting_mod = match state.selection {
+            Some(Selection::Active(idx)) => state.conflicting_mod(idx),
+            _ => None,
+        };
+        let tooltip = match conflicting_mod {
+            Some(entry) => {
+                let name = match &entry.info {
+                    Ok(info) => info.name.clone(),
+                    Err(_) => entry.pak_path.display().to_string(),
+                };
+                format!("{} {}", TIP_LOAD_ORDER_CONFLICT, name)
+            }
+            None => String::new(),
+        };
+        self.active_list.clone().set_tooltip(&tooltip);
     }
 
     fn update_actions(&self) {
         let state = self.state.borrow();
-        let (activate, deactivate, move_up, move_down) = match state.selection {
-            None => (false, false, false, false),
-            Some(Selection::Available(_)) => (true, false, false, false),
+        let (activate, deactivate, toggle_disabled, move_up, move_down) = match state.selection {
+            None | Some(Selection::Pending(_)) => (false, false, false, false, false),
+            Some(Selection::Available(_)) => (true, false, false, false, false),
             Some(Selection::Active(idx)) => {
                 let last_idx = state.active.len() - 1;
-                (false, true, idx > 0, idx < last_idx)
+                let toggle_disabled = matches!(
+                    state.active[idx],
+                    ModRef::Installed(_) | ModRef::Disabled(_)
+                );
+                (false, true, toggle_disabled, idx > 0, idx < last_idx)
             }
         };
 
@@ -606,20 +1349,121 @@ impl ModManagerTab {
 
         self.activate_button.clone().set_activated(activate);
         self.deactivate_button.clone().set_activated(deactivate);
+        self.toggle_disabled_button
+            .clone()
+            .set_activated(toggle_disabled);
         self.move_top_button.clone().set_activated(move_up);
         self.move_up_button.clone().set_activated(move_up);
         self.move_down_button.clone().set_activated(move_down);
         self.move_bottom_button.clone().set_activated(move_down);
+        self.toggle_favorite_button.clone().set_activated(more_info);
         self.description_button.clone().set_activated(more_info);
         self.change_notes_button.clone().set_activated(more_info);
+
+        let mod_name = state
+            .selected_mod()
+            .and_then(|entry| entry.info.as_ref().ok())
+            .map(|info| info.name.as_str());
+        let tooltip = |generic: &str, action: &str, enabled: bool| match mod_name {
+            Some(name) if enabled => format!("{}: {}", action, name),
+            _ => generic.to_string(),
+        };
+
+        self.activate_button
+            .clone()
+            .set_tooltip(&tooltip(TIP_ACTIVATE, "Activate", activate));
+        self.deactivate_button.clone().set_tooltip(&tooltip(
+            TIP_DEACTIVATE,
+            "Deactivate",
+            deactivate,
+        ));
+        self.toggle_disabled_button.clone().set_tooltip(&tooltip(
+            TIP_TOGGLE_DISABLED,
+            "Toggle disabled",
+            toggle_disabled,
+        ));
+        self.move_top_button
+            .clone()
+            .set_tooltip(&tooltip(TIP_MOVE_TOP, "Move to top", move_up));
+        self.move_up_button
+            .clone()
+            .set_tooltip(&tooltip(TIP_MOVE_UP, "Move up", move_up));
+        self.move_down_button
+            .clone()
+            .set_tooltip(&tooltip(TIP_MOVE_DOWN, "Move down", move_down));
+        self.move_bottom_button.clone().set_tooltip(&tooltip(
+            TIP_MOVE_BOTTOM,
+            "Move to bottom",
+            move_down,
+        ));
+        self.toggle_favorite_button.clone().set_tooltip(&tooltip(
+            TIP_TOGGLE_FAVORITE,
+            "Toggle favorite",
+            more_info,
+        ));
+        self.description_button.clone().set_tooltip(&tooltip(
+            TIP_DESCRIPTION,
+            "Description",
+            more_info,
+        ));
+        self.change_notes_button.clone().set_tooltip(&tooltip(
+            TIP_CHANGE_NOTES,
+            "Change notes",
+            more_info,
+        ));
     }
 
-    fn clear_clicked(&self) {
+    fn clear_clicked(self: &Rc<Self>) {
         if self.state.borrow().active.is_empty() || !prompt_confirm(PROMPT_CLEAR_MODS) {
             return;
         }
+        let pre_clear_mods = self.state.borrow().active.clone();
         if self.save_mod_list(Vec::new()) {
             self.populate_state(Vec::new());
+            self.offer_undo_clear(pre_clear_mods);
+        }
+    }
+
+    fn offer_undo_clear(self: &Rc<Self>, pre_clear_mods: Vec<ModRef>) {
+        self.state.borrow_mut().pre_clear_mods = Some(pre_clear_mods);
+
+        let mut button = self.undo_clear_button.clone();
+        button.resize(
+            self.root.x() + (self.root.w() - UNDO_CLEAR_WIDTH) / 2,
+            self.root.y() + self.root.h() - UNDO_CLEAR_HEIGHT - 10,
+            UNDO_CLEAR_WIDTH,
+            UNDO_CLEAR_HEIGHT,
+        );
+        button.show();
+        self.root.clone().redraw();
+
+        let handle = app::add_timeout3(
+            UNDO_CLEAR_TIMEOUT,
+            weak_cb!([this = self] => |_| {
+                this.undo_clear_timeout.take();
+                this.dismiss_undo_clear();
+            }),
+        );
+        self.undo_clear_timeout.set(Some(handle));
+    }
+
+    fn undo_clear_clicked(&self) {
+        let Some(mod_list) = self.state.borrow_mut().pre_clear_mods.take() else {
+            return;
+        };
+        if self.save_mod_list(mod_list.clone()) {
+            self.populate_state(mod_list);
+        }
+    }
+
+    fn dismiss_undo_clear(&self) {
+        if let Some(handle) = self.undo_clear_timeout.take() {
+            app::remove_timeout3(handle);
+        }
+        self.state.borrow_mut().pre_clear_mods = None;
+        if self.undo_clear_button.visible() {
+            self.undo_clear_button.clone().hide();
+            self.root.clone().redraw();
         }
     }
 
@@ -634,7 +1478,27 @@ impl ModManagerTab {
             return;
         }
 
-        let active_mods = match self.mod_mgr.import_mod_list(&mod_list_path) {
+        self.import_mod_list(&mod_list_path);
+    }
+
+    fn mod_list_dropped(&self, text: &str) {
+        let Some(path) = dropped_file_path(text) else {
+            return;
+        };
+        let is_mod_list = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("txt"))
+            .unwrap_or_default();
+        if !is_mod_list {
+            alert_default(ERR_UNSUPPORTED_DROPPED_FILE);
+            return;
+        }
+        self.import_mod_list(&path);
+    }
+
+    fn import_mod_list(&self, mod_list_path: &Path) {
+        self.dismiss_undo_clear();
+        let active_mods = match self.mod_mgr.import_mod_list(mod_list_path) {
             Ok(mods) => mods,
             Err(err) => {
                 error!(self.logger, "Error importing mod list"; "error" => %err);
@@ -642,6 +1506,7 @@ impl ModManagerTab {
                 return;
             }
         };
+        self.malformed_lines.borrow_mut().clear();
         self.populate_state(active_mods);
     }
 
@@ -676,6 +1541,9 @@ impl ModManagerTab {
         let state = self.state.borrow();
         let mut text = String::new();
         for mod_ref in state.active.iter() {
+            if let ModRef::Disabled(_) = mod_ref {
+                continue;
+            }
             if let Some(entry) = state.installed.get(mod_ref) {
                 if let Some(id) = entry
                     .info
@@ -690,6 +1558,7 @@ impl ModManagerTab {
             } else {
                 match mod_ref {
                     ModRef::Installed(_) => unreachable!(),
+                    ModRef::Disabled(_) => unreachable!(),
                     ModRef::Custom(_) => unreachable!(),
                     ModRef::UnknownPakPath(path) => write!(text, ",{}", path.display()).unwrap(),
                     ModRef::UnknownFolder(_) => (),
@@ -705,19 +1574,70 @@ impl ModManagerTab {
     }
 
     fn fix_errors_clicked(&self) {
-        let mut mod_list = {
-            let state = self.state.borrow();
-            state.active.clone()
+        // Every change to the list is saved right away, so reload it to get the file's line numbers
+        let Some((mut mod_list, removed)) = self.load_numbered_mod_list() else {
+            return;
         };
-        if !self.mod_mgr.fix_mod_list(&mut mod_list) {
-            alert_default("Could not fix all of the errors in the mod list.");
-        }
-        if let Err(err) = self.game.save_mod_list(mod_list.iter()) {
-            error!(self.logger, "Error saving mod list"; "error" => %err);
-            alert_error(ERR_SAVING_MOD_LIST, &err);
+        let report = self.mod_mgr.fix_mod_list(&mut mod_list);
+        let mod_list: Vec<ModRef> = mod_list.into_iter().map(|(_, mod_ref)| mod_ref).collect();
+        if !self.save_mod_list(mod_list.clone()) {
             return;
         }
         self.populate_state(mod_list);
+
+        let mut message = String::new();
+        if !report.fixed.is_empty() {
+            message.push_str("Fixed the paths of these mods:");
+            for (line, path) in report.fixed {
+                message.push_str(&format!("\nLine {}: '{}'", line, path.display()));
+            }
+        }
+        if !removed.is_empty() {
+            if !message.is_empty() {
+                message.push_str("\n\n");
+            }
+            message.push_str("Removed the malformed lines:");
+            for (line, text) in removed {
+                message.push_str(&format!("\nLine {}: invalid path '{}'", line, text));
+            }
+        }
+        if report.unfixed.is_empty() {
+            if !message.is_empty() {
+                message_default(&message);
+            }
+        } else {
+            if !message.is_empty() {
+                message.push_str("\n\n");
+            }
+            message.push_str(ERR_UNFIXED_MOD_LIST_ERRORS);
+            for (line, path) in report.unfixed {
+                message.push_str(&format!(
+                    "\nLine {}: mod not found '{}'",
+                    line,
+                    path.display()
+                ));
+            }
+            alert_default(&message);
+        }
+    }
+
+    fn available_double_clicked(&self) {
+        let pending_idx = match self.state.borrow().selection {
+            Some(Selection::Pending(idx)) => Some(idx),
+            _ => None,
+        };
+        match pending_idx {
+            Some(idx) => self.download_pending(idx),
+            None => self.activate_clicked(),
+        }
+    }
+
+    fn download_pending(&self, idx: usize) {
+        let mod_id = self.state.borrow().pending[idx].0;
+        if let Err(err) = self.mod_mgr.download_mod(mod_id) {
+            error!(self.logger, "Error downloading mod"; "mod_id" => mod_id, "error" => %err);
+            alert_error(ERR_DOWNLOADING_MOD, &err);
+        }
     }
 
     fn activate_clicked(&self) {
@@ -725,6 +1645,9 @@ impl ModManagerTab {
         let row_idx = state.get_selected_available().unwrap();
 
         let mod_idx = state.available.remove(row_idx);
+        if let Some(entry) = state.installed.get(&mod_idx) {
+            self.mod_mgr.record_activation(entry);
+        }
         state.active.push(mod_idx);
 
         let row = mutate_table(&mut self.available_list.clone(), |data| {
@@ -744,12 +1667,23 @@ impl ModManagerTab {
 
         let mod_ref = state.active.remove(row_idx);
         let row = mutate_table(&mut self.active_list.clone(), |data| data.remove(row_idx));
-        if let ModRef::Installed(mod_idx) = &mod_ref {
+        let hidden = state.favorites_only
+            && !state
+                .installed
+                .get(&mod_ref)
+                .map(|entry| self.is_favorite(entry))
+                .unwrap_or_default();
+        if let (false, ModRef::Installed(mod_idx) | ModRef::Disabled(mod_idx)) = (hidden, mod_ref) {
+            let order = self.available_order(&state, mod_idx);
             let dest_row_idx = state
                 .available
-                .binary_search_by_key(mod_idx, |mod_ref| mod_ref.to_index().unwrap())
+                .binary_search_by_key(&order, |mod_ref| {
+                    self.available_order(&state, mod_ref.to_index().unwrap())
+                })
                 .unwrap_err();
-            state.available.insert(dest_row_idx, mod_ref);
+            state
+                .available
+                .insert(dest_row_idx, ModRef::Installed(mod_idx));
 
             mutate_table(&mut self.available_list.clone(), |data| {
                 data.insert(dest_row_idx, row)
@@ -762,6 +1696,72 @@ impl ModManagerTab {
         self.save_current_mod_list();
     }
 
+    fn toggle_disabled_clicked(&self) {
+        let mut state = self.state.borrow_mut();
+        let row_idx = state.get_selected_active().unwrap();
+        state.active[row_idx] = match state.active[row_idx] {
+            ModRef::Installed(mod_idx) => ModRef::Disabled(mod_idx),
+            ModRef::Disabled(mod_idx) => ModRef::Installed(mod_idx),
+            _ => return,
+        };
+        drop(state);
+
+        self.active_list.clone().redraw();
+        self.save_current_mod_list();
+    }
+
+    fn toggle_favorite_clicked(&self) {
+        let state = self.state.borrow();
+        let entry = state.selected_mod().unwrap();
+        let folder = entry.info.as_ref().unwrap().folder_name.clone();
+        let favorite = !self.config.get().favorite_mods.contains(&folder);
+        self.config.update(|config| {
+            if favorite {
+                config.favorite_mods.insert(folder);
+            } else {
+                config.favorite_mods.remove(&folder);
+            }
+        });
+
+        let glyph =
+            if favorite { FAVORITE_GLYPH.to_string() } else { provenance_glyph(entry.provenance) };
+        let selection = state.selection;
+        let favorites_only = state.favorites_only;
+        drop(state);
+
+        match selection {
+            Some(Selection::Active(row_idx)) => {
+                mutate_table(&self.active_list, |data| data[row_idx][0] = glyph);
+            }
+            Some(Selection::Available(row_idx)) if favorites_only && !favorite => {
+                self.state.borrow_mut().available.remove(row_idx);
+                mutate_table(&self.available_list, |data| data.remove(row_idx));
+                self.set_selection(None);
+            }
+            Some(Selection::Available(row_idx)) => {
+                mutate_table(&self.available_list, |data| data[row_idx][0] = glyph);
+            }
+            _ => (),
+        }
+    }
+
+    fn favorites_only_toggled(&self, favorites_only: bool) {
+        let active_mods = {
+            let mut state = self.state.borrow_mut();
+            state.favorites_only = favorites_only;
+            state.active.clone()
+        };
+        self.set_selection(None);
+        self.populate_state(active_mods);
+    }
+
+    fn is_favorite(&self, entry: &ModEntry) -> bool {
+        match entry.info.as_ref() {
+            Ok(info) => self.config.get().favorite_mods.contains(&info.folder_name),
+            Err(_) => false,
+        }
+    }
+
     fn move_top_clicked(&self) {
         let mut state = self.state.borrow_mut();
         let row_idx = state.get_selected_active().unwrap();
@@ -829,6 +1829,35 @@ impl ModManagerTab {
         self.save_current_mod_list();
     }
 
+    fn auto_sort_clicked(&self) {
+        let mut mod_list = {
+            let state = self.state.borrow();
+            state.active.clone()
+        };
+        let report = self.mod_mgr.sort_by_dependencies(&mut mod_list);
+
+        self.state.borrow_mut().active = mod_list;
+        self.populate_tables();
+        self.set_selection(None);
+        self.save_current_mod_list();
+
+        if !report.missing.is_empty() {
+            let mut message = ERR_MISSING_DEPENDENCIES.to_string();
+            for (name, folder) in report.missing {
+                message.push_str(&format!("\n{} requires {}", name, folder));
+            }
+            alert_default(&message);
+        }
+        if !report.circular.is_empty() {
+            let mut message = ERR_CIRCULAR_DEPENDENCIES.to_string();
+            for name in report.circular {
+                message.push('\n');
+                message.push_str(&name);
+            }
+            alert_default(&message);
+        }
+    }
+
     fn update_mods_clicked(&self) {
         let outdated_mods = self
             .game
@@ -843,14 +1872,84 @@ impl ModManagerTab {
         self.populate_tables();
     }
 
-    fn save_current_mod_list(&self) {
+    fn find_by_id_clicked(&self) {
+        let input = match fltk::dialog::input_default(PROMPT_FIND_WORKSHOP_ID, "") {
+            Some(input) => input,
+            None => return,
+        };
+        let mod_id = match parse_workshop_id(&input) {
+            Some(mod_id) => mod_id,
+            None => {
+                alert_default(ERR_INVALID_WORKSHOP_ID);
+                return;
+            }
+        };
+
         let state = self.state.borrow();
-        self.save_mod_list(state.active.clone());
+        let Some(ModRef::Installed(mod_idx)) =
+            state.installed.by_steam_id(mod_id, self.game.branch())
+        else {
+            drop(state);
+            if prompt_confirm(PROMPT_OPEN_WORKSHOP_PAGE) {
+                show_web_page("Steam Workshop", &workshop_item_url(mod_id));
+            }
+            return;
+        };
+        let is_match = |mod_ref: &ModRef| match mod_ref {
+            ModRef::Installed(idx) | ModRef::Disabled(idx) => *idx == mod_idx,
+            _ => false,
+        };
+        let available_row = state.available.iter().position(is_match);
+        let active_row = state.active.iter().position(is_match);
+        drop(state);
+
+        match (available_row, active_row) {
+            (Some(row), _) => self.select_row(true, row as _),
+            (None, Some(row)) => self.select_row(false, row as _),
+            (None, None) => alert_default(ERR_MOD_FILTERED_OUT),
+        }
+    }
+
+    fn subscribe_clicked(&self) {
+        let input = match fltk::dialog::input_default(PROMPT_WORKSHOP_ID, "") {
+            Some(input) => input,
+            None => return,
+        };
+        let mod_id = match parse_workshop_id(&input) {
+            Some(mod_id) => mod_id,
+            None => {
+                alert_default(ERR_INVALID_WORKSHOP_ID);
+                return;
+            }
+        };
+        if let Err(err) = self.mod_mgr.subscribe_mod(mod_id) {
+            error!(self.logger, "Error subscribing to mod"; "mod_id" => mod_id, "error" => %err);
+            alert_error(ERR_SUBSCRIBING_TO_MOD, &err);
+        }
+    }
+
+    fn browse_workshop_clicked(&self) {
+        show_web_page("Steam Workshop", &workshop_url(self.game.branch()));
+    }
+
+    fn save_current_mod_list(&self) {
+        self.state.borrow_mut().refresh_conflicts();
+        self.active_list.clone().redraw();
+        self.update_conflict_tooltip();
+
+        let mod_list = self.state.borrow().active.clone();
+        self.save_mod_list(mod_list);
+        self.update_fix_errors_button();
     }
 
     fn save_mod_list(&self, mod_list: Vec<ModRef>) -> bool {
+        self.dismiss_undo_clear();
         match self.game.save_mod_list(mod_list.iter()) {
-            Ok(()) => true,
+            Ok(()) => {
+                // Saving rewrites the whole file, so any malformed lines are gone
+                self.malformed_lines.borrow_mut().clear();
+                true
+            }
             Err(err) => {
                 error!(self.logger, "Error saving mod list"; "error" => %err);
                 alert_error(ERR_SAVING_MOD_LIST, &err);
@@ -862,35 +1961,50 @@ impl ModManagerTab {
     fn show_description(&self) {
         let state = self.state.borrow();
         let info = state.selected_mod().unwrap().info.as_ref().unwrap();
-        self.show_bbcode(&format!("Description: {}", &info.name), &info.description);
+        show_bbcode(&format!("Description: {}", &info.name), &info.description);
     }
 
     fn show_change_notes(&self) {
         let state = self.state.borrow();
         let info = state.selected_mod().unwrap().info.as_ref().unwrap();
-        self.show_bbcode(&format!("Change Notes: {}", &info.name), &info.change_notes);
+        show_bbcode(&format!("Change Notes: {}", &info.name), &info.change_notes);
     }
+}
 
-    fn show_bbcode(&self, title: &str, content: &str) {
-        let mut html = BBCODE.parse(content);
-        html = format!(
-            "<html><head><style>{}</style></head><body>{}</body></html",
-            CSS_INFO_BODY, html
-        );
-        html = urlencoding::encode(&html).to_string();
+fn show_web_page(title: &str, url: &str) {
+    let mut popup = Window::default().with_label(title).with_size(1024, 720);
+    popup.make_modal(true);
+    popup.make_resizable(true);
+    popup.end();
+    popup.show();
 
-        let mut popup = Window::default().with_label(title).with_size(800, 600);
-        popup.make_modal(true);
-        popup.make_resizable(true);
-        popup.end();
-        popup.show();
+    let webview = Webview::create(false, &mut popup);
+    webview.navigate(url);
 
-        let webview = Webview::create(false, &mut popup);
-        webview.set_html(&html);
+    while popup.shown() && !app::should_program_quit() {
+        app::wait();
+    }
+}
 
-        while popup.shown() && !app::should_program_quit() {
-            app::wait();
-        }
+pub(super) fn show_bbcode(title: &str, content: &str) {
+    let mut html = BBCODE.parse(content);
+    html = format!(
+        "<html><head><style>{}</style></head><body>{}</body></html",
+        CSS_INFO_BODY, html
+    );
+    html = urlencoding::encode(&html).to_string();
+
+    let mut popup = Window::default().with_label(title).with_size(800, 600);
+    popup.make_modal(true);
+    popup.make_resizable(true);
+    popup.end();
+    popup.show();
+
+    let webview = Webview::create(false, &mut popup);
+    webview.set_html(&html);
+
+    while popup.shown() && !app::should_program_quit() {
+        app::wait();
     }
 }
 
@@ -904,10 +2018,49 @@ impl LayoutElement for ModManagerTab {
     }
 }
 
+const USES_COLUMN: i32 = 4;
+const USES_COLUMN_WIDTH: i32 = 80;
+const MENU_SHOW_USES: &str = "Show Uses Column";
+const MENU_HIDE_USES: &str = "Hide Uses Column";
+const CONFLICT_COLOR: Color = Color::from_rgb(0xc0, 0x80, 0x00);
+const UNDO_CLEAR_WIDTH: i32 = 160;
+const UNDO_CLEAR_HEIGHT: i32 = 30;
+const UNDO_CLEAR_TIMEOUT: f64 = 60.0;
 const DLG_FILTER_MODLIST: &str = "Mod List Files\t*.txt";
 const PROMPT_CLEAR_MODS: &str = "Are you sure you want to clear the mod list?";
 const ERR_LOADING_MOD_LIST: &str = "Error while loading the mod list.";
+const ERR_MATCHING_SERVER_MODS: &str = "Error while matching the server mods.";
 const ERR_SAVING_MOD_LIST: &str = "Error while saving the mod list.";
+const ERR_SUBSCRIBING_TO_MOD: &str = "Error while subscribing to the mod.";
+const ERR_DOWNLOADING_MOD: &str = "Error while downloading the mod.";
+const ERR_UNFIXED_MOD_LIST_ERRORS: &str = "Could not fix all of the errors in the mod list:";
+const ERR_SAVING_MOD_NOTES: &str = "Error while saving the mod notes.";
+const ERR_MISSING_DEPENDENCIES: &str =
+    "The following mods require mods that are not in the active mod list:";
+const ERR_CIRCULAR_DEPENDENCIES: &str =
+    "Could not determine the load order for the following mods due to circular dependencies:";
+const ERR_UNSUPPORTED_DROPPED_FILE: &str = "Only mod list files (*.txt) can be dropped here.";
+const ERR_INVALID_WORKSHOP_ID: &str = "Invalid Steam Workshop ID.";
+const PROMPT_WORKSHOP_ID: &str = "Enter the Steam Workshop ID or URL of the mod:";
+const PROMPT_FIND_WORKSHOP_ID: &str = "Enter the Steam Workshop ID or URL of the mod to find:";
+const PROMPT_OPEN_WORKSHOP_PAGE: &str =
+    "This mod is not installed. Do you want to open its Steam Workshop page?";
+const ERR_MOD_FILTERED_OUT: &str =
+    "This mod is installed, but hidden because only favorite mods are shown.";
+const TIP_FIX_ERRORS: &str = "Try to fix the errors in the mod list";
+const TIP_ACTIVATE: &str = "Activate the selected mod";
+const TIP_DEACTIVATE: &str = "Deactivate the selected mod";
+const TIP_TOGGLE_DISABLED: &str = "Disable or re-enable the selected mod without removing it";
+const TIP_TOGGLE_FAVORITE: &str = "Add or remove the selected mod from favorites";
+const TIP_MOVE_TOP: &str = "Move the selected mod to top";
+const TIP_MOVE_UP: &str = "Move the selected mod up";
+const TIP_MOVE_DOWN: &str = "Move the selected mod down";
+const TIP_MOVE_BOTTOM: &str = "Move the selected mod to the bottom";
+const TIP_DESCRIPTION: &str = "Show selected mod's description";
+const TIP_CHANGE_NOTES: &str = "Show selected mod's change notes";
+const TIP_LOAD_ORDER_CONFLICT: &str = "Load order conflict with";
+const TIP_MATCH_SERVER_MODS: &str = "Match the mods used by the selected server";
+const FAVORITE_GLYPH: &str = "@heart";
 const CSS_INFO_BODY: &str = include_str!("mod_info.css");
 
 use_inspector_macros!(ModEntry, ());
@@ -962,49 +2115,98 @@ const MOD_DETAILS_ROWS: &[Inspector<ModEntry, ()>] = &[
         .as_ref()
         .ok()
         .and_then(|info| opt_str_value(&info.testlive_steam_file_id))),
+    inspect_opt_attr!("Subscribers", |entry| entry
+        .stats()
+        .map(|stats| stats.subscribers.to_string().into())),
+    inspect_opt_attr!("Favorites", |entry| entry
+        .stats()
+        .map(|stats| stats.favorites.to_string().into())),
+    inspect_opt_attr!("Last Updated", |entry| entry
+        .stats()
+        .and_then(|stats| stats.last_updated)
+        .map(|updated| updated
+            .with_timezone(&Local)
+            .format("%c")
+            .to_string()
+            .into())),
 ];
 
 lazy_static! {
     static ref BBCODE: BBCode = BBCode::from_config(BBCodeTagConfig::extended(), None).unwrap();
 }
 
+fn dropped_file_path(text: &str) -> Option<PathBuf> {
+    // Some platforms deliver a list of URIs, so only take the first one
+    let line = text.lines().next()?.trim();
+    if line.is_empty() {
+        return None;
+    }
+    let path = match line.strip_prefix("file://") {
+        Some(uri) => urlencoding::decode(uri).ok()?.into_owned(),
+        None => line.to_string(),
+    };
+    Some(PathBuf::from(path))
+}
+
 fn mod_list_dir(game: &Arc<Game>) -> &Path {
     let path = game.save_path();
     std::fs::create_dir_all(path).ok();
     path
 }
 
-fn populate_table(table: &DataTable<ModRow>, mods: &Mods, refs: &Vec<ModRef>) {
+fn parse_workshop_id(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let text = match text.rfind("id=") {
+        Some(idx) => &text[(idx + 3)..],
+        None => text,
+    };
+    text.split('&').next()?.parse().ok()
+}
+
+fn populate_table(
+    table: &DataTable<ModRow>,
+    mod_mgr: &ModManager,
+    mods: &Mods,
+    refs: &Vec<ModRef>,
+    favorites: &HashSet<String>,
+) {
     let rows = table.data();
     let mut rows = rows.borrow_mut();
     rows.clear();
 
     for mod_ref in refs {
-        rows.push(make_mod_row(&mods, mod_ref));
+        rows.push(make_mod_row(mod_mgr, &mods, mod_ref, favorites));
     }
     drop(rows);
 
     table.updated(DataTableUpdate::DATA);
 }
 
-fn make_mod_row(mods: &Mods, mod_ref: &ModRef) -> ModRow {
+fn make_mod_row(
+    mod_mgr: &ModManager,
+    mods: &Mods,
+    mod_ref: &ModRef,
+    favorites: &HashSet<String>,
+) -> ModRow {
     if let Some(entry) = mods.get(mod_ref) {
         if let Ok(info) = &entry.info {
+            let glyph = if favorites.contains(&info.folder_name) {
+                FAVORITE_GLYPH.to_string()
+            } else {
+                provenance_glyph(entry.provenance)
+            };
             let version = info.version.to_string();
             let version =
                 if entry.needs_update() { format!("@cloud_download {}", version) } else { version };
-            [
-                provenance_glyph(entry.provenance),
-                info.name.clone(),
-                version,
-                info.author.clone(),
-            ]
+            let uses = mod_mgr.activation_count(entry).to_string();
+            [glyph, info.name.clone(), version, info.author.clone(), uses]
         } else {
             make_err_row(entry.pak_path.display())
         }
     } else {
         match mod_ref {
             ModRef::Installed(_) => unreachable!(),
+            ModRef::Disabled(_) => unreachable!(),
             ModRef::Custom(_) => unreachable!(),
             ModRef::UnknownFolder(folder) => make_err_row(folder),
             ModRef::UnknownPakPath(path) => make_err_row(path.display()),
@@ -1012,15 +2214,34 @@ fn make_mod_row(mods: &Mods, mod_ref: &ModRef) -> ModRow {
     }
 }
 
+fn make_pending_row((mod_id, name): &(u64, Option<String>)) -> ModRow {
+    [
+        "@cloud_download".to_string(),
+        name.clone()
+            .unwrap_or_else(|| format!("Steam Workshop item {}", mod_id)),
+        "Pending download".to_string(),
+        String::new(),
+        String::new(),
+    ]
+}
+
 fn make_err_row<N: std::fmt::Display>(alt_name: N) -> ModRow {
     [
         "@error".to_string(),
         format!("??? ({})", alt_name),
         "???".to_string(),
         "???".to_string(),
+        String::new(),
     ]
 }
 
+fn uses_header(sorted: bool) -> String {
+    format!(
+        "Uses {}",
+        if sorted { glyph::SORT_DESC } else { glyph::UNSORTED }
+    )
+}
+
 fn provenance_glyph(provenance: ModProvenance) -> String {
     match provenance {
         ModProvenance::Local => "@folder".to_string(),
@@ -1,25 +1,33 @@
 use std::cell::{Cell, Ref, RefCell};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use dynabus::Bus;
-use fltk::enums::{Align, Event};
+use fltk::app;
+use fltk::button::Button;
+use fltk::dialog::{FileDialogOptions, FileDialogType, NativeFileChooser};
+use fltk::enums::{Align, Event, Key, Shortcut};
 use fltk::frame::Frame;
 use fltk::group::{Group, Tile};
 use fltk::prelude::*;
 use fltk_float::grid::{CellAlign, Grid, GridBuilder};
 use fltk_float::overlay::Overlay;
 use fltk_float::{LayoutElement, SimpleWrapper, WrapperFactory};
-use slog::{error, warn, Logger};
+use slog::{error, info, warn, Logger};
 use strum::IntoEnumIterator;
 
 use crate::bus::AppBus;
-use crate::config::{ConfigManager, ServerBrowserConfig};
+use crate::config::{ConfigManager, SecurePasswords};
 use crate::game::settings::server::Community;
 use crate::game::Game;
 use crate::gui::data::TableSource;
+use crate::launch_history::{
+    last_visits, launch_history_path, load_launch_history, visited_servers,
+};
 use crate::launcher::{ConnectionInfo, Launcher};
 use crate::mod_manager::ModManager;
 use crate::server_manager::ServerManager;
@@ -30,24 +38,30 @@ use crate::servers::{
 use crate::util::weak_cb;
 
 use super::data::{IterableTableSource, Reindex, RowFilter};
-use super::{alert_error, glyph, wrapper_factory};
+use super::{alert_error, glyph, prompt_confirm, wrapper_factory};
 
 mod actions_pane;
 mod add_server_dialog;
 mod advanced_filter_dialog;
+mod compare_mods_dialog;
 mod connect_dialog;
 mod details_pane;
 mod filter_pane;
 mod list_pane;
+mod notes_search_dialog;
 mod state;
+mod trace_route_dialog;
 
 use self::actions_pane::{Action, ActionsPane};
-use self::add_server_dialog::AddServerDialog;
+use self::add_server_dialog::{AddServerDialog, AddServerDialogResult};
+use self::compare_mods_dialog::CompareModsDialog;
 use self::connect_dialog::ConnectDialog;
 use self::details_pane::DetailsPane;
 use self::filter_pane::{FilterHolder, FilterPane};
 use self::list_pane::ListPane;
+use self::notes_search_dialog::{NotesSearchDialog, NotesSearchMatch};
 use self::state::{Filter, ServerBrowserState, SortOrder};
+use self::trace_route_dialog::TraceRouteDialog;
 
 #[derive(dynabus::Event)]
 pub struct PopulateServers {
@@ -55,6 +69,13 @@ pub struct PopulateServers {
     pub done: bool,
 }
 
+#[derive(dynabus::Event)]
+pub struct AppendServers {
+    pub servers: Vec<Server>,
+    pub loaded_buckets: usize,
+    pub total_buckets: usize,
+}
+
 #[derive(dynabus::Event)]
 pub enum ProcessPongs {
     One(PingResponse),
@@ -76,8 +97,10 @@ pub(super) struct ServerBrowserTab {
     config: Rc<ConfigManager>,
     launcher: Rc<Launcher>,
     server_mgr: Rc<ServerManager>,
+    mod_manager: Rc<ModManager>,
     grid: Grid,
     root: Group,
+    filter_pane: Rc<FilterPane>,
     list_pane: Rc<ListPane>,
     details_pane: DetailsPane,
     actions_pane: Rc<ActionsPane>,
@@ -87,6 +110,8 @@ pub(super) struct ServerBrowserTab {
     deferred_action: Cell<Option<DeferredAction>>,
     filter_dirty: Cell<bool>,
     refreshing: Cell<bool>,
+    refresh_start: Cell<Option<Instant>>,
+    settings_warnings_shown: RefCell<HashSet<(SocketAddr, Vec<&'static str>)>>,
 }
 
 struct BrowserStats {
@@ -95,6 +120,9 @@ struct BrowserStats {
     total_players_text: Frame,
     matching_servers_text: Frame,
     matching_players_text: Frame,
+    clear_filters_button: Button,
+    load_time_text: Frame,
+    load_time_generation: Rc<Cell<u32>>,
     total_servers: Cell<usize>,
     total_players: Cell<usize>,
     matching_servers: Cell<usize>,
@@ -117,11 +145,25 @@ impl ServerBrowserTab {
         server_mgr: Rc<ServerManager>,
         mod_manager: Rc<ModManager>,
     ) -> Rc<Self> {
+        let (visited_servers, last_visits) = match load_launch_history(&launch_history_path(&game))
+        {
+            Ok(history) => (visited_servers(&history), last_visits(&history)),
+            Err(err) => {
+                warn!(logger, "Error loading launch history"; "error" => %err);
+                (HashSet::new(), HashMap::new())
+            }
+        };
+        let last_visits = Rc::new(last_visits);
+
         let browser_cfg = Ref::map(config.get(), |config| &config.server_browser);
         let state = Rc::new(RefCell::new(ServerBrowserState::new(
             Vec::new(),
-            Filter::from_config(&*browser_cfg),
-            SortOrder::new(browser_cfg.sort_criteria, region_sort_order()),
+            Filter::from_config(&*browser_cfg, Arc::new(visited_servers)),
+            SortOrder::new(
+                browser_cfg.sort_criteria,
+                region_sort_order(),
+                Rc::clone(&last_visits),
+            ),
         )));
 
         let mut grid = Grid::builder_with_factory(wrapper_factory())
@@ -141,7 +183,7 @@ impl ServerBrowserTab {
         status_overlay.add(stats_grid);
         let mut loading_label = status_overlay
             .wrap(Frame::default())
-            .with_label(&format!("Fetching server list... {}", glyph::RELOAD));
+            .with_label(&loading_text(None, None));
         loading_label.set_frame(fltk::enums::FrameType::EngravedBox);
         let status_overlay = status_overlay.end();
         grid.cell().unwrap().add(status_overlay);
@@ -157,7 +199,12 @@ impl ServerBrowserTab {
             .with_vert_align(CellAlign::Stretch)
             .add(SimpleWrapper::new(upper_tile.clone(), Default::default()));
 
-        let list_pane = ListPane::new(&state.borrow().order().criteria, browser_cfg.scroll_lock);
+        let list_pane = ListPane::new(
+            &state.borrow().order().criteria,
+            browser_cfg.scroll_lock,
+            browser_cfg.show_health,
+            last_visits,
+        );
 
         upper_tile.end();
 
@@ -169,7 +216,11 @@ impl ServerBrowserTab {
             .with_vert_align(CellAlign::Stretch)
             .add(SimpleWrapper::new(lower_tile.clone(), Default::default()));
 
-        let details_pane = DetailsPane::new(mod_manager);
+        let details_pane = DetailsPane::new(
+            Rc::clone(&mod_manager),
+            Rc::clone(&server_mgr),
+            Arc::clone(&game),
+        );
 
         lower_tile.end();
 
@@ -184,6 +235,7 @@ impl ServerBrowserTab {
 
         grid.row().add();
         let actions_pane = ActionsPane::new(browser_cfg.scroll_lock, server_mgr.can_save_servers());
+        actions_pane.type_filter_changed(browser_cfg.filter.type_filter);
         grid.cell().unwrap().add(actions_pane.element());
 
         let grid = grid.end();
@@ -200,8 +252,10 @@ impl ServerBrowserTab {
             config,
             launcher,
             server_mgr,
+            mod_manager,
             grid,
             root: root.clone(),
+            filter_pane: Rc::clone(&filter_pane),
             list_pane: Rc::clone(&list_pane),
             details_pane,
             actions_pane: Rc::clone(&actions_pane),
@@ -211,15 +265,30 @@ impl ServerBrowserTab {
             deferred_action: Cell::new(Some(DeferredAction::Refresh)),
             filter_dirty: Cell::new(false),
             refreshing: Cell::new(true),
+            refresh_start: Cell::new(None),
+            settings_warnings_shown: RefCell::new(HashSet::new()),
         });
 
         root.handle(weak_cb!([this] => |_, event| {
-            if let Event::Show = event {
-                this.on_show();
+            match event {
+                Event::Show => this.on_show(),
+                Event::Shortcut if is_search_shortcut() => {
+                    this.on_search_notes();
+                    return true;
+                }
+                Event::Shortcut if is_refresh_shortcut() => {
+                    this.on_refresh();
+                    return true;
+                }
+                _ => (),
             }
         }; false));
 
         filter_pane.set_filter_holder(Rc::clone(&this));
+        this.stats
+            .clear_filters_button
+            .clone()
+            .set_callback(weak_cb!([this] => |_| this.on_clear_filters()));
         list_pane.set_on_sort_changed(weak_cb!(
             [this] => |sort_criteria| {
                 let selected_idx = this.selected_server_index();
@@ -235,18 +304,25 @@ impl ServerBrowserTab {
             [this] => |server| {
                 this.details_pane.populate(server);
                 this.actions_pane.server_selected(server);
+                this.server_mgr.server_selected(server);
             }
         ));
+        list_pane.set_on_block_server(weak_cb!([this] => || this.on_block_server()));
+        list_pane.set_on_show_health_changed(weak_cb!([this] => || this.update_config()));
         actions_pane.set_on_action(weak_cb!(
             [this] => |action| {
                 match action {
                     Action::Join => this.on_join(),
                     Action::DirectConnect => this.on_direct_connect(),
                     Action::Ping => this.on_ping(),
+                    Action::TraceRoute => this.on_trace_route(),
+                    Action::CompareMods => this.on_compare_mods(),
                     Action::Refresh => this.on_refresh(),
                     Action::ToggleFavorite => this.on_toggle_favorite(),
                     Action::ToggleSaved => this.on_toggle_saved(),
                     Action::AddSaved => this.on_add_saved(),
+                    Action::ExportSaved => this.on_export_saved(),
+                    Action::ImportSaved => this.on_import_saved(),
                     Action::ScrollLock(scroll_lock) => {
                         this.list_pane.set_scroll_lock(scroll_lock);
                         this.update_config();
@@ -260,6 +336,11 @@ impl ServerBrowserTab {
             bus.subscribe_consumer(weak_cb!(
                 [this] => |PopulateServers { payload, done }| this.populate_servers(payload, done)
             ));
+            bus.subscribe_consumer(weak_cb!(
+                [this] => |AppendServers { servers, loaded_buckets, total_buckets }| {
+                    this.append_servers(servers, loaded_buckets, total_buckets)
+                }
+            ));
             bus.subscribe_consumer(weak_cb!(
                 [this] => |pongs: ProcessPongs| this.update_pinged_servers(pongs)
             ));
@@ -279,6 +360,7 @@ impl ServerBrowserTab {
     }
 
     fn on_show(&self) {
+        self.sync_blocked_servers();
         match self.deferred_action.take() {
             None => (),
             Some(DeferredAction::Refresh) => {
@@ -293,11 +375,20 @@ impl ServerBrowserTab {
 
     fn on_join(&self) {
         if let Some(server_idx) = self.list_pane.selected_index() {
+            if self.config.get().auto_activate_server_mods {
+                let mod_ids = self.state.borrow()[server_idx].steam_mod_ids();
+                if let Err(err) = self.mod_manager.activate_server_mods(&mod_ids) {
+                    error!(self.logger, "Error activating server mods"; "error" => %err);
+                    alert_error(ERR_ACTIVATING_SERVER_MODS, &err);
+                    return;
+                }
+            }
             let conn_info = {
                 let state = self.state.borrow();
                 let server = &state[server_idx];
                 if server.password_protected {
-                    let password = match self.game.load_server_password(&server.name) {
+                    let secure = self.config.get().secure_passwords == SecurePasswords::Enabled;
+                    let password = match self.game.load_server_password(&server.name, secure) {
                         Ok(password) => password.unwrap_or_default(),
                         Err(err) => {
                             warn!(
@@ -328,6 +419,7 @@ impl ServerBrowserTab {
                         if let Err(err) = self.game.save_server_password(
                             &server.name,
                             dlg_result.connection.password.as_ref().unwrap(),
+                            secure,
                         ) {
                             warn!(
                                 self.logger,
@@ -353,11 +445,70 @@ impl ServerBrowserTab {
         }
     }
 
+    fn confirm_settings_mismatch(&self, addr: SocketAddr) -> bool {
+        // The server list only shows the servers that match the filter, so this is only needed
+        // when the server is joined some other way, e.g. by its address
+        let mismatches = {
+            let config = self.config.get();
+            if config
+                .server_browser
+                .dismissed_settings_warnings
+                .contains(&addr)
+            {
+                return true;
+            }
+            let state = self.state.borrow();
+            let server = state
+                .source()
+                .iter()
+                .find(|server| !server.tombstone && server.game_addr() == Some(addr));
+            match server {
+                Some(server) => config.server_browser.filter.mismatched_settings(server),
+                None => return true,
+            }
+        };
+        if mismatches.is_empty() {
+            return true;
+        }
+
+        let warning = (addr, mismatches);
+        if self.settings_warnings_shown.borrow().contains(&warning) {
+            return true;
+        }
+
+        let prompt = format!("{}\n\n{}", PROMPT_SETTINGS_MISMATCH, warning.1.join("\n"));
+        match fltk::dialog::choice2_default(&prompt, "Cancel", "Join", "Always Join") {
+            Some(1) => {
+                self.settings_warnings_shown.borrow_mut().insert(warning);
+                true
+            }
+            Some(2) => {
+                self.config.update(|config| {
+                    config
+                        .server_browser
+                        .dismissed_settings_warnings
+                        .insert(addr);
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn on_clear_filters(&self) {
+        self.mutate_filter(Filter::clear);
+        self.filter_pane.populate(self.state.borrow().filter());
+        self.persist_filter();
+    }
+
     fn on_direct_connect(&self) {
         let dialog = ConnectDialog::direct_connect(&self.root);
         let Some(dlg_result) = dialog.run() else {
             return;
         };
+        if !self.confirm_settings_mismatch(dlg_result.connection.addr) {
+            return;
+        }
         if let Err(err) = self.launcher.join_server(dlg_result.connection) {
             error!(self.logger, "Error on direct connect"; "error" => %err);
             alert_error(ERR_JOINING_SERVER, &err);
@@ -385,19 +536,106 @@ impl ServerBrowserTab {
         }
     }
 
+    fn on_trace_route(&self) {
+        let Some(server_idx) = self.list_pane.selected_index() else {
+            return;
+        };
+        let IpAddr::V4(ip) = self.state.borrow()[server_idx].ip else {
+            return;
+        };
+        TraceRouteDialog::new(&self.logger, &self.root, ip).run();
+    }
+
+    fn on_compare_mods(&self) {
+        let Some(server_idx) = self.list_pane.selected_index() else {
+            return;
+        };
+        let (server_name, mod_ids) = {
+            let state = self.state.borrow();
+            let server = &state[server_idx];
+            (server.name.clone(), server.steam_mod_ids())
+        };
+        let comparison = match self.mod_manager.compare_server_mods(&mod_ids) {
+            Ok(comparison) => comparison,
+            Err(err) => {
+                error!(self.logger, "Error comparing server mods"; "error" => %err);
+                alert_error(ERR_COMPARING_SERVER_MODS, &err);
+                return;
+            }
+        };
+
+        let on_download = {
+            let logger = self.logger.clone();
+            let mod_manager = Rc::clone(&self.mod_manager);
+            move |mod_id| match mod_manager.subscribe_mod(mod_id) {
+                Ok(()) => true,
+                Err(err) => {
+                    error!(logger, "Error subscribing to mod"; "mod_id" => mod_id, "error" => %err);
+                    alert_error(ERR_SUBSCRIBING_TO_MOD, &err);
+                    false
+                }
+            }
+        };
+        let dialog = CompareModsDialog::new(&self.root, &server_name, comparison, on_download);
+        let deactivated = dialog.run();
+        if deactivated.is_empty() {
+            return;
+        }
+        if let Err(err) = self.mod_manager.deactivate_mods(&deactivated) {
+            error!(self.logger, "Error deactivating mods"; "error" => %err);
+            alert_error(ERR_DEACTIVATING_MODS, &err);
+        }
+    }
+
     fn on_refresh(&self) {
         self.refreshing.set(true);
+        self.refresh_start.set(Some(Instant::now()));
         {
+            let blocked_servers = Arc::clone(&self.config.get().server_browser.blocked_servers.0);
             let mut state = self.state.borrow_mut();
             state.update_source(Vec::clear);
+            state.update_filter(|filter| filter.set_blocked_servers(blocked_servers));
         }
         self.list_pane.mark_refreshing();
         self.list_pane.set_selected_index(None, false);
+        self.loading_label
+            .clone()
+            .set_label(&loading_text(None, None));
         self.loading_label.clone().show();
         self.stats.hide();
         self.server_mgr.load_server_list();
     }
 
+    fn on_block_server(&self) {
+        let Some(server_idx) = self.list_pane.selected_index() else {
+            return;
+        };
+        let (name, ip) = {
+            let state = self.state.borrow();
+            let server = &state[server_idx];
+            (server.name.clone(), server.ip)
+        };
+        if !prompt_confirm(&format!("{}\n{} ({})", PROMPT_BLOCK_SERVER, name, ip)) {
+            return;
+        }
+
+        self.config.update(|config| {
+            config.server_browser.blocked_servers.insert(ip);
+        });
+        self.sync_blocked_servers();
+    }
+
+    fn sync_blocked_servers(&self) {
+        let blocked_servers = Arc::clone(&self.config.get().server_browser.blocked_servers.0);
+        if Arc::ptr_eq(
+            self.state.borrow().filter().blocked_servers(),
+            &blocked_servers,
+        ) {
+            return;
+        }
+        self.mutate_filter(|filter| filter.set_blocked_servers(blocked_servers));
+    }
+
     fn on_toggle_favorite(&self) {
         if let Some(server_idx) = self.list_pane.selected_index() {
             // TODO: Only update if action was performed without error
@@ -453,15 +691,112 @@ impl ServerBrowserTab {
 
     fn on_add_saved(&self) {
         let dialog = AddServerDialog::new(&self.root, Arc::clone(&self.game));
-        let Some(server) = dialog.run() else {
+        let Some(AddServerDialogResult { server, password }) = dialog.run() else {
             return;
         };
+        if let Some(password) = password {
+            let secure = self.config.get().secure_passwords == SecurePasswords::Enabled;
+            if let Err(err) = self
+                .game
+                .save_server_password(&server.name, &password, secure)
+            {
+                warn!(
+                    self.logger,
+                    "Error saving password for server";
+                    "server" => &server.name,
+                    "error" => %err,
+                );
+            }
+        }
         if let Err(err) = self.server_mgr.save_server(server, None) {
             error!(self.logger, "Error on adding saved server"; "error" => %err);
             alert_error(ERR_UPDATING_SAVED_SERVERS, &err);
         }
     }
 
+    fn on_export_saved(&self) {
+        let mut dialog = NativeFileChooser::new(FileDialogType::BrowseSaveFile);
+        dialog.set_filter(DLG_FILTER_SAVED_SERVERS);
+        dialog.set_option(FileDialogOptions::SaveAsConfirm);
+        dialog.show();
+
+        let mut path = dialog.filename();
+        if path.as_os_str().is_empty() {
+            return;
+        }
+        if path.extension().is_none() {
+            path.set_extension("json");
+        }
+
+        if let Err(err) = self.server_mgr.export_saved_servers(&path) {
+            error!(self.logger, "Error exporting saved servers"; "error" => %err);
+            alert_error(ERR_EXPORTING_SAVED_SERVERS, &err);
+        }
+    }
+
+    fn on_import_saved(&self) {
+        let mut dialog = NativeFileChooser::new(FileDialogType::BrowseFile);
+        dialog.set_filter(DLG_FILTER_SAVED_SERVERS);
+        dialog.show();
+
+        let path = dialog.filename();
+        if path.as_os_str().is_empty() {
+            return;
+        }
+
+        match self.server_mgr.import_saved_servers(&path) {
+            Ok(count) => {
+                info!(self.logger, "Imported saved servers"; "count" => count);
+            }
+            Err(err) => {
+                error!(self.logger, "Error importing saved servers"; "error" => %err);
+                alert_error(ERR_IMPORTING_SAVED_SERVERS, &err);
+            }
+        }
+    }
+
+    fn on_search_notes(&self) {
+        let Some(query) = fltk::dialog::input_default(PROMPT_SEARCH_NOTES, "") else {
+            return;
+        };
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return;
+        }
+
+        let matches: Vec<NotesSearchMatch> = {
+            let state = self.state.borrow();
+            (0..state.len())
+                .filter_map(|index| {
+                    let server = &state[index];
+                    let notes_match = server
+                        .notes
+                        .as_ref()
+                        .map(|notes| notes.to_lowercase().contains(&query))
+                        .unwrap_or_default();
+                    if notes_match || server.name.to_lowercase().contains(&query) {
+                        Some(NotesSearchMatch {
+                            index,
+                            name: server.name.clone(),
+                            notes: server.notes.clone(),
+                        })
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        };
+        if matches.is_empty() {
+            fltk::dialog::message_default(MSG_NO_SEARCH_MATCHES);
+            return;
+        }
+
+        let dialog = NotesSearchDialog::new(&self.root, matches);
+        if let Some(index) = dialog.run() {
+            self.list_pane.set_selected_index(Some(index), true);
+        }
+    }
+
     fn populate_servers(&self, payload: Result<Vec<Server>>, done: bool) {
         self.deferred_action.set(None);
 
@@ -469,6 +804,9 @@ impl ServerBrowserTab {
             self.refreshing.set(false);
             self.loading_label.clone().hide();
             self.stats.show();
+            if let Some(start) = self.refresh_start.take() {
+                self.stats.show_load_time(start.elapsed());
+            }
         }
 
         let all_servers = match payload {
@@ -485,6 +823,11 @@ impl ServerBrowserTab {
             }
         };
         self.stats.set_total_servers(all_servers.len());
+        if !done {
+            self.loading_label
+                .clone()
+                .set_label(&loading_text(Some(all_servers.len()), None));
+        }
 
         {
             let mut state = self.state.borrow_mut();
@@ -507,6 +850,26 @@ impl ServerBrowserTab {
         }
     }
 
+    fn append_servers(&self, servers: Vec<Server>, loaded_buckets: usize, total_buckets: usize) {
+        {
+            let mut state = self.state.borrow_mut();
+            state.update(|all_servers, _, _| {
+                all_servers.extend(servers);
+                Reindex::all()
+            });
+        }
+        let num_servers = self.state.borrow().source().len();
+        self.stats.set_total_servers(num_servers);
+        self.stats.set_matching_servers(self.state.borrow().len());
+        self.loading_label.clone().set_label(&loading_text(
+            Some(num_servers),
+            Some((loaded_buckets, total_buckets)),
+        ));
+
+        let state = Rc::clone(&self.state);
+        self.list_pane.populate(state);
+    }
+
     fn ping_servers(&self) {
         let ping_requests = {
             let state = self.state.borrow();
@@ -521,7 +884,9 @@ impl ServerBrowserTab {
                     .source()
                     .iter()
                     .enumerate()
-                    .filter(|(idx, _)| state.from_source_index(*idx).is_none())
+                    .filter(|(idx, server)| {
+                        !server.tombstone && state.from_source_index(*idx).is_none()
+                    })
                     .filter_map(|(idx, server)| PingRequest::for_server(idx, server)),
             );
 
@@ -549,6 +914,7 @@ impl ServerBrowserTab {
 
         let mut total_players = self.stats.total_players();
         let mut matching_players = self.stats.matching_players();
+        let mut stats_updated = false;
         self.update_servers(
             updates.len(),
             |all_servers, updated_indices, filter, sort_criteria| {
@@ -558,25 +924,42 @@ impl ServerBrowserTab {
                         continue;
                     };
                     updated_indices.push(update.server_idx);
-                    if Self::update_pinged_server(
+                    let reindex_needed = Self::update_pinged_server(
                         server,
                         update,
+                        &self.server_mgr,
                         filter,
                         &mut total_players,
                         &mut matching_players,
-                    ) {
+                    );
+                    if let (true, Some(addr)) = (server.is_saved(), server.game_addr()) {
+                        self.server_mgr
+                            .record_server_status(addr, server.ping.is_some());
+                        stats_updated = true;
+                    }
+                    if let (Some(addr), Some(players)) =
+                        (server.game_addr(), server.connected_players)
+                    {
+                        self.server_mgr.record_player_count(addr, players);
+                    }
+                    if reindex_needed {
                         reindex = Reindex::Filter;
                     }
                 }
                 reindex.order_if(
                     (sort_criteria.key == SortKey::Players)
                         || (sort_criteria.key == SortKey::Age)
-                        || (sort_criteria.key == SortKey::Ping),
+                        || (sort_criteria.key == SortKey::Ping)
+                        || (sort_criteria.key == SortKey::Health),
                 )
             },
         );
+        if stats_updated {
+            self.server_mgr.save_server_stats();
+        }
         self.stats.set_total_players(total_players);
         self.stats.set_matching_players(matching_players);
+        self.stats.set_matching_servers(self.state.borrow().len());
     }
 
     fn update_server(&self, idx: Option<usize>, server: Server) {
@@ -645,6 +1028,7 @@ impl ServerBrowserTab {
     fn update_pinged_server(
         server: &mut Server,
         update: &PingResponse,
+        server_mgr: &ServerManager,
         filter: &Filter,
         total_players: &mut usize,
         matching_players: &mut usize,
@@ -664,11 +1048,25 @@ impl ServerBrowserTab {
                 server.connected_players = Some(connected_players);
                 server.age = Some(age);
                 server.ping = Some(round_trip);
+                server.record_ping(server_mgr.record_ping(update.addr, round_trip));
+                server.under_maintenance = false;
             }
-            PingResult::Timeout => {
+            PingResult::Timeout | PingResult::Unreachable => {
                 server.connected_players = None;
                 server.age = None;
                 server.ping = None;
+                server.record_timeout();
+                server.under_maintenance = server.is_official()
+                    && server
+                        .game_addr()
+                        .map(|addr| server_mgr.is_offline(addr))
+                        .unwrap_or_default();
+                // Drop servers that keep timing out from the list, unless the user wants them
+                if let PingResult::Unreachable = update.result {
+                    if !server.is_saved() && !server.favorite {
+                        server.tombstone = true;
+                    }
+                }
             }
         };
         server.waiting_for_pong = false;
@@ -697,15 +1095,17 @@ impl ServerBrowserTab {
 
     fn update_config(&self) {
         let state = self.state.borrow();
-        let filter = state.filter();
-        let order = state.order();
-        let browser_cfg = ServerBrowserConfig {
-            filter: filter.as_ref().clone(),
-            sort_criteria: order.criteria,
-            scroll_lock: self.list_pane.scroll_lock(),
-        };
-        self.config
-            .update(|config| config.server_browser = browser_cfg);
+        let filter = state.filter().as_ref().clone();
+        let sort_criteria = state.order().criteria;
+        let scroll_lock = self.list_pane.scroll_lock();
+        let show_health = self.list_pane.show_health();
+        self.config.update(|config| {
+            let browser_cfg = &mut config.server_browser;
+            browser_cfg.filter = filter;
+            browser_cfg.sort_criteria = sort_criteria;
+            browser_cfg.scroll_lock = scroll_lock;
+            browser_cfg.show_health = show_health;
+        });
     }
 }
 
@@ -732,6 +1132,8 @@ impl FilterHolder for ServerBrowserTab {
         self.filter_dirty.set(true);
 
         let state = self.state.borrow();
+        self.actions_pane
+            .type_filter_changed(state.filter().type_filter);
         let matching_players = state
             .iter()
             .map(|server| server.connected_players.unwrap_or_default())
@@ -759,6 +1161,23 @@ impl BrowserStats {
         let total_players_text = browser_stat(&mut grid, "Total Players Online:");
         let matching_servers_text = browser_stat(&mut grid, "Matching Servers:");
         let matching_players_text = browser_stat(&mut grid, "Players on Matching Servers:");
+        grid.col().add();
+        let mut clear_filters_button = grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Clear Filters")
+            .with_tooltip("No servers match the filter. Reset all filters to defaults.");
+        clear_filters_button.hide();
+        grid.col()
+            .with_default_align(CellAlign::Stretch)
+            .with_stretch(1)
+            .add();
+        let load_time_text = grid
+            .cell()
+            .unwrap()
+            .wrap(Frame::default())
+            .with_align(Align::Right | Align::Inside);
 
         let grid = grid.end();
         let mut group = grid.group();
@@ -771,6 +1190,9 @@ impl BrowserStats {
             total_players_text,
             matching_servers_text,
             matching_players_text,
+            clear_filters_button,
+            load_time_text,
+            load_time_generation: Rc::new(Cell::new(0)),
             total_servers: Cell::new(0),
             total_players: Cell::new(0),
             matching_servers: Cell::new(0),
@@ -791,9 +1213,29 @@ impl BrowserStats {
         self.total_players_text.clone().set_label("?");
         self.matching_servers_text.clone().set_label("?");
         self.matching_players_text.clone().set_label("?");
+        self.clear_filters_button.clone().hide();
         group.redraw();
     }
 
+    fn show_load_time(&self, elapsed: Duration) {
+        let mut load_time_text = self.load_time_text.clone();
+        load_time_text.set_label(&format!("Loaded in {:.1}s", elapsed.as_secs_f64()));
+        load_time_text.redraw();
+
+        let generation = self.load_time_generation.get().wrapping_add(1);
+        self.load_time_generation.set(generation);
+        let current_generation = Rc::clone(&self.load_time_generation);
+        let refreshed = Instant::now();
+        app::add_timeout3(LOAD_TIME_DISPLAY_SECS, move |handle| {
+            if current_generation.get() != generation {
+                return;
+            }
+            load_time_text.set_label(&refreshed_text(refreshed.elapsed()));
+            load_time_text.redraw();
+            app::repeat_timeout3(REFRESHED_UPDATE_SECS, handle);
+        });
+    }
+
     fn total_players(&self) -> usize {
         self.total_players.get()
     }
@@ -807,6 +1249,7 @@ impl BrowserStats {
         let mut total_servers_text = self.total_servers_text.clone();
         total_servers_text.set_label(&count.to_string());
         total_servers_text.redraw();
+        self.update_clear_filters_button();
     }
 
     fn set_total_players(&self, count: usize) {
@@ -821,6 +1264,16 @@ impl BrowserStats {
         let mut matching_servers_text = self.matching_servers_text.clone();
         matching_servers_text.set_label(&count.to_string());
         matching_servers_text.redraw();
+        self.update_clear_filters_button();
+    }
+
+    fn update_clear_filters_button(&self) {
+        let mut clear_filters_button = self.clear_filters_button.clone();
+        if (self.matching_servers.get() == 0) && (self.total_servers.get() > 0) {
+            clear_filters_button.show();
+        } else {
+            clear_filters_button.hide();
+        }
     }
 
     fn set_matching_players(&self, count: usize) {
@@ -834,8 +1287,38 @@ impl BrowserStats {
 const ERR_LOADING_SERVERS: &str = "Error while loading the server list.";
 const ERR_PINGING_SERVERS: &str = "Error while pinging servers.";
 const ERR_JOINING_SERVER: &str = "Error while trying to launch the game to join the server.";
+const ERR_ACTIVATING_SERVER_MODS: &str = "Error while activating the server mods.";
+const ERR_COMPARING_SERVER_MODS: &str = "Error while comparing your mods with the server mods.";
+const ERR_SUBSCRIBING_TO_MOD: &str = "Error while subscribing to the mod.";
+const ERR_DEACTIVATING_MODS: &str = "Error while deactivating the mods.";
 const ERR_UPDATING_FAVORITES: &str = "Error while updating favorites.";
 const ERR_UPDATING_SAVED_SERVERS: &str = "Error while updating saved servers.";
+const ERR_EXPORTING_SAVED_SERVERS: &str = "Error while exporting saved servers.";
+const ERR_IMPORTING_SAVED_SERVERS: &str = "Error while importing saved servers.";
+
+const PROMPT_SEARCH_NOTES: &str = "Search server names and notes for:";
+const MSG_NO_SEARCH_MATCHES: &str = "No servers in the list match the search.";
+
+const PROMPT_SETTINGS_MISMATCH: &str = "This server's settings differ from your filter:";
+const PROMPT_BLOCK_SERVER: &str =
+    "Hide all servers at this IP address from the server browser? You can unblock them from \
+    the Blocked Servers dialog on the home tab.";
+
+const DLG_FILTER_SAVED_SERVERS: &str = "Saved Servers\t*.json";
+
+const LOAD_TIME_DISPLAY_SECS: f64 = 5.0;
+const REFRESHED_UPDATE_SECS: f64 = 30.0;
+
+fn is_search_shortcut() -> bool {
+    fltk::app::event_state().contains(Shortcut::Ctrl)
+        && fltk::app::event_key() == Key::from_char('f')
+}
+
+fn is_refresh_shortcut() -> bool {
+    let key = fltk::app::event_key();
+    key == Key::F5
+        || (fltk::app::event_state().contains(Shortcut::Ctrl) && key == Key::from_char('r'))
+}
 
 fn browser_stat(grid: &mut GridBuilder<Group, Rc<WrapperFactory>>, label: &str) -> Frame {
     grid.col()
@@ -857,6 +1340,28 @@ fn browser_stat(grid: &mut GridBuilder<Group, Rc<WrapperFactory>>, label: &str)
         .with_align(Align::Left | Align::Inside)
 }
 
+fn refreshed_text(elapsed: Duration) -> String {
+    match elapsed.as_secs() / 60 {
+        0 => "Refreshed: just now".to_string(),
+        mins @ 1..=59 => format!("Refreshed: {} min ago", mins),
+        mins => format!("Refreshed: {} h {} min ago", mins / 60, mins % 60),
+    }
+}
+
+fn loading_text(loaded: Option<usize>, buckets: Option<(usize, usize)>) -> String {
+    match (loaded, buckets) {
+        (Some(loaded), Some((loaded_buckets, total_buckets))) => format!(
+            "Fetching server list... {} ({} / {} buckets) {}",
+            loaded,
+            loaded_buckets,
+            total_buckets,
+            glyph::RELOAD
+        ),
+        (Some(loaded), None) => format!("Fetching server list... {} {}", loaded, glyph::RELOAD),
+        (None, _) => format!("Fetching server list... {}", glyph::RELOAD),
+    }
+}
+
 fn mode_name(mode: Mode) -> &'static str {
     match mode {
         Mode::PVE => "PVE",
@@ -8,15 +8,19 @@ use fltk_float::LayoutElement;
 
 use crate::gui::prelude::*;
 use crate::gui::wrapper_factory;
-use crate::servers::Server;
+use crate::servers::{Server, TypeFilter};
 
 pub enum Action {
     DirectConnect,
     Refresh,
     AddSaved,
+    ExportSaved,
+    ImportSaved,
     ToggleSaved,
     ToggleFavorite,
     Ping,
+    TraceRoute,
+    CompareMods,
     Join,
     ScrollLock(bool),
 }
@@ -26,9 +30,13 @@ pub(super) struct ActionsPane {
     direct_conn_button: Button,
     refresh_button: Button,
     add_server_button: Button,
+    export_servers_button: Option<Button>,
+    import_servers_button: Option<Button>,
     toggle_saved_button: Option<Button>,
     toggle_favorite_button: Button,
     ping_button: Button,
+    trace_route_button: Button,
+    compare_mods_button: Button,
     join_button: Button,
     scroll_lock_check: CheckButton,
 }
@@ -54,7 +62,7 @@ impl ActionsPane {
             .unwrap()
             .wrap(Button::default())
             .with_label("Refresh")
-            .with_tooltip("Reload the server list");
+            .with_tooltip("Reload the server list (F5 or Ctrl+R)");
 
         grid.col().add();
         let add_server_button = grid
@@ -64,6 +72,30 @@ impl ActionsPane {
             .with_label("Add...")
             .with_tooltip("Manually add a server to your saved servers");
 
+        let (export_servers_button, import_servers_button) = if can_save_servers {
+            grid.col().add();
+            let mut export_servers_button = grid
+                .cell()
+                .unwrap()
+                .wrap(Button::default())
+                .with_label("Export...")
+                .with_tooltip("Export your saved servers to a file");
+            export_servers_button.deactivate();
+
+            grid.col().add();
+            let mut import_servers_button = grid
+                .cell()
+                .unwrap()
+                .wrap(Button::default())
+                .with_label("Import...")
+                .with_tooltip("Add the servers from an exported file to your saved servers");
+            import_servers_button.deactivate();
+
+            (Some(export_servers_button), Some(import_servers_button))
+        } else {
+            (None, None)
+        };
+
         grid.col().with_stretch(1).add();
         let scroll_lock_check = grid
             .cell()
@@ -104,6 +136,24 @@ impl ActionsPane {
             );
         ping_button.deactivate();
 
+        grid.col().add();
+        let mut trace_route_button = grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Trace...")
+            .with_tooltip("Measure the latency at each network hop to the selected server");
+        trace_route_button.deactivate();
+
+        grid.col().add();
+        let mut compare_mods_button = grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Compare Mods...")
+            .with_tooltip("Compare your active mods with the mods used by the selected server");
+        compare_mods_button.deactivate();
+
         grid.col().add();
         let mut join_button = grid
             .cell()
@@ -130,15 +180,18 @@ impl ActionsPane {
         }
 
         let toggle_saved_button = if can_save_servers { Some(toggle_saved_button) } else { None };
-
         Rc::new(Self {
             grid,
             direct_conn_button,
             refresh_button,
             add_server_button,
+            export_servers_button,
+            import_servers_button,
             toggle_saved_button,
             toggle_favorite_button,
             ping_button,
+            trace_route_button,
+            compare_mods_button,
             join_button,
             scroll_lock_check,
         })
@@ -154,6 +207,8 @@ impl ActionsPane {
         let toggle_saved_button = self.toggle_saved_button.clone();
         let mut toggle_favorite_button = self.toggle_favorite_button.clone();
         let mut ping_button = self.ping_button.clone();
+        let mut trace_route_button = self.trace_route_button.clone();
+        let mut compare_mods_button = self.compare_mods_button.clone();
         let mut join_button = self.join_button.clone();
 
         if let Some(server) = server {
@@ -170,6 +225,8 @@ impl ActionsPane {
             });
 
             ping_button.set_activated(server.is_valid());
+            trace_route_button.set_activated(server.is_valid() && server.ip.is_ipv4());
+            compare_mods_button.set_activated(server.is_modded());
             join_button.set_activated(server.is_valid());
         } else {
             if let Some(mut button) = toggle_saved_button {
@@ -179,10 +236,21 @@ impl ActionsPane {
             toggle_favorite_button.set_label("Favorite");
             toggle_favorite_button.deactivate();
             ping_button.deactivate();
+            trace_route_button.deactivate();
+            compare_mods_button.deactivate();
             join_button.deactivate();
         }
     }
 
+    pub fn type_filter_changed(&self, type_filter: TypeFilter) {
+        let saved_only = type_filter == TypeFilter::Saved;
+        for button in [&self.export_servers_button, &self.import_servers_button] {
+            if let Some(button) = button.as_ref() {
+                button.clone().set_activated(saved_only);
+            }
+        }
+    }
+
     pub fn set_on_action(&self, on_action: impl Fn(Action) + 'static) {
         let on_action = Rc::new(on_action);
         {
@@ -200,6 +268,16 @@ impl ActionsPane {
             let on_action = Rc::clone(&on_action);
             add_server_button.set_callback(move |_| on_action(Action::AddSaved));
         }
+        if let Some(button) = self.export_servers_button.as_ref() {
+            let mut export_servers_button = button.clone();
+            let on_action = Rc::clone(&on_action);
+            export_servers_button.set_callback(move |_| on_action(Action::ExportSaved));
+        }
+        if let Some(button) = self.import_servers_button.as_ref() {
+            let mut import_servers_button = button.clone();
+            let on_action = Rc::clone(&on_action);
+            import_servers_button.set_callback(move |_| on_action(Action::ImportSaved));
+        }
         if let Some(button) = self.toggle_saved_button.as_ref() {
             let mut toggle_saved_button = button.clone();
             let on_action = Rc::clone(&on_action);
@@ -215,6 +293,16 @@ impl ActionsPane {
             let on_action = Rc::clone(&on_action);
             ping_button.set_callback(move |_| on_action(Action::Ping));
         }
+        {
+            let mut trace_route_button = self.trace_route_button.clone();
+            let on_action = Rc::clone(&on_action);
+            trace_route_button.set_callback(move |_| on_action(Action::TraceRoute));
+        }
+        {
+            let mut compare_mods_button = self.compare_mods_button.clone();
+            let on_action = Rc::clone(&on_action);
+            compare_mods_button.set_callback(move |_| on_action(Action::CompareMods));
+        }
         {
             let mut join_button = self.join_button.clone();
             let on_action = Rc::clone(&on_action);
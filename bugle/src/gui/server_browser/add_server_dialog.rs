@@ -8,7 +8,7 @@ use anyhow::{anyhow, bail, Result};
 use fltk::button::{Button, CheckButton, ReturnButton, ToggleButton};
 use fltk::frame::Frame;
 use fltk::group::Group;
-use fltk::input::Input;
+use fltk::input::{Input, SecretInput};
 use fltk::misc::InputChoice;
 use fltk::prelude::*;
 use fltk::window::Window;
@@ -35,8 +35,15 @@ pub struct AddServerDialog {
     mode_input: DropDownList,
     region_input: DropDownList,
     pwd_prot_check: CheckButton,
+    password_input: SecretInput,
+    notes_input: Input,
     settings_tabs: SettingsTabs,
-    result: RefCell<Option<Server>>,
+    result: RefCell<Option<AddServerDialogResult>>,
+}
+
+pub struct AddServerDialogResult {
+    pub server: Server,
+    pub password: Option<String>,
 }
 
 impl AddServerDialog {
@@ -75,7 +82,7 @@ impl AddServerDialog {
         for map in game.maps().iter() {
             map_input.add(&map.display_name);
         }
-        let pwd_prot_check = root
+        let mut pwd_prot_check = root
             .cell()
             .unwrap()
             .wrap(CheckButton::default())
@@ -104,6 +111,19 @@ impl AddServerDialog {
             .wrap(CheckButton::default())
             .with_label("Requires BattlEye");
 
+        root.row().add();
+        root.cell()
+            .unwrap()
+            .wrap(Frame::default())
+            .with_label("Password:");
+        let mut password_input = root.cell().unwrap().wrap(SecretInput::default());
+        password_input.deactivate();
+        root.cell()
+            .unwrap()
+            .wrap(Frame::default())
+            .with_label("Notes:");
+        let notes_input = root.span(1, 2).unwrap().wrap(Input::default());
+
         root.row().with_stretch(1).add();
         let mut settings_grid = Grid::builder_with_factory(wrapper_factory());
         let settings_tabs = SettingsTabs::new(
@@ -180,6 +200,9 @@ impl AddServerDialog {
             }
         });
 
+        mode_input.set_value(DEFAULT_MODE as i32);
+        region_input.set_value(DEFAULT_REGION as i32);
+
         let this = Rc::new(Self {
             build_id: game.build_id(),
             window,
@@ -188,10 +211,13 @@ impl AddServerDialog {
             map_input,
             mode_input: mode_input.clone(),
             region_input,
-            pwd_prot_check,
+            pwd_prot_check: pwd_prot_check.clone(),
+            password_input,
+            notes_input,
             settings_tabs,
             result: RefCell::new(None),
         });
+        this.settings_tabs.general_tab.set_mode(DEFAULT_MODE);
 
         ok_button.set_callback(weak_cb!([this] => |_| this.ok_clicked()));
         cancel_button.set_callback(weak_cb!([this] => |_| this.cancel_clicked()));
@@ -201,6 +227,9 @@ impl AddServerDialog {
             };
             this.settings_tabs.general_tab.set_mode(mode);
         }));
+        pwd_prot_check.set_callback(weak_cb!([this] => |input| {
+            this.password_input.clone().set_activated(input.is_checked());
+        }));
         battleye_check.set_callback(weak_cb!([this] => |input| {
             this.settings_tabs.general_tab.set_battleye_required(input.is_checked());
         }));
@@ -221,7 +250,7 @@ impl AddServerDialog {
         this
     }
 
-    pub fn run(&self) -> Option<Server> {
+    pub fn run(&self) -> Option<AddServerDialogResult> {
         let mut window = self.window.clone();
         window.make_modal(true);
         window.show();
@@ -241,7 +270,10 @@ impl AddServerDialog {
                 return;
             }
         };
-        *self.result.borrow_mut() = Some(server);
+        let password = self.password_input.value();
+        let password =
+            if server.password_protected && !password.is_empty() { Some(password) } else { None };
+        *self.result.borrow_mut() = Some(AddServerDialogResult { server, password });
         self.window.clone().hide();
     }
 
@@ -264,14 +296,10 @@ impl AddServerDialog {
             .filter(|s| !s.is_empty())
             .ok_or_else(|| anyhow!("Map cannot be empty."))?;
 
-        if self.mode_input.value() < 0 {
-            bail!("Please select a mode.");
-        }
+        let region = Region::from_repr(self.region_input.value() as _).unwrap_or(DEFAULT_REGION);
 
-        if self.region_input.value() < 0 {
-            bail!("Please select a region.");
-        }
-        let region = Region::from_repr(self.region_input.value() as _).unwrap();
+        let notes = self.notes_input.value();
+        let notes = if notes.trim().is_empty() { None } else { Some(notes) };
 
         let server = Server::new(ServerData {
             id: "".to_string(),
@@ -286,6 +314,7 @@ impl AddServerDialog {
             port: host.port() as _,
             build_id: self.build_id,
             mods: None,
+            notes,
             general: self.settings_tabs.general_tab.public_values(),
             progression: self.settings_tabs.progression_tab.public_values(),
             daylight: self.settings_tabs.daylight_tab.public_values(),
@@ -301,6 +330,9 @@ impl AddServerDialog {
 
 const ERR_INVALID_SERVER_DATA: &str = "Invalid server data.";
 
+const DEFAULT_MODE: Mode = Mode::PVP;
+const DEFAULT_REGION: Region = Region::America;
+
 const LABEL_EXPAND_SETTINGS: &str = "Settings @2>>";
 const LABEL_COLLAPSE_SETTINGS: &str = "Settings @8>>";
 
@@ -55,6 +55,7 @@ pub struct AdvancedFilterDialog<F: FilterHolder + 'static> {
     rsrc_respawn_speed_mult_input: RangeFilterInput<Multiplier>,
     crafting_time_mult_input: RangeFilterInput<Multiplier>,
     thrall_crafting_time_mult_input: RangeFilterInput<Multiplier>,
+    mod_count_input: RangeFilterInput<u8>,
 }
 
 impl<F: FilterHolder + 'static> AdvancedFilterDialog<F> {
@@ -111,6 +112,7 @@ impl<F: FilterHolder + 'static> AdvancedFilterDialog<F> {
             RangeFilterInput::new(&mut window, "Crafting time multiplier");
         let thrall_crafting_time_mult_input =
             RangeFilterInput::new(&mut window, "Thrall crafting time multiplier");
+        let mod_count_input = RangeFilterInput::new(&mut window, "Number of mods");
 
         window.row().add();
         let mut actions = Grid::builder_with_factory(wrapper_factory())
@@ -118,8 +120,13 @@ impl<F: FilterHolder + 'static> AdvancedFilterDialog<F> {
             .with_top_padding(5);
         actions.row().add();
         let col_group = actions.col_group().add();
+        actions.extend_group(col_group).add();
         actions.col().with_stretch(1).add();
         actions.extend_group(col_group).batch(2);
+        let mut reset_button = actions
+            .cell()
+            .unwrap()
+            .wrap(Button::default().with_label("Reset All"));
         actions.cell().unwrap().skip();
         let mut apply_button = actions
             .cell()
@@ -165,6 +172,7 @@ impl<F: FilterHolder + 'static> AdvancedFilterDialog<F> {
             rsrc_respawn_speed_mult_input.set_value(&filter.rsrc_respawn_speed_mult);
             crafting_time_mult_input.set_value(&filter.crafting_time_mult);
             thrall_crafting_time_mult_input.set_value(&filter.thrall_crafting_time_mult);
+            mod_count_input.set_value(&filter.mod_count);
         });
 
         let this = Rc::new(Self {
@@ -192,8 +200,10 @@ impl<F: FilterHolder + 'static> AdvancedFilterDialog<F> {
             rsrc_respawn_speed_mult_input,
             crafting_time_mult_input,
             thrall_crafting_time_mult_input,
+            mod_count_input,
         });
 
+        reset_button.set_callback(weak_cb!([this] => |_| this.reset_clicked()));
         apply_button.set_callback(weak_cb!([this] => |_| this.apply_clicked()));
         cancel_button.set_callback(weak_cb!([this] => |_| this.cancel_clicked()));
 
@@ -210,6 +220,32 @@ impl<F: FilterHolder + 'static> AdvancedFilterDialog<F> {
         }
     }
 
+    fn reset_clicked(&self) {
+        self.community_input.set_value(&None);
+        self.max_clan_size_input.set_value(&None);
+        self.raid_enabled_input.set_value(&None);
+        self.raid_restricted_input.set_value(&None);
+        self.xp_rate_mult_input.set_value(&None);
+        self.day_cycle_speed_mult_input.set_value(&None);
+        self.dawn_dusk_speed_mult_input.set_value(&None);
+        self.use_catch_up_time_input.set_value(&None);
+        self.stamina_cost_mult_input.set_value(&None);
+        self.active_thirst_mult_input.set_value(&None);
+        self.active_hunger_mult_input.set_value(&None);
+        self.idle_thirst_mult_input.set_value(&None);
+        self.idle_hunger_mult_input.set_value(&None);
+        self.drop_items_on_death_input.set_value(&None);
+        self.anyone_can_loot_corpse_input.set_value(&None);
+        self.durability_mult_input.set_value(&None);
+        self.thrall_wakeup_time_input.set_value(&None);
+        self.item_spoil_rate_mult_input.set_value(&None);
+        self.harvest_amount_mult_input.set_value(&None);
+        self.rsrc_respawn_speed_mult_input.set_value(&None);
+        self.crafting_time_mult_input.set_value(&None);
+        self.thrall_crafting_time_mult_input.set_value(&None);
+        self.mod_count_input.set_value(&None);
+    }
+
     fn apply_clicked(&self) {
         if self.apply_changes().is_ok() {
             self.window.clone().hide();
@@ -243,6 +279,7 @@ impl<F: FilterHolder + 'static> AdvancedFilterDialog<F> {
         let rsrc_respawn_speed_mult = self.rsrc_respawn_speed_mult_input.value()?;
         let crafting_time_mult = self.crafting_time_mult_input.value()?;
         let thrall_crafting_time_mult = self.thrall_crafting_time_mult_input.value()?;
+        let mod_count = self.mod_count_input.value()?;
 
         self.filter_holder.mutate_filter(move |filter| {
             filter.community = community;
@@ -267,6 +304,7 @@ impl<F: FilterHolder + 'static> AdvancedFilterDialog<F> {
             filter.rsrc_respawn_speed_mult = rsrc_respawn_speed_mult;
             filter.crafting_time_mult = crafting_time_mult;
             filter.thrall_crafting_time_mult = thrall_crafting_time_mult;
+            filter.mod_count = mod_count;
         });
 
         Ok(())
@@ -0,0 +1,216 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use fltk::browser::{Browser, CheckBrowser, HoldBrowser};
+use fltk::button::{Button, ReturnButton};
+use fltk::frame::Frame;
+use fltk::group::Group;
+use fltk::prelude::*;
+use fltk::window::Window;
+use fltk_float::grid::{CellAlign, Grid};
+use fltk_float::SimpleWrapper;
+
+use crate::game::ModRef;
+use crate::gui::{glyph, wrapper_factory};
+use crate::mod_manager::ServerModComparison;
+
+pub struct CompareModsDialog {
+    window: Window,
+    state: Rc<CompareModsState>,
+}
+
+struct CompareModsState {
+    missing: RefCell<Vec<(u64, String)>>,
+    extra: Vec<ModRef>,
+    result: RefCell<Vec<ModRef>>,
+    missing_list: HoldBrowser,
+    extra_list: CheckBrowser,
+    download_button: Button,
+    on_download: Box<dyn Fn(u64) -> bool>,
+}
+
+impl CompareModsDialog {
+    pub fn new(
+        parent: &Group,
+        server_name: &str,
+        comparison: ServerModComparison,
+        on_download: impl Fn(u64) -> bool + 'static,
+    ) -> Self {
+        let mut window = Window::default()
+            .with_size(900, 480)
+            .with_label(&format!("Compare Mods: {}", server_name));
+
+        let mut root = Grid::builder_with_factory(wrapper_factory())
+            .with_col_spacing(10)
+            .with_row_spacing(10)
+            .with_padding(10, 10, 10, 10);
+        let col_group = root.col_group().with_stretch(1).add();
+        root.extend_group(col_group).batch(3);
+
+        root.row().add();
+        root.cell()
+            .unwrap()
+            .wrap(Frame::default())
+            .with_label(&format!("{} Missing (need to install)", glyph::DOWNLOAD));
+        root.cell()
+            .unwrap()
+            .wrap(Frame::default())
+            .with_label("Extra (not on server)");
+        root.cell()
+            .unwrap()
+            .wrap(Frame::default())
+            .with_label("Matching");
+
+        root.row()
+            .with_stretch(1)
+            .with_default_align(CellAlign::Stretch)
+            .add();
+        let mut missing_list = HoldBrowser::default();
+        for (_, name) in comparison.missing.iter() {
+            missing_list.add(name);
+        }
+        root.cell()
+            .unwrap()
+            .add(SimpleWrapper::new(missing_list.clone(), Default::default()));
+        let mut extra_list = CheckBrowser::default();
+        for (_, name) in comparison.extra.iter() {
+            extra_list.add(name, false);
+        }
+        root.cell()
+            .unwrap()
+            .add(SimpleWrapper::new(extra_list.clone(), Default::default()));
+        let mut matching_list = Browser::default();
+        for name in comparison.matching.iter() {
+            matching_list.add(name);
+        }
+        root.cell().unwrap().add(SimpleWrapper::new(
+            matching_list.clone(),
+            Default::default(),
+        ));
+
+        root.row().add();
+        let mut download_button = root
+            .cell()
+            .unwrap()
+            .with_horz_align(CellAlign::Start)
+            .wrap(Button::default())
+            .with_label(&format!("{} Download", glyph::DOWNLOAD))
+            .with_tooltip("Subscribe to the selected mod in the Steam Workshop");
+        let mut deactivate_button = root
+            .cell()
+            .unwrap()
+            .with_horz_align(CellAlign::Start)
+            .wrap(ReturnButton::default())
+            .with_label("Deactivate Checked")
+            .with_tooltip("Remove the checked mods from your active mod list");
+        let mut close_button = root
+            .cell()
+            .unwrap()
+            .with_horz_align(CellAlign::End)
+            .wrap(Button::default())
+            .with_label("Close");
+
+        let root = root.end();
+        root.group().resize(0, 0, window.w(), window.h());
+        root.layout_children();
+        window.end();
+
+        window.set_pos(
+            parent.x() + (parent.w() - window.w()) / 2,
+            parent.y() + (parent.h() - window.h()) / 2,
+        );
+
+        download_button.deactivate();
+        if comparison.extra.is_empty() {
+            deactivate_button.deactivate();
+        }
+
+        let state = Rc::new(CompareModsState {
+            missing: RefCell::new(comparison.missing),
+            extra: comparison
+                .extra
+                .into_iter()
+                .map(|(mod_ref, _)| mod_ref)
+                .collect(),
+            result: RefCell::new(Vec::new()),
+            missing_list: missing_list.clone(),
+            extra_list: extra_list.clone(),
+            download_button: download_button.clone(),
+            on_download: Box::new(on_download),
+        });
+
+        missing_list.set_callback({
+            let state = Rc::clone(&state);
+            move |_| state.selection_changed()
+        });
+        download_button.set_callback({
+            let state = Rc::clone(&state);
+            move |_| state.download_selected()
+        });
+        deactivate_button.set_callback({
+            let state = Rc::clone(&state);
+            let mut window = window.clone();
+            move |_| {
+                state.result.replace(state.checked_extra());
+                window.hide();
+            }
+        });
+        close_button.set_callback({
+            let mut window = window.clone();
+            move |_| window.hide()
+        });
+
+        Self { window, state }
+    }
+
+    pub fn run(&self) -> Vec<ModRef> {
+        let mut window = self.window.clone();
+        window.make_modal(true);
+        window.show();
+
+        while window.shown() && !fltk::app::should_program_quit() {
+            fltk::app::wait();
+        }
+
+        self.state.result.take()
+    }
+}
+
+impl CompareModsState {
+    fn selected_missing(&self) -> Option<usize> {
+        let line = self.missing_list.value();
+        if line > 0 {
+            Some((line - 1) as usize)
+        } else {
+            None
+        }
+    }
+
+    fn selection_changed(&self) {
+        self.download_button
+            .clone()
+            .set_activated(self.selected_missing().is_some());
+    }
+
+    fn download_selected(&self) {
+        let Some(idx) = self.selected_missing() else {
+            return;
+        };
+        let mod_id = self.missing.borrow()[idx].0;
+        if !(self.on_download)(mod_id) {
+            return;
+        }
+        self.missing.borrow_mut().remove(idx);
+        self.missing_list.clone().remove(idx as i32 + 1);
+        self.selection_changed();
+    }
+
+    fn checked_extra(&self) -> Vec<ModRef> {
+        self.extra
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| self.extra_list.checked(*idx as i32 + 1))
+            .map(|(_, mod_ref)| mod_ref.clone())
+            .collect()
+    }
+}
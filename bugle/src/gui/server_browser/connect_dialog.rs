@@ -3,17 +3,17 @@ use std::net::SocketAddr;
 use std::rc::Rc;
 use std::str::FromStr;
 
-use fltk::button::{Button, CheckButton, ReturnButton};
+use fltk::button::{Button, CheckButton, ReturnButton, ToggleButton};
 use fltk::enums::Align;
 use fltk::frame::Frame;
 use fltk::group::Group;
-use fltk::input::{Input, SecretInput};
+use fltk::input::{Input, InputType, SecretInput};
 use fltk::prelude::*;
 use fltk::window::Window;
 use fltk_float::grid::{CellAlign, GridBuilder};
 use fltk_float::LayoutElement;
 
-use crate::gui::{alert_error, wrapper_factory};
+use crate::gui::{alert_error, glyph, wrapper_factory};
 use crate::launcher::ConnectionInfo;
 use crate::servers::Server;
 
@@ -145,7 +145,15 @@ impl ConnectDialog {
             .unwrap()
             .wrap(Frame::default())
             .with_label("Password:");
-        let mut password_text = window.span(1, 3).unwrap().wrap(SecretInput::default());
+        let mut password_text = window.span(1, 2).unwrap().wrap(SecretInput::default());
+        let mut reveal_button = window
+            .cell()
+            .unwrap()
+            .with_horz_align(CellAlign::Start)
+            .wrap(ToggleButton::default())
+            .with_label(glyph::REVEAL)
+            .with_tooltip("Show or hide the password");
+        reveal_button.clear_visible_focus();
 
         let save_password_check = password.map(|password| {
             password_text.set_value(password);
@@ -193,6 +201,18 @@ impl ConnectDialog {
             parent.y() + (parent.h() - window.h()) / 2,
         );
 
+        reveal_button.set_callback({
+            let mut password_text = password_text.clone();
+            move |button| {
+                // Fl_Secret_Input only masks its text while its type is FL_SECRET_INPUT
+                password_text.set_type(if button.is_toggled() {
+                    InputType::Normal
+                } else {
+                    InputType::Secret
+                });
+                password_text.redraw();
+            }
+        });
         cancel_button.set_callback({
             let mut window = window.clone();
             move |_| window.hide()
@@ -1,6 +1,13 @@
 use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
 use std::rc::Rc;
+use std::sync::Arc;
 
+use fltk::app;
+use fltk::prelude::*;
+use fltk::table::{TableContext, TableRow};
 use nom::character::complete::{char, digit1};
 use nom::combinator::map_res;
 use nom::sequence::{separated_pair, terminated};
@@ -8,33 +15,151 @@ use nom::IResult;
 
 use crate::game::settings::server::DropOnDeath;
 use crate::game::settings::Hours;
-use crate::gui::weekday_name;
+use crate::game::Game;
+use crate::gui::mod_manager::show_bbcode;
 use crate::gui::widgets::{use_inspector_macros, Inspector, PropertiesTable, PropertyRow};
+use crate::gui::{is_table_nav_event, weekday_name};
 use crate::mod_manager::ModManager;
-use crate::servers::{Server, Validity};
+use crate::server_manager::ServerManager;
+use crate::servers::{PeakHours, Server, Validity};
 use crate::util::weekday_iter;
 
 use super::{community_name, mode_name, region_name};
 
 pub(super) struct DetailsPane {
-    table: PropertiesTable<Server, InspectorCtx>,
+    table: Rc<PropertiesTable<Server, InspectorCtx>>,
+    current_addr: Cell<Option<SocketAddr>>,
+    scroll_positions: RefCell<ScrollPositions>,
+}
+
+#[derive(Default)]
+struct ScrollPositions {
+    positions: HashMap<SocketAddr, i32>,
+    order: VecDeque<SocketAddr>,
 }
 
 struct InspectorCtx {
     mod_manager: Rc<ModManager>,
+    server_mgr: Rc<ServerManager>,
+    game: Arc<Game>,
+    listed_mod_ids: RefCell<Vec<u64>>,
 }
 
 impl DetailsPane {
-    pub fn new(mod_manager: Rc<ModManager>) -> Self {
-        let ctx = InspectorCtx { mod_manager };
+    pub fn new(
+        mod_manager: Rc<ModManager>,
+        server_mgr: Rc<ServerManager>,
+        game: Arc<Game>,
+    ) -> Self {
+        let ctx = InspectorCtx {
+            mod_manager,
+            server_mgr,
+            game,
+            listed_mod_ids: RefCell::new(Vec::new()),
+        };
+        let table = Rc::new(PropertiesTable::new(
+            ctx,
+            SERVER_DETAILS_ROWS,
+            "Server Details",
+            false,
+        ));
+
+        TableRow::clone(&table).set_callback({
+            let table = Rc::downgrade(&table);
+            move |widget| {
+                let Some(table) = table.upgrade() else {
+                    return;
+                };
+                if is_table_nav_event()
+                    && app::event_clicks()
+                    && (widget.callback_context() == TableContext::Cell)
+                {
+                    let row = widget.callback_row() as usize;
+                    if table.find_row(SERVER_NAME_HEADER) == Some(row) {
+                        show_server_info(&table, row);
+                    } else {
+                        show_mod_description(&table, row);
+                    }
+                }
+            }
+        });
+
         Self {
-            table: PropertiesTable::new(ctx, SERVER_DETAILS_ROWS, "Server Details"),
+            table,
+            current_addr: Cell::new(None),
+            scroll_positions: RefCell::new(Default::default()),
         }
     }
 
     pub fn populate(&self, server: Option<&Server>) {
+        let mut scroll_positions = self.scroll_positions.borrow_mut();
+        if let Some(addr) = self.current_addr.get() {
+            scroll_positions.insert(addr, self.table.row_position());
+        }
+
+        let addr = server.and_then(Server::game_addr);
+        self.current_addr.set(addr);
         self.table.populate(server);
+
+        if let Some(position) = addr.and_then(|addr| scroll_positions.get(addr)) {
+            TableRow::clone(&self.table).set_row_position(position);
+        }
+    }
+}
+
+impl ScrollPositions {
+    fn get(&self, addr: SocketAddr) -> Option<i32> {
+        self.positions.get(&addr).copied()
+    }
+
+    fn insert(&mut self, addr: SocketAddr, position: i32) {
+        if self.positions.insert(addr, position).is_some() {
+            return;
+        }
+        self.order.push_back(addr);
+        if self.order.len() > MAX_SCROLL_POSITIONS {
+            let evicted = self.order.pop_front().unwrap();
+            self.positions.remove(&evicted);
+        }
+    }
+}
+
+fn show_server_info(table: &PropertiesTable<Server, InspectorCtx>, row: usize) {
+    let Some(name) = table.value(row) else {
+        return;
+    };
+
+    // Without any BBCode tags, the rendered name would be identical to the raw one, so leave the
+    // double-click to the cell copy behavior.
+    if !name.contains('[') {
+        return;
     }
+    show_bbcode("Server Info", &name);
+}
+
+fn show_mod_description(table: &PropertiesTable<Server, InspectorCtx>, row: usize) {
+    let Some(mods_row) = table.find_row(MODS_HEADER) else {
+        return;
+    };
+    let Some(offset) = row.checked_sub(mods_row) else {
+        return;
+    };
+    let ctx = table.ctx();
+    let Some(mod_id) = ctx.listed_mod_ids.borrow().get(offset).copied() else {
+        return;
+    };
+
+    // Mods that aren't installed locally have no description to show, so leave the double-click
+    // to the cell copy behavior.
+    let installed_mods = ctx.game.installed_mods();
+    let Some(info) = installed_mods
+        .iter()
+        .filter_map(|entry| entry.info.as_ref().ok())
+        .find(|info| info.steam_file_id(ctx.game.branch()) == Some(mod_id))
+    else {
+        return;
+    };
+    show_bbcode(&format!("Description: {}", &info.name), &info.description);
 }
 
 impl InspectorCtx {
@@ -71,13 +196,114 @@ impl InspectorCtx {
         }
     }
 
+    fn inspect_peak_hours(
+        &self,
+        server: Option<&Server>,
+        row_consumer: &mut dyn FnMut(PropertyRow),
+        include_empty: bool,
+    ) {
+        let peak_hours = server
+            .and_then(Server::game_addr)
+            .and_then(|addr| self.server_mgr.server_peak_hours(addr));
+        match peak_hours {
+            Some(PeakHours {
+                start_hour,
+                end_hour,
+            }) => row_consumer([
+                PEAK_HOURS_HEADER.into(),
+                format!(
+                    "~{:02}:00\u{2013}{:02}:00 UTC (estimated from this session)",
+                    start_hour, end_hour
+                )
+                .into(),
+            ]),
+            None if include_empty => row_consumer([PEAK_HOURS_HEADER.into(), "".into()]),
+            None => (),
+        }
+    }
+
+    fn inspect_maintenance(
+        &self,
+        server: Option<&Server>,
+        row_consumer: &mut dyn FnMut(PropertyRow),
+        include_empty: bool,
+    ) {
+        let windows = server
+            .and_then(Server::game_addr)
+            .map(|addr| self.server_mgr.server_maintenance_windows(addr))
+            .unwrap_or_default();
+        if windows.is_empty() {
+            if include_empty {
+                row_consumer([MAINTENANCE_HEADER.into(), "".into()]);
+            }
+            return;
+        }
+
+        let text = windows
+            .iter()
+            .map(|window| {
+                format!(
+                    "~{}\u{2013}{} UTC",
+                    minute_of_day_text(window.start_minute),
+                    minute_of_day_text(window.end_minute)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        row_consumer([MAINTENANCE_HEADER.into(), text.into()]);
+    }
+
+    fn inspect_uptime(
+        &self,
+        server: Option<&Server>,
+        row_consumer: &mut dyn FnMut(PropertyRow),
+        include_empty: bool,
+    ) {
+        let uptime = server
+            .and_then(Server::game_addr)
+            .and_then(|addr| self.server_mgr.server_uptime(addr));
+        let Some(uptime) = uptime else {
+            if include_empty {
+                row_consumer([UPTIME_HEADER.into(), "".into()]);
+            }
+            return;
+        };
+
+        row_consumer([
+            UPTIME_HEADER.into(),
+            format!("{:.1}%", uptime.percentage).into(),
+        ]);
+        let num_days = uptime.hourly.len() / 24;
+        for (day_idx, day) in uptime.hourly.chunks(24).enumerate() {
+            if day.iter().all(Option::is_none) {
+                continue;
+            }
+            let bars: String = day
+                .iter()
+                .map(|status| match status {
+                    Some(true) => '█',
+                    Some(false) => '░',
+                    None => '·',
+                })
+                .collect();
+            let days_ago = num_days - day_idx - 1;
+            let label = match days_ago {
+                0 => "last 24 hours".to_string(),
+                1 => "1 day ago".to_string(),
+                _ => format!("{} days ago", days_ago),
+            };
+            row_consumer(["".into(), format!("{} ({})", bars, label).into()]);
+        }
+    }
+
     fn inspect_mods(
         &self,
         server: Option<&Server>,
         row_consumer: &mut dyn FnMut(PropertyRow),
         include_empty: bool,
     ) {
-        let mut header = "Mods";
+        let mut header = MODS_HEADER;
+        self.listed_mod_ids.borrow_mut().clear();
 
         let mods = match server.and_then(|server| server.mods.as_ref()) {
             Some(mods) => mods,
@@ -112,6 +338,11 @@ impl InspectorCtx {
         }
 
         Rc::clone(&self.mod_manager).resolve_mods(&mut resolution[0..resolve_count]);
+        if server.is_some() {
+            self.listed_mod_ids
+                .borrow_mut()
+                .extend(resolution[0..resolve_count].iter().map(|(id, _)| *id));
+        }
         for (id, name) in &resolution[0..resolve_count] {
             match name {
                 Some(name) => row_consumer([header.into(), name.clone().into()]),
@@ -140,10 +371,17 @@ use_inspector_macros!(Server, InspectorCtx);
 const SERVER_DETAILS_ROWS: &[Inspector<Server, InspectorCtx>] = &[
     inspect_attr!("ID", |server| server.id.clone().into()),
     inspect_attr!("Server Name", |server| server.name.clone().into()),
+    inspect_opt_attr!("Wipe Schedule", |server| server
+        .wipe_schedule
+        .clone()
+        .map(Into::into)),
     inspect_attr!("Host", |server| server.host().into()),
     inspect_attr!("Map Name", |server| server.map.clone().into()),
     inspect_attr!("Mode", |server| mode_name(server.mode()).into()),
     inspect_attr!("Region", |server| region_name(server.region).into()),
+    InspectorCtx::inspect_peak_hours,
+    InspectorCtx::inspect_maintenance,
+    InspectorCtx::inspect_uptime,
     inspect_attr!("Max Clan Size", |server| server
         .general
         .max_clan_size
@@ -232,6 +470,13 @@ const SERVER_DETAILS_ROWS: &[Inspector<Server, InspectorCtx>] = &[
     inspect_opt_attr!("Problems", problems_cell_value),
 ];
 
+const SERVER_NAME_HEADER: &str = "Server Name";
+const MODS_HEADER: &str = "Mods";
+const PEAK_HOURS_HEADER: &str = "Peak Hours";
+const MAINTENANCE_HEADER: &str = "Typical Maintenance";
+const UPTIME_HEADER: &str = "Uptime (7 days)";
+const MAX_SCROLL_POSITIONS: usize = 100;
+
 fn parse_mod_counts(input: &str) -> IResult<&str, (usize, usize), ()> {
     terminated(
         separated_pair(
@@ -264,3 +509,7 @@ fn problems_cell_value(server: &Server) -> Option<Cow<'static, str>> {
     problems.truncate(problems.len() - 2);
     Some(problems.into())
 }
+
+fn minute_of_day_text(minute: u32) -> String {
+    format!("{:02}:{:02}", minute / 60, minute % 60)
+}
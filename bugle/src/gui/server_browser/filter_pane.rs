@@ -169,7 +169,7 @@ impl FilterPane {
         });
     }
 
-    fn populate(&self, filter: &Filter) {
+    pub fn populate(&self, filter: &Filter) {
         self.name_input.clone().set_value(filter.name());
         self.map_input.clone().set_value(filter.map());
         self.type_input.clone().set_value(filter.type_filter as u8);
@@ -335,6 +335,8 @@ fn type_name(type_filter: TypeFilter) -> Cow<'static, str> {
         TypeFilter::Official => format!("Official {}", glyph::OFFICIAL).into(),
         TypeFilter::Private => "Private".into(),
         TypeFilter::Favorite => format!("Favorite {}", glyph::FAVORITE).into(),
+        TypeFilter::Visited => "Visited".into(),
+        TypeFilter::Saved => "Saved".into(),
     }
 }
 
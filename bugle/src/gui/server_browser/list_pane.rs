@@ -1,32 +1,42 @@
 use std::borrow::Cow;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
+use std::net::SocketAddr;
 use std::rc::Rc;
 
-use fltk::enums::{Align, Event};
+use chrono::{DateTime, Local, Utc};
+use fltk::app;
+use fltk::enums::{Align, Color, Event};
+use fltk::menu::MenuItem;
 use fltk::misc::Tooltip;
 use fltk::prelude::*;
 use fltk::table::TableContext;
 use lazy_static::lazy_static;
 
-use crate::gui::data::{IterableTableSource, TableSource};
-use crate::gui::widgets::{DataColumn, DataTable, DataTableProperties, DataTableUpdate};
-use crate::gui::{glyph, is_table_nav_event};
-use crate::servers::{Server, SortCriteria, SortKey};
-use crate::util::weak_cb;
+use crate::game::settings::Hours;
+use crate::gui::data::TableSource;
+use crate::gui::widgets::{
+    draw_table_cell, DataColumn, DataTable, DataTableProperties, DataTableUpdate,
+};
+use crate::gui::{glyph, is_table_nav_event, weekday_name};
+use crate::servers::{PingTrend, Server, SortCriteria, SortKey};
+use crate::util::{weak_cb, weekday_iter};
 
 use super::{mode_name, region_name};
 
-type ServerRow = [Cow<'static, str>; NUM_COLS];
-
 pub(super) struct ListPane {
-    table: DataTable<ServerRow>,
+    table: DataTable<()>,
     sort_criteria: RefCell<SortCriteria>,
     server_list: RefCell<Rc<RefCell<dyn TableSource<Output = Server>>>>,
     on_sort_changed: RefCell<Box<dyn Fn(SortCriteria)>>,
     on_server_selected: RefCell<Box<dyn Fn(Option<&Server>)>>,
+    on_block_server: RefCell<Box<dyn Fn()>>,
+    on_show_health_changed: RefCell<Box<dyn Fn()>>,
     selection: RefCell<Selection>,
+    show_health: Cell<bool>,
+    settings_tooltip: RefCell<CString>,
+    last_visits: Rc<HashMap<SocketAddr, DateTime<Utc>>>,
 }
 
 struct Selection {
@@ -35,7 +45,12 @@ struct Selection {
 }
 
 impl ListPane {
-    pub fn new(initial_sort: &SortCriteria, scroll_lock: bool) -> Rc<Self> {
+    pub fn new(
+        initial_sort: &SortCriteria,
+        scroll_lock: bool,
+        show_health: bool,
+        last_visits: Rc<HashMap<SocketAddr, DateTime<Utc>>>,
+    ) -> Rc<Self> {
         let sorted_col = sort_key_to_column(initial_sort.key);
         let columns = SERVER_LIST_COLS
             .iter()
@@ -45,7 +60,9 @@ impl ListPane {
                 col.to_data_column(ascending)
             })
             .collect();
-        let mut table = DataTable::default().with_properties(DataTableProperties {
+        // Cell values are computed from the server list as the cells are drawn, so that only the
+        // visible rows are ever materialized.
+        let mut table = DataTable::new(|_, _| "").with_properties(DataTableProperties {
             columns,
             cell_padding: 4,
             cell_selection_color: fltk::enums::Color::Free,
@@ -56,6 +73,9 @@ impl ListPane {
         table.set_row_header(false);
         table.set_col_header(true);
         table.set_col_resize(true);
+        if !show_health {
+            table.set_col_width(HEALTH_COLUMN as _, 0);
+        }
 
         table.end();
         table.hide();
@@ -66,12 +86,21 @@ impl ListPane {
             server_list: RefCell::new(Rc::new(RefCell::new(Vec::new()))),
             on_sort_changed: RefCell::new(Box::new(|_| ())),
             on_server_selected: RefCell::new(Box::new(|_| ())),
+            on_block_server: RefCell::new(Box::new(|| ())),
+            on_show_health_changed: RefCell::new(Box::new(|| ())),
             selection: RefCell::new(Selection {
                 index: None,
                 scroll_lock,
             }),
+            show_health: Cell::new(show_health),
+            settings_tooltip: RefCell::new(CString::default()),
+            last_visits,
         });
 
+        let _ = table.clone().with_draw_fn(weak_cb!(
+            [this] => |table, row, col, x, y, w, h| this.draw_cell(table, row, col, x, y, w, h)
+        ));
+
         table.set_callback(weak_cb!(
             [this] => |_| {
                 if is_table_nav_event() {
@@ -82,6 +111,9 @@ impl ListPane {
 
         let mut tooltip_pos = None;
         table.handle(weak_cb!([this] => |_, event| {
+            if this.context_menu_requested(event) {
+                return true;
+            }
             this.update_tooltip(event, &mut tooltip_pos);
         }; false));
 
@@ -107,19 +139,8 @@ impl ListPane {
         let servers = servers_ref.borrow();
 
         let selection = self.selection.borrow();
-        let mut reselect = false;
-
-        {
-            let data = self.table.data();
-            let mut data = data.borrow_mut();
-            for idx in indices.into_iter() {
-                data[idx] = make_server_row(&servers[idx]);
-                if Some(idx) == selection.index {
-                    reselect = true;
-                }
-            }
-        }
-        self.table.updated(DataTableUpdate::DATA);
+        let reselect = indices.into_iter().any(|idx| Some(idx) == selection.index);
+        self.table.set_row_count(servers.len());
 
         if reselect {
             self.on_server_selected.borrow()(Some(&servers[selection.index.unwrap()]));
@@ -134,6 +155,14 @@ impl ListPane {
         *self.on_server_selected.borrow_mut() = Box::new(on_server_selected);
     }
 
+    pub fn set_on_block_server(&self, on_block_server: impl Fn() + 'static) {
+        *self.on_block_server.borrow_mut() = Box::new(on_block_server);
+    }
+
+    pub fn set_on_show_health_changed(&self, on_show_health_changed: impl Fn() + 'static) {
+        *self.on_show_health_changed.borrow_mut() = Box::new(on_show_health_changed);
+    }
+
     pub fn selected_index(&self) -> Option<usize> {
         self.selection.borrow().index
     }
@@ -170,6 +199,10 @@ impl ListPane {
         self.selection.borrow().scroll_lock
     }
 
+    pub fn show_health(&self) -> bool {
+        self.show_health.get()
+    }
+
     pub fn set_scroll_lock(&self, scroll_lock: bool) {
         self.selection.borrow_mut().scroll_lock = scroll_lock;
         if scroll_lock {
@@ -178,19 +211,126 @@ impl ListPane {
     }
 
     fn set_server_list(&self, server_list: Rc<RefCell<dyn TableSource<Output = Server>>>) {
+        let row_count = server_list.borrow().len();
+        *self.server_list.borrow_mut() = server_list;
+        self.table.set_row_count(row_count);
+    }
+
+    fn draw_cell(&self, table: &DataTable<()>, row: i32, col: i32, x: i32, y: i32, w: i32, h: i32) {
+        let text = match self.cell_value(row as _, col as _) {
+            Some(text) => text,
+            None => return,
+        };
+        let props = table.properties();
+        let props = props.borrow();
+        let fill_color =
+            if table.is_selected(row, col) { props.cell_selection_color } else { props.cell_color };
+        draw_table_cell(
+            &text,
+            x,
+            y,
+            w,
+            h,
+            props.columns[col as usize].align,
+            props.cell_border_color,
+            fill_color,
+            props.cell_font_color,
+            props.cell_font,
+            props.cell_font_size,
+            props.cell_padding,
+        );
+
+        if col as usize == HEALTH_COLUMN {
+            let Some(score) = self.health_score(row as _) else {
+                return;
+            };
+            let color = if score >= 80 {
+                Color::DarkGreen
+            } else if score >= 40 {
+                Color::DarkYellow
+            } else {
+                Color::Red
+            };
+            let bar_w =
+                (w - 2 * props.cell_padding - HEALTH_TEXT_WIDTH).max(0) * score as i32 / 100;
+            fltk::draw::draw_rect_fill(
+                x + props.cell_padding,
+                y + HEALTH_BAR_MARGIN,
+                bar_w,
+                h - 2 * HEALTH_BAR_MARGIN,
+                color,
+            );
+        }
+
+        if col as usize == PING_COLUMN {
+            let (glyph, color) = match self.ping_trend(row as _) {
+                Some(PingTrend::Degrading) => (glyph::TREND_UP, Color::Red),
+                Some(PingTrend::Improving) => (glyph::TREND_DOWN, Color::DarkGreen),
+                _ => return,
+            };
+            fltk::draw::set_draw_color(color);
+            fltk::draw::draw_text2(
+                glyph,
+                x + props.cell_padding,
+                y,
+                TREND_GLYPH_WIDTH,
+                h,
+                Align::Left,
+            );
+        }
+    }
+
+    fn cell_value(&self, row: usize, col: usize) -> Option<Cow<'static, str>> {
+        let server_list = self.server_list.try_borrow().ok()?;
+        let servers = server_list.try_borrow().ok()?;
+        if row >= servers.len() {
+            return None;
+        }
+        if col == LAST_VISITED_COLUMN {
+            return Some(self.last_visited_col_value(&servers[row]));
+        }
+        Some(SERVER_LIST_COLS[col].value_for(&servers[row]))
+    }
+
+    fn last_visited_col_value(&self, server: &Server) -> Cow<'static, str> {
+        match server
+            .game_addr()
+            .and_then(|addr| self.last_visits.get(&addr))
         {
-            let servers = server_list.borrow();
-            {
-                *self.table.data().borrow_mut() = servers.iter().map(make_server_row).collect();
-            }
-            self.table.updated(DataTableUpdate::DATA);
+            Some(visit) => visit
+                .with_timezone(&Local)
+                .format("%Y-%m-%d")
+                .to_string()
+                .into(),
+            None => "".into(),
         }
-        *self.server_list.borrow_mut() = server_list;
+    }
+
+    fn health_score(&self, row: usize) -> Option<u8> {
+        let server_list = self.server_list.try_borrow().ok()?;
+        let servers = server_list.try_borrow().ok()?;
+        servers.get(row)?.health_score()
+    }
+
+    fn ping_trend(&self, row: usize) -> Option<PingTrend> {
+        let server_list = self.server_list.try_borrow().ok()?;
+        let servers = server_list.try_borrow().ok()?;
+        if row >= servers.len() {
+            return None;
+        }
+        let server = &servers[row];
+        server.ping.map(|_| server.ping_trend)
     }
 
     fn clicked(&self) {
         match self.table.callback_context() {
-            TableContext::ColHeader => self.header_clicked(),
+            TableContext::ColHeader => {
+                if app::event_mouse_button() == app::MouseButton::Right {
+                    self.header_right_clicked();
+                } else {
+                    self.header_clicked();
+                }
+            }
             TableContext::Cell => {
                 let _ = self.table.clone().take_focus();
 
@@ -204,6 +344,24 @@ impl ListPane {
         }
     }
 
+    fn context_menu_requested(&self, event: Event) -> bool {
+        if (event != Event::Push) || (app::event_mouse_button() != app::MouseButton::Right) {
+            return false;
+        }
+        let Some((TableContext::Cell, row, _, _)) = self.table.cursor2rowcol() else {
+            return false;
+        };
+
+        let _ = self.table.clone().take_focus();
+        self.set_selected_index(Some(row as _), false);
+
+        let menu = MenuItem::new(&[MENU_BLOCK_SERVER]);
+        if menu.popup(app::event_x(), app::event_y()).is_some() {
+            self.on_block_server.borrow()();
+        }
+        true
+    }
+
     fn header_clicked(&self) {
         let col = self.table.callback_col() as usize;
         let new_key = match column_to_sort_key(col) {
@@ -214,11 +372,33 @@ impl ListPane {
         let new_criteria = if new_key == old_criteria.key {
             old_criteria.reversed()
         } else {
-            SortCriteria {
-                key: new_key,
-                ascending: true,
-            }
+            SortCriteria::new(new_key, true)
         };
+        self.set_sort_criteria(new_criteria);
+    }
+
+    fn header_right_clicked(&self) {
+        let show_health = !self.show_health.get();
+        let menu = MenuItem::new(&[if show_health { MENU_SHOW_HEALTH } else { MENU_HIDE_HEALTH }]);
+        if menu.popup(app::event_x(), app::event_y()).is_none() {
+            return;
+        }
+
+        self.show_health.set(show_health);
+        let new_width = if show_health { SERVER_LIST_COLS[HEALTH_COLUMN].width } else { 0 };
+        self.table
+            .clone()
+            .set_col_width(HEALTH_COLUMN as _, new_width);
+        self.on_show_health_changed.borrow()();
+
+        if !show_health && (self.sort_criteria.borrow().key == SortKey::Health) {
+            self.set_sort_criteria(SortCriteria::default());
+        }
+    }
+
+    fn set_sort_criteria(&self, new_criteria: SortCriteria) {
+        let old_criteria = *self.sort_criteria.borrow();
+        let col = sort_key_to_column(new_criteria.key);
         {
             let props = self.table.properties();
             let mut props = props.borrow_mut();
@@ -252,36 +432,29 @@ impl ListPane {
         let table_widget: &fltk::table::TableRow = &self.table;
         match event {
             Event::Move => {
-                let mut new_pos = match self.table.cursor2rowcol() {
-                    Some((TableContext::ColHeader, row, col, _)) if col < 6 => {
-                        Some((TableContext::ColHeader, row, col))
-                    }
-                    Some((TableContext::Cell, row, col, _)) if col < 6 => {
-                        Some((TableContext::Cell, row, col))
+                let new_pos = match self.table.cursor2rowcol() {
+                    Some((ctx @ (TableContext::ColHeader | TableContext::Cell), row, col, _)) => {
+                        Some((ctx, row, col))
                     }
                     _ => None,
                 };
-                if *tooltip_pos != new_pos {
-                    if let Some((TableContext::Cell, row, col)) = &new_pos {
-                        let data = self.table.data();
-                        let data = data.borrow();
-                        if data[*row as usize][*col as usize].is_empty() {
-                            new_pos = None;
-                        }
-                    }
+                if *tooltip_pos == new_pos {
+                    return;
                 }
+                let tip = new_pos.and_then(|(ctx, row, col)| self.tooltip_text(ctx, row, col));
+                let new_pos = tip.and(new_pos);
                 if *tooltip_pos != new_pos {
                     *tooltip_pos = new_pos;
                     Tooltip::current(&self.table.parent().unwrap());
-                    if let Some((ctx, row, col)) = &tooltip_pos {
-                        let (x, y, w, h) = self.table.find_cell(*ctx, *row, *col).unwrap();
+                    if let (Some((ctx, row, col)), Some(tip)) = (new_pos, tip) {
+                        let (x, y, w, h) = self.table.find_cell(ctx, row, col).unwrap();
                         Tooltip::enter_area(
                             table_widget,
                             x - &self.table.x(),
                             y - &self.table.y(),
                             w,
                             h,
-                            COL_TOOLTIPS[*col as usize].as_c_str(),
+                            tip,
                         );
                     }
                 }
@@ -294,6 +467,63 @@ impl ListPane {
             _ => (),
         }
     }
+
+    fn tooltip_text(&self, ctx: TableContext, row: i32, col: i32) -> Option<&'static CStr> {
+        let col = col as usize;
+        match ctx {
+            TableContext::ColHeader => COL_TOOLTIPS.get(col).map(CString::as_c_str),
+            TableContext::Cell if col < COL_TOOLTIPS.len() => {
+                let value = self.cell_value(row as _, col)?;
+                if value.is_empty() {
+                    None
+                } else {
+                    Some(COL_TOOLTIPS[col].as_c_str())
+                }
+            }
+            TableContext::Cell => self.settings_tooltip(row as _),
+            _ => None,
+        }
+    }
+
+    fn settings_tooltip(&self, row: usize) -> Option<&'static CStr> {
+        let summary = {
+            let server_list = self.server_list.try_borrow().ok()?;
+            let servers = server_list.try_borrow().ok()?;
+            if row >= servers.len() {
+                return None;
+            }
+            settings_summary(&servers[row])
+        };
+
+        // FLTK holds on to the tooltip text without copying it. The text is only replaced when the
+        // cursor enters another cell, right before the tooltip is pointed at the new text.
+        let mut tooltip = self.settings_tooltip.borrow_mut();
+        *tooltip = CString::new(summary).ok()?;
+        Some(unsafe { &*(tooltip.as_c_str() as *const CStr) })
+    }
+}
+
+fn settings_summary(server: &Server) -> String {
+    let mut parts = vec![
+        format!("XP: {}\u{d7}", server.progression.xp_rate_mult.0),
+        format!("Harvest: {}\u{d7}", server.harvesting.harvest_amount_mult.0),
+    ];
+    let raid_hours: Vec<String> = weekday_iter()
+        .filter_map(|weekday| {
+            let Hours { start, end } = server.general.raid_hours.get(weekday)?;
+            Some(format!(
+                "{} {}\u{2013}{}",
+                weekday_name(weekday),
+                start,
+                end
+            ))
+        })
+        .collect();
+    if !raid_hours.is_empty() {
+        parts.push(format!("Raid: {}", raid_hours.join(", ")));
+    }
+    parts.push(format!("Clan max: {}", server.general.max_clan_size));
+    parts.join(", ")
 }
 
 struct Column {
@@ -360,7 +590,7 @@ const SERVER_LIST_COLS: &[Column] = &[
     col!(glyph::ERROR, 20, Center, None, |server| str_if(!server.is_valid(), glyph::ERROR)),
     col!(glyph::LOCK, 20, Center, None, |server| str_if(server.password_protected, glyph::LOCK)),
     col!(glyph::TOOLS, 20, Center, None, |server| str_if(server.is_modded(), glyph::TOOLS)),
-    col!(glyph::OFFICIAL, 20, Center, None, |server| str_if(server.is_official(), glyph::OFFICIAL)),
+    col!(glyph::OFFICIAL, 20, Center, None, |server| official_col_value(server)),
     col!(glyph::BATTLEYE, 20, Center, None, |server| str_if(server.general.battleye_required, glyph::BATTLEYE)),
     col!(glyph::FAVORITE, 20, Center, None, |server| str_if(server.favorite, glyph::FAVORITE)),
     col!(glyph::SAVED, 20, Center, None, |server| str_if(server.is_saved(), glyph::SAVED)),
@@ -371,8 +601,21 @@ const SERVER_LIST_COLS: &[Column] = &[
     col!("Players", 70, Center, Some(SortKey::Players), |server| players_col_value(server).into()),
     col!("Age", 60, Center, Some(SortKey::Age), |server| age_col_value(server).into()),
     col!("Ping", 60, Center, Some(SortKey::Ping), |server| ping_col_value(server).into()),
+    col!("Health", 80, Right, Some(SortKey::Health), |server| health_col_value(server).into()),
+    // Filled in by ListPane, since the value comes from the launch history
+    col!("Last Visited", 90, Center, Some(SortKey::LastVisited), |_| "".into()),
 ];
-const NUM_COLS: usize = SERVER_LIST_COLS.len();
+
+const PING_COLUMN: usize = 13;
+const HEALTH_COLUMN: usize = 14;
+const LAST_VISITED_COLUMN: usize = 15;
+const TREND_GLYPH_WIDTH: i32 = 10;
+const HEALTH_TEXT_WIDTH: i32 = 24;
+const HEALTH_BAR_MARGIN: i32 = 4;
+
+const MENU_BLOCK_SERVER: &str = "Block Server";
+const MENU_SHOW_HEALTH: &str = "Show Health Column";
+const MENU_HIDE_HEALTH: &str = "Hide Health Column";
 
 lazy_static! {
     static ref SORT_KEY_TO_COLUMN: HashMap<SortKey, usize> = {
@@ -384,13 +627,14 @@ lazy_static! {
         }
         map
     };
-    static ref COL_TOOLTIPS: [CString; 6] = [
+    static ref COL_TOOLTIPS: [CString; 7] = [
         CString::new("Invalid").unwrap(),
         CString::new("Password protected").unwrap(),
         CString::new("Modded").unwrap(),
         CString::new("Official").unwrap(),
         CString::new("BattlEye required").unwrap(),
         CString::new("Favorite").unwrap(),
+        CString::new("Saved").unwrap(),
     ];
 }
 
@@ -406,6 +650,14 @@ fn str_if(condition: bool, str_true: &'static str) -> Cow<'static, str> {
     (if condition { str_true } else { "" }).into()
 }
 
+fn official_col_value(server: &Server) -> Cow<'static, str> {
+    if server.under_maintenance {
+        glyph::MAINTENANCE.into()
+    } else {
+        str_if(server.is_official(), glyph::OFFICIAL)
+    }
+}
+
 fn players_col_value(server: &Server) -> String {
     let prefix = match server.connected_players {
         Some(players) => format!("{}/{}", players, server.max_players),
@@ -425,11 +677,19 @@ fn age_col_value(server: &Server) -> String {
 fn ping_col_value(server: &Server) -> String {
     let prefix = match server.ping {
         Some(ping) => format!("{}", ping.as_millis()),
+        None if server.under_maintenance => glyph::MAINTENANCE.to_string(),
         None => "????".to_string(),
     };
     with_pong_suffix(prefix, server)
 }
 
+fn health_col_value(server: &Server) -> String {
+    server
+        .health_score()
+        .map(|score| score.to_string())
+        .unwrap_or_default()
+}
+
 fn with_pong_suffix(mut prefix: String, server: &Server) -> String {
     if server.waiting_for_pong {
         prefix.push(' ');
@@ -437,7 +697,3 @@ fn with_pong_suffix(mut prefix: String, server: &Server) -> String {
     }
     prefix
 }
-
-fn make_server_row(server: &Server) -> ServerRow {
-    std::array::from_fn(|idx| SERVER_LIST_COLS[idx].value_for(server))
-}
@@ -0,0 +1,128 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use fltk::browser::HoldBrowser;
+use fltk::button::{Button, ReturnButton};
+use fltk::group::Group;
+use fltk::prelude::*;
+use fltk::window::Window;
+use fltk_float::grid::{CellAlign, Grid};
+use fltk_float::{LayoutElement, SimpleWrapper};
+
+use crate::gui::wrapper_factory;
+
+pub struct NotesSearchDialog {
+    window: Window,
+    result: Rc<Cell<Option<usize>>>,
+}
+
+pub struct NotesSearchMatch {
+    pub index: usize,
+    pub name: String,
+    pub notes: Option<String>,
+}
+
+impl NotesSearchDialog {
+    pub fn new(parent: &Group, matches: Vec<NotesSearchMatch>) -> Self {
+        let mut window = Window::default()
+            .with_size(480, 320)
+            .with_label("Search Results");
+
+        let mut root = Grid::builder_with_factory(wrapper_factory())
+            .with_col_spacing(10)
+            .with_row_spacing(10)
+            .with_padding(10, 10, 10, 10);
+        root.col().with_stretch(1).add();
+        let btn_group = root.col_group().add();
+        root.extend_group(btn_group).batch(2);
+
+        root.row().with_stretch(1).add();
+        let mut results_list = HoldBrowser::default();
+        root.span(1, 3)
+            .unwrap()
+            .with_vert_align(CellAlign::Stretch)
+            .add(SimpleWrapper::new(
+                results_list.as_base_widget(),
+                Default::default(),
+            ));
+        for found in matches.iter() {
+            let label = match found.notes.as_ref() {
+                Some(notes) => format!("{} — {}", found.name, notes.replace('\n', " ")),
+                None => found.name.clone(),
+            };
+            results_list.add(&label);
+        }
+
+        root.row().add();
+        root.cell().unwrap().skip();
+        let mut select_button = root
+            .cell()
+            .unwrap()
+            .wrap(ReturnButton::default())
+            .with_label("Select");
+        let mut cancel_button = root
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Cancel");
+
+        let root = root.end();
+        root.group().resize(0, 0, window.w(), window.h());
+        root.layout_children();
+        window.end();
+
+        window.set_pos(
+            parent.x() + (parent.w() - window.w()) / 2,
+            parent.y() + (parent.h() - window.h()) / 2,
+        );
+
+        let result = Rc::new(Cell::new(None));
+        let indices: Rc<Vec<usize>> = Rc::new(matches.iter().map(|found| found.index).collect());
+
+        let select = {
+            let results_list = results_list.clone();
+            let result = Rc::clone(&result);
+            let mut window = window.clone();
+            move || {
+                let line = results_list.value();
+                if line > 0 {
+                    result.set(Some(indices[(line - 1) as usize]));
+                    window.hide();
+                }
+            }
+        };
+        let select = Rc::new(select);
+
+        results_list.set_callback({
+            let select = Rc::clone(&select);
+            move |_| {
+                if fltk::app::event_clicks() {
+                    select();
+                }
+            }
+        });
+        select_button.set_callback(move |_| select());
+        cancel_button.set_callback({
+            let mut window = window.clone();
+            move |_| window.hide()
+        });
+
+        if !matches.is_empty() {
+            results_list.select(1);
+        }
+
+        Self { window, result }
+    }
+
+    pub fn run(&self) -> Option<usize> {
+        let mut window = self.window.clone();
+        window.make_modal(true);
+        window.show();
+
+        while window.shown() && !fltk::app::should_program_quit() {
+            fltk::app::wait();
+        }
+
+        self.result.take()
+    }
+}
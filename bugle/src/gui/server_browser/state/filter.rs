@@ -1,27 +1,34 @@
+use std::collections::HashSet;
+use std::net::IpAddr;
 use std::ops::{Deref, DerefMut};
-use std::str::FromStr;
+use std::sync::Arc;
 
-use ini_persist::load::ParseProperty;
-use ini_persist::save::DisplayProperty;
 use regex::{Regex, RegexBuilder};
 
 use crate::config::ServerBrowserConfig;
 use crate::gui::data::RowFilter;
-use crate::servers::{EnumFilter, RangeFilter, Server};
+use crate::servers::Server;
 
 #[derive(Clone, Debug)]
 pub struct Filter {
     values: crate::servers::Filter,
     name_re: Regex,
     map_re: Regex,
+    blocked_servers: Arc<HashSet<IpAddr>>,
+    visited_servers: Arc<HashSet<IpAddr>>,
 }
 
 impl Filter {
-    pub fn from_config(config: &ServerBrowserConfig) -> Self {
+    pub fn from_config(
+        config: &ServerBrowserConfig,
+        visited_servers: Arc<HashSet<IpAddr>>,
+    ) -> Self {
         Self {
             values: config.filter.clone(),
             name_re: Self::regex(&config.filter.name),
             map_re: Self::regex(&config.filter.map),
+            blocked_servers: Arc::clone(&config.blocked_servers.0),
+            visited_servers,
         }
     }
 
@@ -43,6 +50,20 @@ impl Filter {
         self.values.map = map;
     }
 
+    pub fn blocked_servers(&self) -> &Arc<HashSet<IpAddr>> {
+        &self.blocked_servers
+    }
+
+    pub fn set_blocked_servers(&mut self, blocked_servers: Arc<HashSet<IpAddr>>) {
+        self.blocked_servers = blocked_servers;
+    }
+
+    pub fn clear(&mut self) {
+        self.values = Default::default();
+        self.name_re = Self::regex("");
+        self.map_re = Self::regex("");
+    }
+
     fn regex(text: &str) -> Regex {
         RegexBuilder::new(&regex::escape(&text))
             .case_insensitive(true)
@@ -70,40 +91,16 @@ impl AsRef<crate::servers::Filter> for Filter {
     }
 }
 
-trait PropertyFilter<T> {
-    fn matches(&self, getter: impl FnOnce() -> T) -> bool;
-}
-
-impl<T, F: PropertyFilter<T>> PropertyFilter<T> for Option<F> {
-    fn matches(&self, getter: impl FnOnce() -> T) -> bool {
-        self.as_ref().map_or(true, |filter| filter.matches(getter))
-    }
-}
-
-impl PropertyFilter<bool> for bool {
-    fn matches(&self, getter: impl FnOnce() -> bool) -> bool {
-        *self == getter()
-    }
-}
-
-impl<T: ParseProperty + DisplayProperty + Copy + PartialOrd> PropertyFilter<T> for RangeFilter<T> {
-    fn matches(&self, getter: impl FnOnce() -> T) -> bool {
-        self.matches(getter())
-    }
-}
-
-impl<T: FromStr + Into<&'static str> + Copy + Eq> PropertyFilter<T> for EnumFilter<T> {
-    fn matches(&self, getter: impl FnOnce() -> T) -> bool {
-        self.matches(getter())
-    }
-}
-
 impl RowFilter<Server> for Filter {
     fn matches(&self, server: &Server) -> bool {
         !server.tombstone
+            && !self.blocked_servers.contains(&server.ip)
             && self.name_re.is_match(&server.name)
             && self.map_re.is_match(&server.map)
-            && self.values.type_filter.matches(server)
+            && self
+                .values
+                .type_filter
+                .matches(server, &self.visited_servers)
             && self.values.mode.map_or(true, |mode| server.mode() == mode)
             && self
                 .values
@@ -118,94 +115,9 @@ impl RowFilter<Server> for Filter {
                 .values
                 .mods
                 .map_or(true, |mods| server.is_modded() == mods)
-            && self.values.community.matches(|| server.general.community)
-            && self
-                .values
-                .max_clan_size
-                .matches(|| server.general.max_clan_size)
-            && self
-                .values
-                .raid_enabled
-                .matches(|| server.general.raid_enabled)
-            && self
-                .values
-                .raid_restricted
-                .matches(|| server.general.raid_restricted)
-            && self
-                .values
-                .xp_rate_mult
-                .matches(|| server.progression.xp_rate_mult)
-            && self
-                .values
-                .day_cycle_speed_mult
-                .matches(|| server.daylight.day_cycle_speed_mult)
-            && self
-                .values
-                .dawn_dusk_speed_mult
-                .matches(|| server.daylight.dawn_dusk_speed_mult)
-            && self
-                .values
-                .use_catch_up_time
-                .matches(|| server.daylight.use_catch_up_time)
-            && self
-                .values
-                .stamina_cost_mult
-                .matches(|| server.survival.stamina_cost_mult)
-            && self
-                .values
-                .idle_thirst_mult
-                .matches(|| server.survival.idle_thirst_mult)
-            && self
-                .values
-                .active_thirst_mult
-                .matches(|| server.survival.active_thirst_mult)
-            && self
-                .values
-                .idle_hunger_mult
-                .matches(|| server.survival.idle_hunger_mult)
-            && self
-                .values
-                .active_hunger_mult
-                .matches(|| server.survival.active_hunger_mult)
-            && self
-                .values
-                .drop_items_on_death
-                .matches(|| server.survival.drop_items_on_death)
-            && self
-                .values
-                .anyone_can_loot_corpse
-                .matches(|| server.survival.anyone_can_loot_corpse)
-            && self
-                .values
-                .offline_chars_in_world
-                .matches(|| server.survival.offline_chars_in_world)
-            && self
-                .values
-                .durability_mult
-                .matches(|| server.combat.durability_mult)
-            && self
-                .values
-                .thrall_wakeup_time_secs
-                .matches(|| server.combat.thrall_wakeup_time.num_seconds())
-            && self
-                .values
-                .harvest_amount_mult
-                .matches(|| server.harvesting.harvest_amount_mult)
-            && self
-                .values
-                .item_spoil_rate_mult
-                .matches(|| server.harvesting.item_spoil_rate_mult)
-            && self
-                .values
-                .rsrc_respawn_speed_mult
-                .matches(|| server.harvesting.rsrc_respawn_speed_mult)
-            && self
-                .values
-                .crafting_time_mult
-                .matches(|| server.crafting.crafting_time_mult)
-            && self
-                .values
-                .thrall_crafting_time_mult
-                .matches(|| server.crafting.thrall_crafting_time_mult)
+            && self.values.mod_count.as_ref().map_or(true, |mod_count| {
+                mod_count.matches(server.mod_count().try_into().unwrap_or(u8::MAX))
+            })
+            && self.values.settings_match(server)
     }
 }
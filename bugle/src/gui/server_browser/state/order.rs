@@ -1,7 +1,10 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::rc::Rc;
 
+use chrono::{DateTime, Utc};
+
 use crate::gui::data::{RowComparator, RowOrder};
 use crate::servers::{Region, Server, SortCriteria, SortKey};
 
@@ -43,19 +46,45 @@ macro_rules! cmp_options {
 pub struct SortOrder {
     pub criteria: SortCriteria,
     region_order: Rc<HashMap<Region, usize>>,
+    last_visits: Rc<HashMap<SocketAddr, DateTime<Utc>>>,
 }
 
 impl SortOrder {
-    pub fn new(criteria: SortCriteria, region_order: HashMap<Region, usize>) -> Self {
+    pub fn new(
+        criteria: SortCriteria,
+        region_order: HashMap<Region, usize>,
+        last_visits: Rc<HashMap<SocketAddr, DateTime<Utc>>>,
+    ) -> Self {
         Self {
             criteria,
             region_order: Rc::new(region_order),
+            last_visits,
         }
     }
 
-    fn region_comparator(&self) -> RowComparator<Server> {
+    fn key_comparator(&self, key: SortKey, ascending: bool) -> RowComparator<Server> {
+        match key {
+            SortKey::Name => Box::new(cmp_values!(ascending, name)),
+            SortKey::Map => Box::new(cmp_values!(ascending, map)),
+            SortKey::Mode => Box::new(cmp_values!(ascending, mode())),
+            SortKey::Region => self.region_comparator(ascending),
+            SortKey::Players => Box::new({
+                let connected_cmp = cmp_options!(ascending, connected_players);
+                let max_cmp = cmp_values!(ascending, max_players);
+                move |lhs: &Server, rhs: &Server| {
+                    connected_cmp(lhs, rhs).then_with(|| max_cmp(lhs, rhs))
+                }
+            }),
+            SortKey::Age => Box::new(cmp_options!(ascending, age)),
+            SortKey::Ping => Box::new(cmp_options!(ascending, ping)),
+            SortKey::LastVisited => self.last_visited_comparator(ascending),
+            SortKey::Health => Box::new(cmp_options!(ascending, health_score())),
+        }
+    }
+
+    fn region_comparator(&self, ascending: bool) -> RowComparator<Server> {
         let region_order = Rc::clone(&self.region_order);
-        if self.criteria.ascending {
+        if ascending {
             Box::new(move |lhs: &Server, rhs: &Server| {
                 region_order[&lhs.region].cmp(&region_order[&rhs.region])
             })
@@ -65,29 +94,51 @@ impl SortOrder {
             })
         }
     }
+
+    // Ascending order puts the most recent visit first, and unvisited servers always go last
+    fn last_visited_comparator(&self, ascending: bool) -> RowComparator<Server> {
+        let last_visits = Rc::clone(&self.last_visits);
+        Box::new(move |lhs: &Server, rhs: &Server| {
+            let lhs_visit = lhs.game_addr().and_then(|addr| last_visits.get(&addr));
+            let rhs_visit = rhs.game_addr().and_then(|addr| last_visits.get(&addr));
+            match (lhs_visit, rhs_visit) {
+                (Some(lv), Some(rv)) => {
+                    if ascending {
+                        rv.cmp(lv)
+                    } else {
+                        lv.cmp(rv)
+                    }
+                }
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        })
+    }
 }
 
 impl RowOrder<Server> for SortOrder {
     fn comparator(&self) -> RowComparator<Server> {
-        let cmp: RowComparator<Server> = match self.criteria.key {
-            SortKey::Name => Box::new(cmp_values!(self.criteria.ascending, name)),
-            SortKey::Map => Box::new(cmp_values!(self.criteria.ascending, map)),
-            SortKey::Mode => Box::new(cmp_values!(self.criteria.ascending, mode())),
-            SortKey::Region => self.region_comparator(),
-            SortKey::Players => Box::new({
-                let connected_cmp = cmp_options!(self.criteria.ascending, connected_players);
-                let max_cmp = cmp_values!(self.criteria.ascending, max_players);
-                move |lhs: &Server, rhs: &Server| {
-                    connected_cmp(lhs, rhs).then_with(|| max_cmp(lhs, rhs))
-                }
-            }),
-            SortKey::Age => Box::new(cmp_options!(self.criteria.ascending, age)),
-            SortKey::Ping => Box::new(cmp_options!(self.criteria.ascending, ping)),
-        };
+        let cmp = self.key_comparator(self.criteria.key, self.criteria.ascending);
+
+        // The secondary key always sorts in its natural "best first" order (lowest ping, most
+        // players), so the list doesn't reshuffle as pongs arrive for otherwise equal servers.
+        let secondary_cmp = self
+            .criteria
+            .secondary_key
+            .map(|key| self.key_comparator(key, key != SortKey::Players));
+        let region_cmp = self.region_comparator(true);
         let tie_breaker = cmp_values!(self.criteria.ascending, id);
         Box::new(move |lhs: &Server, rhs: &Server| {
-            lhs.preference(&rhs)
-                .then_with(|| cmp(lhs, rhs).then_with(|| tie_breaker(lhs, rhs)))
+            lhs.preference(&rhs).then_with(|| {
+                cmp(lhs, rhs)
+                    .then_with(|| match &secondary_cmp {
+                        Some(secondary_cmp) => secondary_cmp(lhs, rhs),
+                        None => Ordering::Equal,
+                    })
+                    .then_with(|| region_cmp(lhs, rhs))
+                    .then_with(|| tie_breaker(lhs, rhs))
+            })
         })
     }
 }
@@ -0,0 +1,186 @@
+use std::net::Ipv4Addr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::time::Duration;
+
+use fltk::app;
+use fltk::button::Button;
+use fltk::frame::Frame;
+use fltk::group::Group;
+use fltk::prelude::*;
+use fltk::window::Window;
+use fltk_float::grid::{CellAlign, Grid};
+use fltk_float::{LayoutElement, SimpleWrapper};
+use slog::{error, Logger};
+
+use crate::gui::widgets::{DataTable, DataTableProperties, DataTableUpdate};
+use crate::gui::wrapper_factory;
+use crate::net::{trace_route, Hop};
+
+pub struct TraceRouteDialog {
+    logger: Logger,
+    window: Window,
+    hops_table: DataTable<Vec<String>>,
+    status_text: Frame,
+    dest: Ipv4Addr,
+}
+
+enum TraceEvent {
+    Hop(Hop),
+    Done(std::io::Result<()>),
+}
+
+impl TraceRouteDialog {
+    pub fn new(logger: &Logger, parent: &Group, dest: Ipv4Addr) -> Self {
+        let mut window = Window::default()
+            .with_size(480, 360)
+            .with_label("Network Diagnostics");
+
+        let mut root = Grid::builder_with_factory(wrapper_factory())
+            .with_col_spacing(10)
+            .with_row_spacing(10)
+            .with_padding(10, 10, 10, 10);
+        root.col().with_stretch(1).add();
+        root.col().add();
+
+        root.row().with_stretch(1).add();
+        let mut hops_table = DataTable::default().with_properties(DataTableProperties {
+            columns: vec![
+                ("Hop", 50).into(),
+                "IP".into(),
+                ("Latency (ms)", 100).into(),
+            ],
+            cell_selection_color: fltk::enums::Color::Free,
+            header_font_color: fltk::enums::Color::Gray0,
+            ..Default::default()
+        });
+        hops_table.set_col_header(true);
+        hops_table.set_row_header(false);
+        hops_table.set_flex_col(1);
+        hops_table.end();
+        root.span(1, 2)
+            .unwrap()
+            .with_vert_align(CellAlign::Stretch)
+            .add(SimpleWrapper::new(
+                hops_table.as_base_widget(),
+                Default::default(),
+            ));
+
+        root.row().add();
+        let status_text = root
+            .cell()
+            .unwrap()
+            .wrap(Frame::default())
+            .with_label(&format!("Tracing route to {}...", dest))
+            .with_align(fltk::enums::Align::Left | fltk::enums::Align::Inside);
+        let mut close_button = root
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Close");
+
+        let root = root.end();
+        root.group().resize(0, 0, window.w(), window.h());
+        root.layout_children();
+        window.end();
+
+        window.set_pos(
+            parent.x() + (parent.w() - window.w()) / 2,
+            parent.y() + (parent.h() - window.h()) / 2,
+        );
+
+        close_button.set_callback({
+            let mut window = window.clone();
+            move |_| window.hide()
+        });
+
+        Self {
+            logger: logger.clone(),
+            window,
+            hops_table,
+            status_text,
+            dest,
+        }
+    }
+
+    pub fn run(&self) {
+        let mut window = self.window.clone();
+        window.make_modal(true);
+        window.show();
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+        {
+            let dest = self.dest;
+            let cancelled = Arc::clone(&cancelled);
+            std::thread::spawn(move || {
+                let result = trace_route(dest, MAX_HOPS, HOP_TIMEOUT, |hop| {
+                    tx.send(TraceEvent::Hop(hop)).is_ok() && !cancelled.load(Ordering::Relaxed)
+                });
+                tx.send(TraceEvent::Done(result)).ok();
+            });
+        }
+
+        let mut done = false;
+        while window.shown() && !app::should_program_quit() {
+            if !done {
+                done = self.process_events(&rx);
+            }
+            app::wait_for(0.1).ok();
+        }
+
+        cancelled.store(true, Ordering::Relaxed);
+    }
+
+    fn process_events(&self, rx: &Receiver<TraceEvent>) -> bool {
+        loop {
+            match rx.try_recv() {
+                Ok(TraceEvent::Hop(hop)) => self.add_hop(hop),
+                Ok(TraceEvent::Done(result)) => {
+                    let status = match result {
+                        Ok(()) => format!("Finished tracing route to {}", self.dest),
+                        Err(err) => {
+                            error!(
+                                self.logger,
+                                "Error tracing route";
+                                "dest" => %self.dest,
+                                "error" => %err,
+                            );
+                            format!("{} {}", ERR_TRACING_ROUTE, err)
+                        }
+                    };
+                    let mut status_text = self.status_text.clone();
+                    status_text.set_label(&status);
+                    status_text.redraw();
+                    return true;
+                }
+                Err(TryRecvError::Empty) => return false,
+                Err(TryRecvError::Disconnected) => return true,
+            }
+        }
+    }
+
+    fn add_hop(&self, hop: Hop) {
+        let row = vec![
+            hop.ttl.to_string(),
+            hop.addr
+                .map(|addr| addr.to_string())
+                .unwrap_or_else(|| "*".to_string()),
+            hop.latency
+                .map(|latency| latency.as_millis().to_string())
+                .unwrap_or_else(|| "*".to_string()),
+        ];
+        {
+            let data = self.hops_table.data();
+            let mut data = data.borrow_mut();
+            data.push(row);
+        }
+        self.hops_table.updated(DataTableUpdate::DATA);
+    }
+}
+
+const MAX_HOPS: u8 = 30;
+const HOP_TIMEOUT: Duration = Duration::from_secs(2);
+
+const ERR_TRACING_ROUTE: &str = "Error while tracing the route:";
@@ -20,6 +20,8 @@ use fltk_float::{LayoutElement, SimpleWrapper};
 use slog::{error, warn, Logger};
 
 use crate::bus::AppBus;
+use crate::config::ConfigManager;
+use crate::env;
 use crate::game::settings::server::{Preset, ServerSettings};
 use crate::game::{Game, GameDB};
 use crate::launcher::Launcher;
@@ -30,7 +32,7 @@ use super::data::{IterableTableSource, Reindex, RowComparator, RowFilter, RowOrd
 use super::prelude::*;
 use super::server_settings::dialog::ServerSettingsDialog;
 use super::widgets::{DataTable, DataTableProperties, DataTableUpdate};
-use super::{alert_error, is_table_nav_event, prompt_confirm, wrapper_factory};
+use super::{alert_error, glyph, is_table_nav_event, prompt_confirm, wrapper_factory};
 
 #[derive(dynabus::Event)]
 pub struct PopulateSinglePlayerGames(pub Result<Vec<GameDB>>);
@@ -76,6 +78,7 @@ impl SinglePlayerState {
 pub struct SinglePlayerTab {
     logger: Logger,
     game: Arc<Game>,
+    config: Rc<ConfigManager>,
     launcher: Rc<Launcher>,
     saves: Rc<SavedGamesManager>,
     grid: Grid,
@@ -83,7 +86,9 @@ pub struct SinglePlayerTab {
     in_progress_table: DataTable<Vec<String>>,
     backups_table: DataTable<Vec<String>>,
     continue_button: Button,
+    dlc_warning: Frame,
     load_button: Button,
+    load_and_launch_button: Button,
     save_button: Button,
     save_as_button: Button,
     export_button: Button,
@@ -96,6 +101,7 @@ impl SinglePlayerTab {
         logger: &Logger,
         bus: Rc<RefCell<AppBus>>,
         game: Arc<Game>,
+        config: Rc<ConfigManager>,
         launcher: Rc<Launcher>,
         saves: Rc<SavedGamesManager>,
     ) -> Rc<Self> {
@@ -112,14 +118,29 @@ impl SinglePlayerTab {
             .unwrap()
             .wrap(Frame::default())
             .with_label("Map:");
-        let mut map_input = grid.cell().unwrap().wrap(InputChoice::default_fill());
+        let mut map_grid = Grid::builder_with_factory(wrapper_factory()).with_col_spacing(5);
+        map_grid.row().add();
+        map_grid.col().with_stretch(1).add();
+        map_grid.col().add();
+        let mut map_input = map_grid.cell().unwrap().wrap(InputChoice::default_fill());
         for map in game.maps().iter() {
             map_input.add(&map.display_name);
         }
         map_input.input().set_readonly(true);
         map_input.input().clear_visible_focus();
-        map_input.set_value_index(0);
-        let selected_map_id = game.maps().iter().next().unwrap().id;
+        let selected_map_id = config
+            .get()
+            .last_used_sp_map_id
+            .filter(|&map_id| map_id < game.maps().iter().count())
+            .unwrap_or_else(|| game.maps().iter().next().unwrap().id);
+        map_input.set_value_index(selected_map_id as _);
+        let mut dlc_warning = map_grid
+            .cell()
+            .unwrap()
+            .wrap(Frame::default().with_size(20, 20))
+            .with_label(glyph::ERROR);
+        dlc_warning.hide();
+        grid.cell().unwrap().add(map_grid.end());
         let mut new_button = grid
             .cell()
             .unwrap()
@@ -155,18 +176,18 @@ impl SinglePlayerTab {
             ));
         grid.cell().unwrap().skip();
 
-        grid.row().batch(5);
+        grid.row().batch(7);
         grid.row()
             .with_default_align(CellAlign::Start)
             .with_stretch(9)
             .add();
-        grid.span(6, 1)
+        grid.span(8, 1)
             .unwrap()
             .with_vert_align(CellAlign::Start)
             .wrap(Frame::default())
             .with_label("Backups:");
         let mut backups_table = make_db_list();
-        grid.span(6, 3)
+        grid.span(8, 3)
             .unwrap()
             .with_vert_align(CellAlign::Stretch)
             .add(SimpleWrapper::new(
@@ -180,6 +201,14 @@ impl SinglePlayerTab {
             .wrap(Button::default())
             .with_label("Load")
             .with_tooltip("Replace the current singleplayer game with the selected backup");
+        let mut load_and_launch_button = grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Load && Launch")
+            .with_tooltip(
+                "Replace the current singleplayer game with the selected backup and continue it",
+            );
         let mut save_button = grid
             .cell()
             .unwrap()
@@ -210,6 +239,12 @@ impl SinglePlayerTab {
             .wrap(Button::default())
             .with_label("Delete")
             .with_tooltip("Delete the selected backup");
+        let mut open_folder_button = grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Open Folder")
+            .with_tooltip("Open the folder with the saved games");
 
         let grid = grid.end();
         grid.layout_children();
@@ -220,6 +255,7 @@ impl SinglePlayerTab {
         let this = Rc::new(Self {
             logger: logger.clone(),
             game,
+            config,
             launcher,
             saves,
             grid,
@@ -227,7 +263,9 @@ impl SinglePlayerTab {
             in_progress_table,
             backups_table: backups_table.clone(),
             continue_button: continue_button.clone(),
+            dlc_warning,
             load_button: load_button.clone(),
+            load_and_launch_button: load_and_launch_button.clone(),
             save_button: save_button.clone(),
             save_as_button: save_as_button.clone(),
             export_button: export_button.clone(),
@@ -288,11 +326,13 @@ impl SinglePlayerTab {
         );
         continue_button.set_callback(weak_cb!([this] => |_| this.continue_clicked()));
         load_button.set_callback(weak_cb!([this] => |_| this.load_clicked()));
+        load_and_launch_button.set_callback(weak_cb!([this] => |_| this.load_and_launch_clicked()));
         save_button.set_callback(weak_cb!([this] => |_| this.save_clicked()));
         save_as_button.set_callback(weak_cb!([this] => |_| this.save_as_clicked()));
         import_button.set_callback(weak_cb!([this] => |_| this.import_clicked()));
         export_button.set_callback(weak_cb!([this] => |_| this.export_clicked()));
         delete_button.set_callback(weak_cb!([this] => |_| this.delete_clicked()));
+        open_folder_button.set_callback(weak_cb!([this] => |_| this.open_folder_clicked()));
         settings_button.set_callback(weak_cb!([this] => |_| this.settings_clicked()));
 
         {
@@ -310,6 +350,7 @@ impl SinglePlayerTab {
     }
 
     fn on_show(&self) {
+        self.update_dlc_warning();
         self.saves.list_games();
     }
 
@@ -353,9 +394,26 @@ impl SinglePlayerTab {
             state.backups.update_filter(|filter| filter.map_id = map_id);
         }
 
+        self.update_dlc_warning();
         self.populate_list();
     }
 
+    fn update_dlc_warning(&self) {
+        let map_id = self.state.borrow().filter().map_id;
+        let missing_dlc = self
+            .game
+            .map_dlc(map_id)
+            .filter(|dlc| self.game.dlc_owned(dlc.app_id) == Some(false));
+        let mut dlc_warning = self.dlc_warning.clone();
+        match missing_dlc {
+            Some(dlc) => {
+                dlc_warning.set_tooltip(&format!("{} DLC not owned.", dlc.name));
+                dlc_warning.show();
+            }
+            None => dlc_warning.hide(),
+        }
+    }
+
     fn backup_clicked(&self) {
         if let TableContext::Cell = self.backups_table.callback_context() {
             let _ = self.backups_table.clone().take_focus();
@@ -398,6 +456,7 @@ impl SinglePlayerTab {
             alert_error(ERR_LAUNCHING_SP, &err);
             return;
         }
+        self.remember_map(map_id);
 
         {
             let mut state = self.state.borrow_mut();
@@ -411,16 +470,99 @@ impl SinglePlayerTab {
         if let Err(err) = self.launcher.continue_singleplayer_game(map_id) {
             error!(self.logger, "Error launching singleplayer game"; "error" => %err);
             alert_error(ERR_LAUNCHING_SP, &err);
+            return;
+        }
+        self.remember_map(map_id);
+    }
+
+    fn remember_map(&self, map_id: usize) {
+        if self.config.get().last_used_sp_map_id != Some(map_id) {
+            self.config
+                .update(|config| config.last_used_sp_map_id = Some(map_id));
         }
     }
 
     fn load_clicked(&self) {
         let state = self.state.borrow();
-        let backup_idx = state.selected_backup_idx.unwrap();
         let map_id = state.filter().map_id;
-        if state.in_progress.contains_key(&map_id) && !prompt_confirm(PROMPT_REPLACE_IN_PROGRESS) {
+        let in_progress_exists = state.in_progress.contains_key(&map_id);
+        drop(state);
+
+        if in_progress_exists
+            && !prompt_confirm(&format!(
+                "{}{}",
+                PROMPT_REPLACE_IN_PROGRESS,
+                self.backup_mod_diff_text()
+            ))
+        {
             return;
         }
+
+        self.load_selected_backup();
+    }
+
+    fn load_and_launch_clicked(&self) {
+        let map_id = self.state.borrow().filter().map_id;
+        let map_name = &self.game.maps()[map_id].display_name;
+        if !prompt_confirm(&format!(
+            "This will overwrite your current in-progress game for {}. Continue?{}",
+            map_name,
+            self.backup_mod_diff_text()
+        )) {
+            return;
+        }
+
+        if self.load_selected_backup() {
+            self.continue_clicked();
+        }
+    }
+
+    fn backup_mod_diff_text(&self) -> String {
+        let state = self.state.borrow();
+        let backup_idx = state.selected_backup_idx.unwrap();
+        let map_id = state.filter().map_id;
+        let backup_name = state.backups[backup_idx].file_name.clone();
+        let in_progress_exists = state.in_progress.contains_key(&map_id);
+        drop(state);
+        if !in_progress_exists {
+            return String::new();
+        }
+
+        let metadata = self
+            .saves
+            .save_metadata(SaveGame::InProgress { map_id })
+            .and_then(|in_progress| {
+                let backup = self
+                    .saves
+                    .save_metadata(SaveGame::Backup { name: backup_name })?;
+                Ok((in_progress, backup))
+            });
+        let (in_progress, backup) = match metadata {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                warn!(self.logger, "Error comparing the mods in saved games"; "error" => %err);
+                return String::new();
+            }
+        };
+
+        let diff = self.saves.compute_mod_diff(&in_progress, &backup);
+        if diff.is_empty() {
+            return String::new();
+        }
+        let mut text = format!("\n\n{}", TXT_MOD_DIFF);
+        for name in diff.added.iter() {
+            text.push_str(&format!("\n+ {}", name));
+        }
+        for name in diff.removed.iter() {
+            text.push_str(&format!("\n- {}", name));
+        }
+        text
+    }
+
+    fn load_selected_backup(&self) -> bool {
+        let state = self.state.borrow();
+        let backup_idx = state.selected_backup_idx.unwrap();
+        let map_id = state.filter().map_id;
         let backup_name = state.backups[backup_idx].file_name.clone();
         drop(state);
 
@@ -429,7 +571,7 @@ impl SinglePlayerTab {
         if let Err(err) = self.saves.copy_save(src, dest) {
             error!(self.logger, "Error loading singleplayer backup"; "error" => %err);
             alert_error(ERR_LOADING_GAME, &err);
-            return;
+            return false;
         }
 
         {
@@ -439,10 +581,11 @@ impl SinglePlayerTab {
             state.in_progress.insert(map_id, new_in_progress);
         }
         self.populate_list();
+        true
     }
 
     fn save_clicked(&self) {
-        if !prompt_confirm(PROMPT_REPLACE_BACKUP) {
+        if !prompt_confirm(PROMPT_REPLACE_BACKUP) || !self.saves.confirm_free_space() {
             return;
         }
 
@@ -503,6 +646,10 @@ impl SinglePlayerTab {
 
         drop(state);
 
+        if !self.saves.confirm_free_space() {
+            return;
+        }
+
         let src = SaveGame::InProgress { map_id };
         let dest = SaveGame::Backup {
             name: backup_name.clone(),
@@ -545,6 +692,9 @@ impl SinglePlayerTab {
             Some(name) => name.into(),
             None => return,
         };
+        if !self.saves.confirm_free_space() {
+            return;
+        }
 
         let src = SaveGame::External { path };
         let dest = SaveGame::Backup { name: backup_name };
@@ -593,6 +743,19 @@ impl SinglePlayerTab {
         }
     }
 
+    fn open_folder_clicked(&self) {
+        let save_path = self.game.save_path();
+        if let Err(err) = env::open_folder(save_path) {
+            warn!(
+                self.logger,
+                "Error opening the save folder";
+                "path" => save_path.display(),
+                "error" => %err,
+            );
+            dialog::input_default(ERR_OPENING_SAVE_FOLDER, &save_path.to_string_lossy());
+        }
+    }
+
     fn delete_clicked(&self) {
         if !prompt_confirm(PROMPT_DELETE_BACKUP) {
             return;
@@ -675,6 +838,9 @@ impl SinglePlayerTab {
             .clone()
             .set_activated(in_progress_exists);
         self.load_button.clone().set_activated(backup_selected);
+        self.load_and_launch_button
+            .clone()
+            .set_activated(in_progress_exists && backup_selected);
         self.save_button
             .clone()
             .set_activated(in_progress_exists && backup_selected);
@@ -714,6 +880,8 @@ const ERR_LOADING_GAME: &str = "Error while loading a saved game.";
 const ERR_SAVING_GAME: &str = "Error while saving the in-progress game.";
 const ERR_EXPORTING_GAME: &str = "Error while exporting the backup.";
 const ERR_DELETING_GAME: &str = "Error while deleting a saved game.";
+const ERR_OPENING_SAVE_FOLDER: &str =
+    "Could not open the save folder. You can copy its location from below.";
 const ERR_INVALID_BACKUP_NAME: &str =
     "Invalid backup name. Please use a non-empty filename without a path.";
 const ERR_PREFIX_INVALID_NAME: &str = "Invalid filename";
@@ -721,6 +889,7 @@ const ERR_LOADING_SETTINGS: &str = "Error while loading the game settings.";
 const ERR_SAVING_SETTINGS: &str = "Error while saving the game settings.";
 
 const PROMPT_REPLACE_IN_PROGRESS: &str = "Are you sure you want to overwrite the in-progress game?";
+const TXT_MOD_DIFF: &str = "Mods changed since the backup was created:";
 const PROMPT_REPLACE_BACKUP: &str = "Are you sure you want to overwrite this backup?";
 const PROMPT_BACKUP_NAME: &str = "Backup name:";
 const PROMPT_DELETE_BACKUP: &str = "Are you sure you want to delete this backup?";
@@ -6,6 +6,10 @@ use lazy_static::lazy_static;
 
 use crate::config::ThemeChoice;
 
+mod preview_dialog;
+
+pub use self::preview_dialog::ThemePreviewDialog;
+
 pub struct Theme {
     colors: HashMap<u8, (u8, u8, u8)>,
 }
@@ -21,6 +25,20 @@ impl Theme {
         match theme {
             ThemeChoice::Light => &LIGHT_THEME,
             ThemeChoice::Dark => &DARK_THEME,
+            ThemeChoice::System => {
+                if system_prefers_dark() {
+                    &DARK_THEME
+                } else {
+                    &LIGHT_THEME
+                }
+            }
+        }
+    }
+
+    pub fn color(&self, index: u8) -> Color {
+        match self.colors.get(&index) {
+            Some(&(r, g, b)) => Color::from_rgb(r, g, b),
+            None => Color::by_index(index),
         }
     }
 
@@ -36,6 +54,46 @@ impl Theme {
     }
 }
 
+#[cfg(windows)]
+fn system_prefers_dark() -> bool {
+    use std::ptr::null_mut;
+
+    use winapi::shared::minwindef::DWORD;
+    use winapi::shared::winerror::ERROR_SUCCESS;
+    use winapi::um::winreg::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD};
+
+    let key: Vec<u16> = PERSONALIZE_KEY.encode_utf16().chain(Some(0)).collect();
+    let value: Vec<u16> = APPS_USE_LIGHT_THEME.encode_utf16().chain(Some(0)).collect();
+    let mut data: DWORD = 0;
+    let mut size = std::mem::size_of::<DWORD>() as DWORD;
+    let result = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            key.as_ptr(),
+            value.as_ptr(),
+            RRF_RT_REG_DWORD,
+            null_mut(),
+            &mut data as *mut DWORD as *mut _,
+            &mut size,
+        )
+    };
+
+    // Windows versions without app theme support have no such value, and are always light
+    result == ERROR_SUCCESS as i32 && data == 0
+}
+
+#[cfg(not(windows))]
+fn system_prefers_dark() -> bool {
+    std::env::var("GTK_THEME")
+        .map(|theme| theme.to_lowercase().contains("dark"))
+        .unwrap_or_default()
+}
+
+#[cfg(windows)]
+const PERSONALIZE_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Themes\Personalize";
+#[cfg(windows)]
+const APPS_USE_LIGHT_THEME: &str = "AppsUseLightTheme";
+
 lazy_static! {
     static ref LIGHT_THEME: Theme = {
         let mut theme = Theme::new();
@@ -0,0 +1,230 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use fltk::button::{Button, RadioRoundButton, ReturnButton};
+use fltk::draw;
+use fltk::enums::{Align, Font};
+use fltk::frame::Frame;
+use fltk::prelude::*;
+use fltk::window::Window;
+use fltk_float::grid::{CellAlign, Grid};
+use fltk_float::SimpleWrapper;
+
+use crate::config::ThemeChoice;
+use crate::gui::wrapper_factory;
+
+use super::{system_prefers_dark, Theme};
+
+pub struct ThemePreviewDialog {
+    window: Window,
+    result: Rc<Cell<Option<ThemeChoice>>>,
+}
+
+impl ThemePreviewDialog {
+    pub fn new(parent: &impl WindowExt, current: ThemeChoice) -> Self {
+        let mut window = Window::default()
+            .with_size(720, 260)
+            .with_label("Theme Preview");
+
+        let mut root = Grid::builder_with_factory(wrapper_factory())
+            .with_col_spacing(10)
+            .with_row_spacing(10)
+            .with_padding(10, 10, 10, 10);
+        let col_group = root.col_group().with_stretch(1).add();
+        root.extend_group(col_group).batch(THEME_CHOICES.len());
+
+        root.row().add();
+        let mut choice_buttons = Vec::with_capacity(THEME_CHOICES.len());
+        for &choice in THEME_CHOICES {
+            let mut button = root
+                .cell()
+                .unwrap()
+                .wrap(RadioRoundButton::default())
+                .with_label(&choice_label(choice));
+            button.set_value(choice == current);
+            choice_buttons.push(button);
+        }
+
+        root.row()
+            .with_stretch(1)
+            .with_default_align(CellAlign::Stretch)
+            .add();
+        for &choice in THEME_CHOICES {
+            let mut mockup = Frame::default();
+            let theme = Theme::from_config(choice);
+            mockup
+                .draw(move |frame| draw_mockup(theme, frame.x(), frame.y(), frame.w(), frame.h()));
+            root.cell()
+                .unwrap()
+                .add(SimpleWrapper::new(mockup, Default::default()));
+        }
+
+        root.row().add();
+        root.span(1, THEME_CHOICES.len() - 1).unwrap().skip();
+        let mut btn_grid = Grid::builder_with_factory(wrapper_factory()).with_col_spacing(10);
+        btn_grid.row().add();
+        let btn_group = btn_grid.col_group().add();
+        btn_grid.extend_group(btn_group).batch(2);
+        let mut ok_button = btn_grid
+            .cell()
+            .unwrap()
+            .wrap(ReturnButton::default())
+            .with_label("Use Theme");
+        let mut cancel_button = btn_grid
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("Cancel");
+        root.cell()
+            .unwrap()
+            .with_horz_align(CellAlign::End)
+            .add(btn_grid.end());
+
+        let root = root.end();
+        root.group().resize(0, 0, window.w(), window.h());
+        root.layout_children();
+        window.end();
+
+        window.set_pos(
+            parent.x() + (parent.w() - window.w()) / 2,
+            parent.y() + (parent.h() - window.h()) / 2,
+        );
+
+        let result = Rc::new(Cell::new(None));
+
+        ok_button.set_callback({
+            let result = Rc::clone(&result);
+            let mut window = window.clone();
+            move |_| {
+                let selected = choice_buttons
+                    .iter()
+                    .position(|button| button.is_toggled())
+                    .map(|idx| THEME_CHOICES[idx]);
+                result.set(selected);
+                window.hide();
+            }
+        });
+        cancel_button.set_callback({
+            let mut window = window.clone();
+            move |_| window.hide()
+        });
+
+        Self { window, result }
+    }
+
+    pub fn run(&self) -> Option<ThemeChoice> {
+        let mut window = self.window.clone();
+        window.make_modal(true);
+        window.show();
+
+        while window.shown() && !fltk::app::should_program_quit() {
+            fltk::app::wait();
+        }
+
+        self.result.take()
+    }
+}
+
+fn choice_label(choice: ThemeChoice) -> String {
+    match choice {
+        ThemeChoice::Light => "Light".to_string(),
+        ThemeChoice::Dark => "Dark".to_string(),
+        ThemeChoice::System => format!(
+            "System (currently {})",
+            if system_prefers_dark() { "Dark" } else { "Light" }
+        ),
+    }
+}
+
+// The mockup is drawn with explicit RGB colors, since the palette indices only ever reflect the
+// theme that is currently applied
+fn draw_mockup(theme: &Theme, x: i32, y: i32, w: i32, h: i32) {
+    let foreground = theme.color(FOREGROUND_COLOR);
+    let background = theme.color(BACKGROUND_COLOR);
+    let background2 = theme.color(BACKGROUND2_COLOR);
+    let border = theme.color(INACTIVE_COLOR);
+    let selection = theme.color(SELECTION_COLOR);
+
+    draw::draw_rect_fill(x, y, w, h, background);
+    draw::draw_rect_with_color(x, y, w, h, border);
+    draw::set_font(Font::Helvetica, 12);
+
+    let half = (w - 3 * MOCKUP_PADDING) / 2;
+    let mut cur_y = y + MOCKUP_PADDING;
+
+    let button_x = x + MOCKUP_PADDING;
+    draw::draw_rect_fill(
+        button_x,
+        cur_y,
+        half,
+        MOCKUP_ROW_HEIGHT,
+        theme.color(BUTTON_COLOR),
+    );
+    draw::draw_rect_with_color(button_x, cur_y, half, MOCKUP_ROW_HEIGHT, border);
+    draw::set_draw_color(foreground);
+    draw::draw_text2(
+        "Button",
+        button_x,
+        cur_y,
+        half,
+        MOCKUP_ROW_HEIGHT,
+        Align::Center,
+    );
+
+    let input_x = button_x + half + MOCKUP_PADDING;
+    draw::draw_rect_fill(input_x, cur_y, half, MOCKUP_ROW_HEIGHT, background2);
+    draw::draw_rect_with_color(input_x, cur_y, half, MOCKUP_ROW_HEIGHT, border);
+    draw::set_draw_color(foreground);
+    draw::draw_text2(
+        "Text input",
+        input_x + 4,
+        cur_y,
+        half - 8,
+        MOCKUP_ROW_HEIGHT,
+        Align::Left | Align::Inside,
+    );
+
+    cur_y += MOCKUP_ROW_HEIGHT + MOCKUP_PADDING;
+    let list_x = x + MOCKUP_PADDING;
+    let list_w = w - 2 * MOCKUP_PADDING;
+    let list_h = y + h - MOCKUP_PADDING - cur_y;
+    draw::draw_rect_fill(list_x, cur_y, list_w, list_h, background2);
+    draw::draw_rect_with_color(list_x, cur_y, list_w, list_h, border);
+
+    for (idx, &label) in ["Server list row", "Selected row", "Another row"]
+        .iter()
+        .enumerate()
+    {
+        let row_y = cur_y + 1 + idx as i32 * MOCKUP_ROW_HEIGHT;
+        if row_y + MOCKUP_ROW_HEIGHT > cur_y + list_h {
+            break;
+        }
+        let text_color = if idx == 1 {
+            draw::draw_rect_fill(list_x + 1, row_y, list_w - 2, MOCKUP_ROW_HEIGHT, selection);
+            background2
+        } else {
+            foreground
+        };
+        draw::set_draw_color(text_color);
+        draw::draw_text2(
+            label,
+            list_x + 4,
+            row_y,
+            list_w - 8,
+            MOCKUP_ROW_HEIGHT,
+            Align::Left | Align::Inside,
+        );
+    }
+}
+
+const THEME_CHOICES: &[ThemeChoice] = &[ThemeChoice::Light, ThemeChoice::Dark, ThemeChoice::System];
+
+const FOREGROUND_COLOR: u8 = 0;
+const BACKGROUND2_COLOR: u8 = 7;
+const INACTIVE_COLOR: u8 = 8;
+const SELECTION_COLOR: u8 = 15;
+const BACKGROUND_COLOR: u8 = 49;
+const BUTTON_COLOR: u8 = 52;
+
+const MOCKUP_PADDING: i32 = 10;
+const MOCKUP_ROW_HEIGHT: i32 = 24;
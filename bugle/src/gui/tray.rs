@@ -0,0 +1,272 @@
+use std::cell::RefCell;
+use std::sync::Arc;
+
+use anyhow::Result;
+use fltk::app;
+use fltk::prelude::*;
+use fltk::window::Window;
+use slog::{warn, Logger};
+
+use crate::game::Game;
+
+struct TrayState {
+    _icon: TrayIcon,
+    window: Window,
+}
+
+#[cfg(windows)]
+#[derive(Clone, Copy)]
+enum TrayAction {
+    Restore,
+    Quit,
+}
+
+thread_local! {
+    static TRAY_STATE: RefCell<Option<TrayState>> = RefCell::new(None);
+}
+
+pub fn tray_supported() -> bool {
+    cfg!(windows)
+}
+
+pub fn minimize_to_tray(logger: &Logger, game: Arc<Game>) -> Result<()> {
+    let Some(mut window) = app::first_window() else {
+        return Ok(());
+    };
+    let icon = TrayIcon::new(TIP_TRAY_ICON)?;
+    window.hide();
+    TRAY_STATE.with(|state| {
+        *state.borrow_mut() = Some(TrayState {
+            _icon: icon,
+            window,
+        })
+    });
+
+    let logger = logger.clone();
+    app::add_timeout3(GAME_POLL_INTERVAL, move |handle| {
+        if !is_minimized_to_tray() {
+            return;
+        }
+        match game.is_running() {
+            Ok(false) => restore_from_tray(),
+            Ok(true) => app::repeat_timeout3(GAME_POLL_INTERVAL, handle),
+            Err(err) => {
+                warn!(logger, "Error checking whether the game is running"; "error" => %err);
+                app::repeat_timeout3(GAME_POLL_INTERVAL, handle);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+pub fn is_minimized_to_tray() -> bool {
+    TRAY_STATE.with(|state| state.borrow().is_some())
+}
+
+pub fn restore_from_tray() {
+    if let Some(TrayState { mut window, .. }) = TRAY_STATE.with(|state| state.borrow_mut().take()) {
+        window.show();
+    }
+}
+
+#[cfg(windows)]
+fn perform_action(action: TrayAction) {
+    // Defer to the event loop, since the action tears down the tray icon whose window procedure
+    // is calling us
+    app::add_timeout3(0.0, move |_| match action {
+        TrayAction::Restore => restore_from_tray(),
+        TrayAction::Quit => {
+            TRAY_STATE.with(|state| state.borrow_mut().take());
+            app::quit();
+        }
+    });
+}
+
+#[cfg(windows)]
+struct TrayIcon {
+    hwnd: winapi::shared::windef::HWND,
+}
+
+#[cfg(windows)]
+impl TrayIcon {
+    fn new(tooltip: &str) -> Result<Self> {
+        use std::ptr::{null, null_mut};
+
+        use winapi::um::libloaderapi::GetModuleHandleW;
+        use winapi::um::shellapi::{Shell_NotifyIconW, NIF_ICON, NIF_MESSAGE, NIF_TIP, NIM_ADD};
+        use winapi::um::winuser::{
+            CreateWindowExW, DestroyWindow, LoadIconW, RegisterClassW, IDI_APPLICATION, WNDCLASSW,
+        };
+
+        let class_name = to_wide(TRAY_WINDOW_CLASS);
+        unsafe {
+            let instance = GetModuleHandleW(null());
+
+            let mut class: WNDCLASSW = std::mem::zeroed();
+            class.lpfnWndProc = Some(tray_window_proc);
+            class.hInstance = instance;
+            class.lpszClassName = class_name.as_ptr();
+
+            // Fails harmlessly if the class was registered by an earlier tray icon
+            RegisterClassW(&class);
+
+            let hwnd = CreateWindowExW(
+                0,
+                class_name.as_ptr(),
+                class_name.as_ptr(),
+                0,
+                0,
+                0,
+                0,
+                0,
+                null_mut(),
+                null_mut(),
+                instance,
+                null_mut(),
+            );
+            if hwnd.is_null() {
+                return Err(std::io::Error::last_os_error().into());
+            }
+
+            let mut data = notify_icon_data(hwnd);
+            data.uFlags = NIF_MESSAGE | NIF_ICON | NIF_TIP;
+            data.uCallbackMessage = WM_TRAY_ICON;
+            data.hIcon = LoadIconW(null_mut(), IDI_APPLICATION);
+            for (dest, src) in data.szTip[..data.szTip.len() - 1]
+                .iter_mut()
+                .zip(tooltip.encode_utf16())
+            {
+                *dest = src;
+            }
+
+            if Shell_NotifyIconW(NIM_ADD, &mut data) == 0 {
+                DestroyWindow(hwnd);
+                anyhow::bail!("Failed to add the system tray icon");
+            }
+
+            Ok(Self { hwnd })
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Drop for TrayIcon {
+    fn drop(&mut self) {
+        use winapi::um::shellapi::{Shell_NotifyIconW, NIM_DELETE};
+        use winapi::um::winuser::DestroyWindow;
+
+        unsafe {
+            let mut data = notify_icon_data(self.hwnd);
+            Shell_NotifyIconW(NIM_DELETE, &mut data);
+            DestroyWindow(self.hwnd);
+        }
+    }
+}
+
+#[cfg(windows)]
+fn notify_icon_data(hwnd: winapi::shared::windef::HWND) -> winapi::um::shellapi::NOTIFYICONDATAW {
+    use winapi::um::shellapi::NOTIFYICONDATAW;
+
+    let mut data: NOTIFYICONDATAW = unsafe { std::mem::zeroed() };
+    data.cbSize = std::mem::size_of::<NOTIFYICONDATAW>() as _;
+    data.hWnd = hwnd;
+    data.uID = TRAY_ICON_ID;
+    data
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn tray_window_proc(
+    hwnd: winapi::shared::windef::HWND,
+    msg: winapi::shared::minwindef::UINT,
+    wparam: winapi::shared::minwindef::WPARAM,
+    lparam: winapi::shared::minwindef::LPARAM,
+) -> winapi::shared::minwindef::LRESULT {
+    use winapi::um::winuser::{DefWindowProcW, WM_CONTEXTMENU, WM_LBUTTONDBLCLK, WM_RBUTTONUP};
+
+    if msg != WM_TRAY_ICON {
+        return DefWindowProcW(hwnd, msg, wparam, lparam);
+    }
+
+    match lparam as winapi::shared::minwindef::UINT {
+        WM_LBUTTONDBLCLK => perform_action(TrayAction::Restore),
+        WM_RBUTTONUP | WM_CONTEXTMENU => {
+            if let Some(action) = show_tray_menu(hwnd) {
+                perform_action(action);
+            }
+        }
+        _ => (),
+    }
+    0
+}
+
+#[cfg(windows)]
+unsafe fn show_tray_menu(hwnd: winapi::shared::windef::HWND) -> Option<TrayAction> {
+    use std::ptr::null;
+
+    use winapi::shared::windef::POINT;
+    use winapi::um::winuser::{
+        AppendMenuW, CreatePopupMenu, DestroyMenu, GetCursorPos, SetForegroundWindow,
+        TrackPopupMenu, MF_STRING, TPM_NONOTIFY, TPM_RETURNCMD,
+    };
+
+    let menu = CreatePopupMenu();
+    if menu.is_null() {
+        return None;
+    }
+    let restore_label = to_wide("Restore BUGLE");
+    let quit_label = to_wide("Quit");
+    AppendMenuW(menu, MF_STRING, CMD_RESTORE, restore_label.as_ptr());
+    AppendMenuW(menu, MF_STRING, CMD_QUIT, quit_label.as_ptr());
+
+    let mut pos: POINT = std::mem::zeroed();
+    GetCursorPos(&mut pos);
+
+    // Without this, the menu won't close when the user clicks elsewhere
+    SetForegroundWindow(hwnd);
+    let cmd = TrackPopupMenu(
+        menu,
+        TPM_RETURNCMD | TPM_NONOTIFY,
+        pos.x,
+        pos.y,
+        0,
+        hwnd,
+        null(),
+    );
+    DestroyMenu(menu);
+
+    match cmd as usize {
+        CMD_RESTORE => Some(TrayAction::Restore),
+        CMD_QUIT => Some(TrayAction::Quit),
+        _ => None,
+    }
+}
+
+#[cfg(windows)]
+fn to_wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+#[cfg(not(windows))]
+struct TrayIcon;
+
+#[cfg(not(windows))]
+impl TrayIcon {
+    fn new(_tooltip: &str) -> Result<Self> {
+        anyhow::bail!("System tray is not supported on this platform")
+    }
+}
+
+const TIP_TRAY_ICON: &str = "BUGLE";
+const GAME_POLL_INTERVAL: f64 = 5.0;
+
+#[cfg(windows)]
+const TRAY_WINDOW_CLASS: &str = "BugleTrayIcon";
+#[cfg(windows)]
+const TRAY_ICON_ID: u32 = 1;
+#[cfg(windows)]
+const WM_TRAY_ICON: u32 = winapi::um::winuser::WM_APP + 1;
+#[cfg(windows)]
+const CMD_RESTORE: usize = 1;
+#[cfg(windows)]
+const CMD_QUIT: usize = 2;
@@ -0,0 +1,128 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use fltk::button::{Button, CheckButton, ReturnButton};
+use fltk::prelude::*;
+use fltk::text::{TextBuffer, TextDisplay, WrapMode};
+use fltk::window::Window;
+use fltk_float::grid::{CellAlign, Grid};
+use fltk_float::{LayoutElement, SimpleWrapper};
+
+use crate::env;
+
+use super::{alert_error, wrapper_factory};
+
+pub struct WhatsNewDialog {
+    window: Window,
+    dont_show_again: Rc<Cell<bool>>,
+}
+
+impl WhatsNewDialog {
+    pub fn new(parent: &impl WindowExt, version: &str) -> Option<Self> {
+        let notes = changelog_for_version(version)?;
+
+        let mut window = Window::default()
+            .with_size(640, 480)
+            .with_label(&format!("What's New in BUGLE {}", version));
+
+        let mut root = Grid::builder_with_factory(wrapper_factory())
+            .with_col_spacing(10)
+            .with_row_spacing(10)
+            .with_padding(10, 10, 10, 10);
+        root.col().with_stretch(1).add();
+        let btn_group = root.col_group().add();
+        root.extend_group(btn_group).batch(2);
+
+        root.row().with_stretch(1).add();
+        let mut notes_display = TextDisplay::default();
+        root.span(1, 3)
+            .unwrap()
+            .with_vert_align(CellAlign::Stretch)
+            .add(SimpleWrapper::new(
+                notes_display.as_base_widget(),
+                Default::default(),
+            ));
+        let mut notes_buffer = TextBuffer::default();
+        notes_buffer.set_text(notes);
+        notes_display.set_buffer(notes_buffer);
+        notes_display.wrap_mode(WrapMode::AtBounds, 0);
+
+        root.row().add();
+        let dont_show_check = root
+            .cell()
+            .unwrap()
+            .with_horz_align(CellAlign::Start)
+            .wrap(CheckButton::default())
+            .with_label("Don't show again");
+        let mut changelog_button = root
+            .cell()
+            .unwrap()
+            .wrap(Button::default())
+            .with_label("View Full Changelog on GitHub")
+            .with_tooltip("Open the complete BUGLE changelog in your browser");
+        let mut ok_button = root
+            .cell()
+            .unwrap()
+            .wrap(ReturnButton::default())
+            .with_label("OK");
+
+        let root = root.end();
+        root.group().resize(0, 0, window.w(), window.h());
+        root.layout_children();
+        window.end();
+
+        window.set_pos(
+            parent.x() + (parent.w() - window.w()) / 2,
+            parent.y() + (parent.h() - window.h()) / 2,
+        );
+
+        let dont_show_again = Rc::new(Cell::new(false));
+
+        changelog_button.set_callback(|_| {
+            if let Err(err) = env::open_url(CHANGELOG_URL) {
+                alert_error(ERR_OPENING_CHANGELOG, &err);
+            }
+        });
+        ok_button.set_callback({
+            let dont_show_again = Rc::clone(&dont_show_again);
+            let mut window = window.clone();
+            move |_| {
+                dont_show_again.set(dont_show_check.is_checked());
+                window.hide();
+            }
+        });
+
+        Some(Self {
+            window,
+            dont_show_again,
+        })
+    }
+
+    pub fn run(&self) -> bool {
+        let mut window = self.window.clone();
+        window.make_modal(true);
+        window.show();
+
+        while window.shown() && !fltk::app::should_program_quit() {
+            fltk::app::wait();
+        }
+
+        self.dont_show_again.get()
+    }
+}
+
+fn changelog_for_version(version: &str) -> Option<&'static str> {
+    let heading = format!("## {} ", version);
+    let start = CHANGELOG.find(&heading)?;
+    let section = &CHANGELOG[start..];
+    let end = section[heading.len()..]
+        .find("\n## ")
+        .map(|idx| heading.len() + idx)
+        .unwrap_or(section.len());
+    Some(section[..end].trim())
+}
+
+const CHANGELOG: &str = include_str!("../../../CHANGELOG.md");
+const CHANGELOG_URL: &str = "https://github.com/vstojkovic/bugle/blob/main/CHANGELOG.md";
+
+const ERR_OPENING_CHANGELOG: &str = "Error while trying to open the changelog.";
@@ -18,7 +18,10 @@ pub use self::read_only_text::{ReadOnlyText, ReadOnlyTextElement};
 
 use super::is_table_nav_event;
 
-pub fn make_readonly_cell_widget<T: 'static>(table: &DataTable<T>) -> ReadOnlyText {
+pub fn make_readonly_cell_widget<T: 'static>(
+    table: &DataTable<T>,
+    copy_to_clipboard: bool,
+) -> ReadOnlyText {
     let mut cell = ReadOnlyText::new(String::new());
     cell.set_scrollbar_size(-1);
     cell.hide();
@@ -45,6 +48,9 @@ pub fn make_readonly_cell_widget<T: 'static>(table: &DataTable<T>) -> ReadOnlyTe
                         cell.resize(x, y, w, h);
                         let cell_value = table.cell_text(row, col);
                         let cell_value_len = cell_value.len();
+                        if copy_to_clipboard {
+                            app::copy(&cell_value);
+                        }
                         cell.set_value(cell_value.to_string());
                         cell.buffer().unwrap().select(0, cell_value_len as _);
                         cell.show();
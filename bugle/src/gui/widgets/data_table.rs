@@ -179,6 +179,14 @@ impl<T: 'static> DataTable<T> {
         inner.redraw();
     }
 
+    /// Sets the number of rows without materializing any data, for tables whose cells are drawn
+    /// from an external source by a custom draw function.
+    pub fn set_row_count(&self, rows: usize) {
+        let mut inner = self.inner.clone();
+        inner.set_rows(rows as _);
+        inner.redraw();
+    }
+
     pub fn set_flex_col(&mut self, mut flex_col: i32) {
         let mut flex_width = self.width();
 
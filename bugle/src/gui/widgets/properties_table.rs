@@ -19,7 +19,12 @@ pub struct PropertiesTable<S: 'static, C: 'static> {
 }
 
 impl<S: 'static, C: 'static> PropertiesTable<S, C> {
-    pub fn new(ctx: C, inspectors: &'static [Inspector<S, C>], title: &'static str) -> Self {
+    pub fn new(
+        ctx: C,
+        inspectors: &'static [Inspector<S, C>],
+        title: &'static str,
+        copy_to_clipboard: bool,
+    ) -> Self {
         let table_props = DataTableProperties {
             columns: vec![title.into()],
             cell_selection_color: fltk::enums::Color::Free,
@@ -35,7 +40,7 @@ impl<S: 'static, C: 'static> PropertiesTable<S, C> {
 
         table.end();
 
-        let cell = make_readonly_cell_widget(&table);
+        let cell = make_readonly_cell_widget(&table, copy_to_clipboard);
 
         let this = Self {
             ctx,
@@ -63,6 +68,22 @@ impl<S: 'static, C: 'static> PropertiesTable<S, C> {
         this
     }
 
+    pub fn ctx(&self) -> &C {
+        &self.ctx
+    }
+
+    pub fn find_row(&self, header: &str) -> Option<usize> {
+        let data = self.table.data();
+        let data = data.borrow();
+        data.iter().position(|row| row[0] == header)
+    }
+
+    pub fn value(&self, row: usize) -> Option<Cow<'static, str>> {
+        let data = self.table.data();
+        let data = data.borrow();
+        data.get(row).map(|row| row[1].clone())
+    }
+
     pub fn populate(&self, subject: Option<&S>) {
         self.cell.clone().hide();
         {
@@ -0,0 +1,123 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::game::Game;
+use crate::launcher::LaunchMode;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LaunchRecord {
+    pub timestamp: DateTime<Utc>,
+    pub mode: LaunchMode,
+    #[serde(default)]
+    pub server_addr: Option<SocketAddr>,
+    #[serde(default)]
+    pub map_id: Option<usize>,
+    pub mod_list_hash: String,
+    #[serde(default)]
+    pub mods: Vec<String>,
+    pub battleye: bool,
+    #[serde(default)]
+    pub exit_code: Option<i32>,
+}
+
+pub fn launch_history_path(game: &Game) -> PathBuf {
+    game.save_path().join(LAUNCH_HISTORY_FILENAME)
+}
+
+pub fn append_launch_record(path: &Path, record: &LaunchRecord) -> Result<()> {
+    {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+        file.write_all(line.as_bytes())?;
+    }
+
+    if std::fs::metadata(path)?.len() > MAX_HISTORY_BYTES {
+        prune_launch_history(path)?;
+    }
+    Ok(())
+}
+
+pub fn set_launch_exit_code(
+    path: &Path,
+    timestamp: DateTime<Utc>,
+    exit_code: Option<i32>,
+) -> Result<()> {
+    let mut records = load_launch_history(path)?;
+    let Some(record) = records
+        .iter_mut()
+        .rev()
+        .find(|record| record.timestamp == timestamp)
+    else {
+        return Ok(());
+    };
+    record.exit_code = exit_code;
+    write_launch_history(path, &records)
+}
+
+pub fn load_launch_history(path: &Path) -> Result<Vec<LaunchRecord>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut records = Vec::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        // Skip entries that can't be parsed, rather than losing the whole history
+        if let Ok(record) = serde_json::from_str(&line) {
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+
+pub fn visited_servers(history: &[LaunchRecord]) -> HashSet<IpAddr> {
+    history
+        .iter()
+        .filter_map(|record| record.server_addr)
+        .map(|addr| addr.ip())
+        .collect()
+}
+
+pub fn last_visits(history: &[LaunchRecord]) -> HashMap<SocketAddr, DateTime<Utc>> {
+    let mut visits = HashMap::new();
+    for record in history {
+        if let Some(addr) = record.server_addr {
+            let last = visits.entry(addr).or_insert(record.timestamp);
+            *last = (*last).max(record.timestamp);
+        }
+    }
+    visits
+}
+
+pub fn mod_list_hash(mods: &[String]) -> String {
+    format!("{:x}", md5::compute(mods.join("\n")))
+}
+
+fn prune_launch_history(path: &Path) -> Result<()> {
+    let records = load_launch_history(path)?;
+    write_launch_history(path, &records[(records.len() / 2)..])
+}
+
+fn write_launch_history(path: &Path, records: &[LaunchRecord]) -> Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for record in records {
+        serde_json::to_writer(&mut writer, record)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+const LAUNCH_HISTORY_FILENAME: &str = "launch_history.json";
+const MAX_HISTORY_BYTES: u64 = 1024 * 1024;
@@ -1,18 +1,24 @@
-use std::cell::{Cell, Ref};
+use std::cell::{Cell, Ref, RefCell};
 use std::net::SocketAddr;
 use std::rc::Rc;
 use std::sync::Arc;
 
 use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
 use fltk::app;
-use slog::{trace, Logger};
+use serde::{Deserialize, Serialize};
+use slog::{trace, warn, Logger};
 
 use crate::auth_manager::AuthManager;
+use crate::bus::AppBus;
 use crate::config::{BattlEyeUsage, ConfigManager};
 use crate::game::platform::steam::SteamClient;
 use crate::game::settings::server::ServerSettings;
-use crate::game::{Game, Launch, LaunchOptions, MapRef, ServerRef, Session};
-use crate::gui::Dialog;
+use crate::game::{Game, GameExit, Launch, LaunchOptions, MapRef, ServerRef, Session};
+use crate::gui::{minimize_to_tray, prompt_confirm, tray_supported, Dialog, UpdateLaunchStats};
+use crate::launch_history::{
+    append_launch_record, launch_history_path, mod_list_hash, set_launch_exit_code, LaunchRecord,
+};
 use crate::mod_manager::ModManager;
 use crate::saved_games_manager::SavedGamesManager;
 use crate::server_manager::ServerManager;
@@ -21,6 +27,7 @@ use crate::workers::TaskState;
 
 pub struct Launcher {
     logger: Logger,
+    bus: Rc<RefCell<AppBus>>,
     config: Rc<ConfigManager>,
     game: Arc<Game>,
     steam: Rc<SteamClient>,
@@ -30,6 +37,33 @@ pub struct Launcher {
     saves: Rc<SavedGamesManager>,
 }
 
+#[derive(dynabus::Event)]
+pub struct GameLaunched {
+    pub mode: LaunchMode,
+    pub map_id: Option<usize>,
+    pub server_addr: Option<SocketAddr>,
+}
+
+#[derive(dynabus::Event)]
+struct GameExited {
+    timestamp: DateTime<Utc>,
+    exit: GameExit,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum LaunchMode {
+    Game,
+    ContinueSession,
+    JoinServer,
+    SinglePlayer,
+}
+
+#[derive(Serialize)]
+struct SessionStats {
+    session_count: u32,
+    last_session_start: DateTime<Utc>,
+}
+
 pub struct ConnectionInfo {
     pub addr: SocketAddr,
     pub password: Option<String>,
@@ -39,6 +73,7 @@ pub struct ConnectionInfo {
 impl Launcher {
     pub fn new(
         logger: &Logger,
+        bus: Rc<RefCell<AppBus>>,
         config: Rc<ConfigManager>,
         game: Arc<Game>,
         steam: Rc<SteamClient>,
@@ -47,8 +82,9 @@ impl Launcher {
         mods: Rc<ModManager>,
         saves: Rc<SavedGamesManager>,
     ) -> Rc<Self> {
-        Rc::new(Self {
+        let this = Rc::new(Self {
             logger: logger.clone(),
+            bus,
             config,
             game,
             steam,
@@ -56,7 +92,13 @@ impl Launcher {
             servers,
             mods,
             saves,
-        })
+        });
+
+        this.bus.borrow_mut().subscribe_consumer(weak_cb!(
+            [this] => |GameExited { timestamp, exit }| this.game_exited(timestamp, exit)
+        ));
+
+        this
     }
 
     pub fn launch_game(&self) -> Result<()> {
@@ -82,8 +124,13 @@ impl Launcher {
             }
         };
         let launch_opts = self.launch_options(use_battleye);
-        if self.monitor_launch(self.game.launch(launch_opts, &[])?)? {
-            app::quit();
+        let record = self.launch_record(LaunchMode::Game, None, None, use_battleye);
+        if self.monitor_launch(self.game.launch(launch_opts, &[])?, record)? {
+            self.game_started(GameLaunched {
+                mode: LaunchMode::Game,
+                map_id: None,
+                server_addr: None,
+            });
         }
         Ok(())
     }
@@ -126,13 +173,50 @@ impl Launcher {
             return Ok(());
         };
         let launch_opts = self.launch_options(use_battleye);
-        if self.monitor_launch(self.game.continue_session(launch_opts)?)? {
-            app::quit();
+        let (map_id, server_addr) = match &*self.game.last_session() {
+            Some(Session::SinglePlayer(MapRef::Known { map_id })) => (Some(*map_id), None),
+            Some(Session::Online(ServerRef::Known(server))) => (None, server.game_addr()),
+            Some(Session::Online(ServerRef::Unknown(addr))) => (None, Some(*addr)),
+            _ => (None, None),
+        };
+        let record = self.launch_record(
+            LaunchMode::ContinueSession,
+            map_id,
+            server_addr,
+            use_battleye,
+        );
+        if self.monitor_launch(self.game.continue_session(launch_opts)?, record)? {
+            self.game_started(GameLaunched {
+                mode: LaunchMode::ContinueSession,
+                map_id,
+                server_addr,
+            });
         }
         Ok(())
     }
 
+    pub fn should_auto_join(&self) -> bool {
+        self.config.get().auto_join_on_startup
+            && matches!(&*self.game.last_session(), Some(Session::Online(_)))
+    }
+
+    pub fn auto_join_last_session(&self) -> Result<()> {
+        let delay_secs = self.config.get().auto_join_delay_secs.0;
+        if !self.auto_join_countdown(delay_secs) {
+            return Ok(());
+        }
+        self.continue_last_session()
+    }
+
     pub fn join_server(&self, conn_info: ConnectionInfo) -> Result<()> {
+        self.join_server_with_battleye(conn_info, None)
+    }
+
+    pub fn join_server_with_battleye(
+        &self,
+        conn_info: ConnectionInfo,
+        battleye_override: Option<bool>,
+    ) -> Result<()> {
         let ConnectionInfo {
             addr,
             password,
@@ -152,7 +236,11 @@ impl Launcher {
             return Ok(());
         }
 
-        let use_battleye = match self.config.get().use_battleye {
+        let battleye_usage = match battleye_override {
+            Some(enabled) => BattlEyeUsage::Always(enabled),
+            None => self.config.get().use_battleye,
+        };
+        let use_battleye = match battleye_usage {
             BattlEyeUsage::Always(enabled) => enabled,
             BattlEyeUsage::Auto => {
                 if let Some(enabled) = battleye_required.or_else(|| self.prompt_battleye()) {
@@ -163,8 +251,13 @@ impl Launcher {
             }
         };
         let launch_opts = self.launch_options(use_battleye);
-        if self.monitor_launch(self.game.join_server(addr, password, launch_opts)?)? {
-            app::quit();
+        let record = self.launch_record(LaunchMode::JoinServer, None, Some(addr), use_battleye);
+        if self.monitor_launch(self.game.join_server(addr, password, launch_opts)?, record)? {
+            self.game_started(GameLaunched {
+                mode: LaunchMode::JoinServer,
+                map_id: None,
+                server_addr: Some(addr),
+            });
         }
         Ok(())
     }
@@ -187,6 +280,9 @@ impl Launcher {
             }
             self.show_offline_singleplayer_bug_warning();
         }
+        if !self.saves.confirm_free_space() {
+            return Ok(());
+        }
         self.game.save_server_settings(settings)?;
         self.saves
             .clear_progress(map_id, fls_account_id.as_deref())?;
@@ -204,6 +300,9 @@ impl Launcher {
             }
             self.show_offline_singleplayer_bug_warning();
         }
+        if !self.saves.confirm_free_space() {
+            return Ok(());
+        }
         self.launch_single_player(map_id, false)
     }
 
@@ -225,13 +324,30 @@ impl Launcher {
             false
         };
         let launch_opts = self.launch_options(use_battleye);
-        if self.monitor_launch(self.game.launch_single_player(map_id, launch_opts)?)? {
-            app::quit();
+        let record = self.launch_record(LaunchMode::SinglePlayer, Some(map_id), None, use_battleye);
+        if self.monitor_launch(self.game.launch_single_player(map_id, launch_opts)?, record)? {
+            self.game_started(GameLaunched {
+                mode: LaunchMode::SinglePlayer,
+                map_id: Some(map_id),
+                server_addr: None,
+            });
         }
         Ok(())
     }
 
     fn can_launch(&self) -> bool {
+        match self.game.is_running() {
+            Ok(true) => {
+                if !prompt_confirm(PROMPT_GAME_RUNNING) {
+                    return false;
+                }
+            }
+            Ok(false) => (),
+            Err(err) => {
+                warn!(self.logger, "Error checking whether the game is running"; "error" => %err);
+            }
+        }
+
         if self.steam.can_launch() {
             return true;
         }
@@ -271,6 +387,52 @@ impl Launcher {
         }
     }
 
+    fn auto_join_countdown(&self, delay_secs: u8) -> bool {
+        if delay_secs == 0 {
+            return true;
+        }
+
+        let monitor = self.task_monitor(
+            "Joining Last Server",
+            &auto_join_message(delay_secs),
+            "Cancel",
+        );
+        monitor.show();
+
+        let remaining = Rc::new(Cell::new(delay_secs));
+        app::add_timeout3(
+            1.0,
+            weak_cb!(
+                [remaining] => |handle| {
+                    let secs = remaining.get().saturating_sub(1);
+                    remaining.set(secs);
+                    if secs > 0 {
+                        app::repeat_timeout3(1.0, handle);
+                    }
+                    app::awake();
+                }
+            ),
+        );
+        let mut displayed = delay_secs;
+        loop {
+            if monitor.result().is_some() {
+                return false;
+            }
+            let secs = remaining.get();
+            if secs == 0 {
+                return true;
+            }
+            if secs != displayed {
+                monitor.set_message(&auto_join_message(secs));
+                displayed = secs;
+            }
+            app::wait();
+            if app::should_program_quit() {
+                return false;
+            }
+        }
+    }
+
     fn prompt_battleye(&self) -> Option<bool> {
         let battleye_dialog = Dialog::default(
             fltk::app::first_window().as_ref().unwrap(),
@@ -351,12 +513,90 @@ impl Launcher {
         LaunchOptions {
             enable_battleye: use_battleye,
             use_all_cores: config.use_all_cores,
+            windowed: config.windowed,
             extra_args: config.extra_args.clone(),
         }
     }
 
-    fn monitor_launch(&self, mut launch: Launch) -> Result<bool> {
+    fn launch_record(
+        &self,
+        mode: LaunchMode,
+        map_id: Option<usize>,
+        server_addr: Option<SocketAddr>,
+        battleye: bool,
+    ) -> LaunchRecord {
+        let mods = self.mods.active_mod_names().unwrap_or_else(|err| {
+            warn!(self.logger, "Error reading the mod list for launch history"; "error" => %err);
+            Vec::new()
+        });
+        LaunchRecord {
+            timestamp: Utc::now(),
+            mode,
+            server_addr,
+            map_id,
+            mod_list_hash: mod_list_hash(&mods),
+            mods,
+            battleye,
+            exit_code: None,
+        }
+    }
+
+    fn monitor_launch(&self, mut launch: Launch, mut record: LaunchRecord) -> Result<bool> {
+        let result = self.wait_for_launch(&mut launch);
+        record.exit_code = launch.exit_code();
+        let running = record.exit_code.is_none() && matches!(result, Ok(true));
+
+        // A cancelled launch is killed on purpose, so it doesn't count as a crash
+        if !running && !matches!(result, Ok(false)) && launch.crashed_early() {
+            self.record_crash();
+        }
+
+        let history_path = launch_history_path(&self.game);
+        if let Err(err) = append_launch_record(&history_path, &record) {
+            warn!(
+                self.logger,
+                "Error saving launch history";
+                "path" => history_path.display(),
+                "error" => %err,
+            );
+            return result;
+        }
+
+        // The exit code is filled in if BUGLE is still running when the game exits
+        if running {
+            let tx = self.bus.borrow().sender().clone();
+            let timestamp = record.timestamp;
+            std::thread::spawn(move || {
+                let exit = launch.wait_for_exit();
+                tx.send(GameExited { timestamp, exit }).ok();
+            });
+        }
+        result
+    }
+
+    fn game_exited(&self, timestamp: DateTime<Utc>, exit: GameExit) {
+        if exit.crashed_early {
+            self.record_crash();
+        }
+
+        let history_path = launch_history_path(&self.game);
+        if let Err(err) = set_launch_exit_code(&history_path, timestamp, exit.exit_code) {
+            warn!(
+                self.logger,
+                "Error saving the game exit code";
+                "path" => history_path.display(),
+                "error" => %err,
+            );
+        }
+    }
+
+    fn wait_for_launch(&self, launch: &mut Launch) -> Result<bool> {
+        self.config
+            .update(|config| config.total_launches = config.total_launches.saturating_add(1));
+        self.bus.borrow().publish(UpdateLaunchStats);
+
         if let TaskState::Ready(()) = launch.poll()? {
+            self.record_session();
             return Ok(true);
         }
 
@@ -382,6 +622,7 @@ impl Launcher {
         loop {
             if should_poll.replace(false) {
                 if let TaskState::Ready(()) = launch.poll()? {
+                    self.record_session();
                     return Ok(true);
                 }
             }
@@ -396,6 +637,49 @@ impl Launcher {
         }
     }
 
+    fn record_crash(&self) {
+        warn!(self.logger, "Game crashed shortly after launch");
+        self.config
+            .update(|config| config.crash_count = config.crash_count.saturating_add(1));
+        self.bus.borrow().publish(UpdateLaunchStats);
+    }
+
+    fn game_started(&self, launched: GameLaunched) {
+        self.bus.borrow().publish(launched);
+        if app::should_program_quit() {
+            return;
+        }
+        if self.config.get().minimize_on_launch && tray_supported() {
+            match minimize_to_tray(&self.logger, Arc::clone(&self.game)) {
+                Ok(()) => return,
+                Err(err) => warn!(self.logger, "Error minimizing to tray"; "error" => %err),
+            }
+        }
+        app::quit();
+    }
+
+    fn record_session(&self) {
+        self.config
+            .update(|config| config.session_count = config.session_count.saturating_add(1));
+
+        let stats = SessionStats {
+            session_count: self.config.get().session_count,
+            last_session_start: Utc::now(),
+        };
+        let stats_path = self.game.save_path().join("stats.json");
+        let result = std::fs::File::create(&stats_path)
+            .map_err(anyhow::Error::from)
+            .and_then(|file| Ok(serde_json::to_writer_pretty(file, &stats)?));
+        if let Err(err) = result {
+            warn!(
+                self.logger,
+                "Error saving session statistics";
+                "path" => stats_path.display(),
+                "error" => %err,
+            );
+        }
+    }
+
     fn task_monitor(&self, title: &str, message: &str, button: &str) -> Dialog<()> {
         Dialog::new(
             fltk::app::first_window().as_ref().unwrap(),
@@ -440,6 +724,13 @@ enum SessionBattlEyeUsage {
     AskUser,
 }
 
+fn auto_join_message(secs: u8) -> String {
+    format!("Rejoining your last server in {} seconds...", secs)
+}
+
+const PROMPT_GAME_RUNNING: &str =
+    "Conan Exiles is already running. Do you want to launch another instance?";
+
 const ERR_STEAM_NOT_ONLINE: &str = "Steam is in offline mode. Online play is disabled.";
 const ERR_FLS_ACCOUNT_NOT_CACHED: &str =
     "Steam is offline and the game has not stored your FLS account info. You need to start the \
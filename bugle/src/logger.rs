@@ -1,4 +1,5 @@
 use std::fmt::{Debug, Display};
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
@@ -10,14 +11,20 @@ pub const DEFAULT_LOG_LEVEL: FilterLevel = FilterLevel::Debug;
 #[cfg(not(default_log_debug))]
 pub const DEFAULT_LOG_LEVEL: FilterLevel = FilterLevel::Info;
 
+pub struct RootLogger {
+    pub logger: Logger,
+    pub guard: AsyncGuard,
+    pub log_path: Option<PathBuf>,
+}
+
 #[cfg(not(windows))]
-pub fn create_root_logger(log_level: &Arc<AtomicUsize>) -> (Logger, AsyncGuard) {
+pub fn create_root_logger(log_level: &Arc<AtomicUsize>) -> RootLogger {
     create_term_logger(log_level)
 }
 
 #[cfg(windows)]
-pub fn create_root_logger(log_level: &Arc<AtomicUsize>) -> (Logger, AsyncGuard) {
-    if unsafe { winapi::um::wincon::AttachConsole(u32::MAX) } != 0 {
+pub fn create_root_logger(log_level: &Arc<AtomicUsize>) -> RootLogger {
+    if crate::env::attach_parent_console() {
         create_term_logger(log_level)
     } else {
         try_create_portable_mode_logger(log_level)
@@ -52,27 +59,25 @@ where
     }
 }
 
-fn create_term_logger(log_level: &Arc<AtomicUsize>) -> (Logger, AsyncGuard) {
+fn create_term_logger(log_level: &Arc<AtomicUsize>) -> RootLogger {
     let drain = slog_term::TermDecorator::new().build();
     let drain = slog_term::FullFormat::new(drain).build().fuse();
-    create_root_logger_for_drain(drain, log_level)
+    create_root_logger_for_drain(drain, log_level, None)
 }
 
-fn create_discard_logger(log_level: &Arc<AtomicUsize>) -> (Logger, AsyncGuard) {
-    create_root_logger_for_drain(Discard, log_level)
+fn create_discard_logger(log_level: &Arc<AtomicUsize>) -> RootLogger {
+    create_root_logger_for_drain(Discard, log_level, None)
 }
 
 #[cfg(windows)]
-fn try_create_portable_mode_logger(
-    log_level: &Arc<AtomicUsize>,
-) -> anyhow::Result<(Logger, AsyncGuard)> {
+fn try_create_portable_mode_logger(log_level: &Arc<AtomicUsize>) -> anyhow::Result<RootLogger> {
     use crate::env::current_exe_dir;
 
     try_create_logger_in_dir(current_exe_dir()?, log_level)
 }
 
 #[cfg(windows)]
-fn try_create_appdata_logger(log_level: &Arc<AtomicUsize>) -> anyhow::Result<(Logger, AsyncGuard)> {
+fn try_create_appdata_logger(log_level: &Arc<AtomicUsize>) -> anyhow::Result<RootLogger> {
     use crate::env::{appdata_dir, AppDataFolder};
 
     let mut log_path = appdata_dir(AppDataFolder::LocalLow)?;
@@ -86,21 +91,25 @@ fn try_create_appdata_logger(log_level: &Arc<AtomicUsize>) -> anyhow::Result<(Lo
 fn try_create_logger_in_dir(
     mut path: std::path::PathBuf,
     log_level: &Arc<AtomicUsize>,
-) -> anyhow::Result<(Logger, AsyncGuard)> {
+) -> anyhow::Result<RootLogger> {
     path.push("bugle.log");
 
     let log_file = std::fs::OpenOptions::new()
         .write(true)
         .truncate(true)
         .create(true)
-        .open(path)?;
+        .open(&path)?;
 
     let drain = slog_term::PlainDecorator::new(log_file);
     let drain = slog_term::FullFormat::new(drain).build().fuse();
-    Ok(create_root_logger_for_drain(drain, log_level))
+    Ok(create_root_logger_for_drain(drain, log_level, Some(path)))
 }
 
-fn create_root_logger_for_drain<D>(drain: D, log_level: &Arc<AtomicUsize>) -> (Logger, AsyncGuard)
+fn create_root_logger_for_drain<D>(
+    drain: D,
+    log_level: &Arc<AtomicUsize>,
+    log_path: Option<PathBuf>,
+) -> RootLogger
 where
     D: 'static + Drain + Send,
     D::Err: Debug,
@@ -108,7 +117,11 @@ where
     let drain = RuntimeLevelFilter::new(drain, log_level).fuse();
     let (drain, guard) = Async::new(drain).build_with_guard();
     let drain = drain.fuse();
-    (Logger::root(drain, o!()), guard)
+    RootLogger {
+        logger: Logger::root(drain, o!()),
+        guard,
+        log_path,
+    }
 }
 
 struct RuntimeLevelFilter<D: Drain> {
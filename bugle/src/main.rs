@@ -1,29 +1,39 @@
 #![cfg_attr(windows, windows_subsystem = "windows")]
 
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 
+use anyhow::anyhow;
 use dynabus::Bus;
 use fltk::app::{self, App};
 use fltk::dialog;
 use fltk::prelude::WindowExt;
 use game::platform::steam::SteamModDirectory;
+use lazy_static::lazy_static;
 use slog::{error, info, warn, FilterLevel, Logger};
 
 mod auth;
 mod auth_manager;
 mod battleye;
 mod bus;
+mod cli;
 mod config;
+mod diagnostics;
 mod env;
 mod game;
 mod gui;
+mod launch_history;
 mod launcher;
 mod logger;
 mod mod_manager;
+mod mod_notes;
 mod net;
 mod parser_utils;
 mod saved_games_manager;
@@ -32,39 +42,56 @@ mod servers;
 mod util;
 mod workers;
 
+use self::auth::Capability;
 use self::auth_manager::AuthManager;
 use self::bus::AppBus;
 use self::config::{
-    BattlEyeUsage, ConfigManager, ConfigPersister, IniConfigPersister, TransientConfig,
+    BattlEyeUsage, ConfigManager, ConfigPersister, IniConfigPersister, TransientConfig, WhatsNew,
 };
-use self::game::platform::steam::{Steam, SteamClient};
-use self::game::{Branch, Game};
+use self::game::platform::steam::{game_update_url, PlatformReady, Steam, SteamClient};
+use self::game::{Branch, Game, KNOWN_DLCS};
 use self::gui::theme::Theme;
-use self::gui::LauncherWindow;
-use self::launcher::Launcher;
-use self::logger::create_root_logger;
+use self::gui::{LauncherServices, LauncherWindow, LogSettings, UpdateAuthState, WhatsNewDialog};
+use self::launcher::{ConnectionInfo, Launcher};
+use self::logger::{create_root_logger, RootLogger};
 use self::mod_manager::ModManager;
 use self::saved_games_manager::SavedGamesManager;
 use self::server_manager::ServerManager;
 use self::util::weak_cb;
+use self::workers::TaskState;
 
 #[derive(dynabus::Event)]
 pub struct Idle;
 
+struct CliJoin {
+    addr: SocketAddr,
+    battleye: Option<bool>,
+}
+
+enum PendingJoin {
+    Server(CliJoin),
+    LastSession,
+}
+
 struct LauncherApp {
+    logger: Logger,
     app: App,
     bus: Rc<RefCell<AppBus>>,
+    config: Rc<ConfigManager>,
     steam: Rc<SteamClient>,
     auth: Rc<AuthManager>,
     servers: Rc<ServerManager>,
     mods: Rc<ModManager>,
+    launcher: Rc<Launcher>,
     main_window: LauncherWindow,
+    pending_join: Rc<RefCell<Option<PendingJoin>>>,
 }
 
 impl LauncherApp {
     fn new(
         logger: Logger,
         log_level: Option<Arc<AtomicUsize>>,
+        log_path: Option<PathBuf>,
         can_switch_branch: bool,
         app: App,
         steam: Steam,
@@ -75,11 +102,23 @@ impl LauncherApp {
         let bus = bus::bus();
 
         let steam = steam.init_client(&*game, Rc::clone(&bus));
+
+        // Steam might still be initializing, so check again once it's ready
+        check_dlc_ownership(&game, &steam);
+        bus.borrow_mut().subscribe_observer({
+            let game = Arc::clone(&game);
+            let steam = Rc::downgrade(&steam);
+            move |_: &PlatformReady| {
+                if let Some(steam) = steam.upgrade() {
+                    check_dlc_ownership(&game, &steam);
+                }
+            }
+        });
         let mod_directory = SteamModDirectory::new(
             &logger,
             Rc::clone(&steam),
             bus.borrow().sender().clone(),
-            game.installed_mods(),
+            &game.installed_mods(),
         );
 
         let auth = AuthManager::new(
@@ -89,7 +128,12 @@ impl LauncherApp {
             Rc::clone(&steam),
         );
 
-        let servers = ServerManager::new(&logger, Rc::clone(&bus), Arc::clone(&game));
+        let servers = ServerManager::new(
+            &logger,
+            Rc::clone(&bus),
+            Arc::clone(&game),
+            Rc::clone(&config),
+        );
 
         let mods = ModManager::new(
             &logger,
@@ -99,10 +143,11 @@ impl LauncherApp {
             Rc::<SteamModDirectory>::clone(&mod_directory),
         );
 
-        let saves = SavedGamesManager::new(Rc::clone(&bus), Arc::clone(&game));
+        let saves = SavedGamesManager::new(Rc::clone(&bus), Rc::clone(&config), Arc::clone(&game));
 
         let launcher = Launcher::new(
             &logger,
+            Rc::clone(&bus),
             Rc::clone(&config),
             Arc::clone(&game),
             Rc::clone(&steam),
@@ -112,50 +157,116 @@ impl LauncherApp {
             Rc::clone(&saves),
         );
 
+        // The home tab consumes the auth state updates, so this needs to be subscribed first
+        let pending_join: Rc<RefCell<Option<PendingJoin>>> = Rc::new(RefCell::new(None));
+        bus.borrow_mut().subscribe_observer({
+            let logger = logger.clone();
+            let launcher = Rc::downgrade(&launcher);
+            let pending_join = Rc::clone(&pending_join);
+            move |UpdateAuthState(state): &UpdateAuthState| {
+                let TaskState::Ready(capability) = &state.online_capability else {
+                    return;
+                };
+                let Some(join) = pending_join.borrow_mut().take() else {
+                    return;
+                };
+                if let Some(launcher) = launcher.upgrade() {
+                    join_when_ready(&logger, launcher, join, capability);
+                }
+            }
+        });
+
         let main_window = LauncherWindow::new(
             &logger,
-            Rc::clone(&bus),
-            Arc::clone(&game),
-            Rc::clone(&config),
-            log_level,
-            Rc::clone(&auth),
-            Rc::clone(&launcher),
-            Rc::clone(&servers),
-            Rc::clone(&saves),
-            Rc::clone(&mods),
+            LauncherServices {
+                bus: Rc::clone(&bus),
+                game: Arc::clone(&game),
+                config: Rc::clone(&config),
+                auth: Rc::clone(&auth),
+                launcher: Rc::clone(&launcher),
+                servers: Rc::clone(&servers),
+                saves: Rc::clone(&saves),
+                mod_manager: Rc::clone(&mods),
+            },
+            LogSettings {
+                level: log_level,
+                path: log_path,
+            },
             can_switch_branch,
         );
 
         let this = Rc::new(Self {
+            logger,
             bus,
             app,
+            config,
             steam,
             auth,
             servers,
             mods,
+            launcher,
             main_window,
+            pending_join,
         });
 
         this
     }
 
-    fn run(self: &Rc<Self>, disable_prefetch: bool) {
+    fn run(self: &Rc<Self>, disable_prefetch: bool, join: Option<CliJoin>) {
         self.main_window.show();
+        self.show_whats_new();
 
         if !disable_prefetch {
             self.servers.load_server_list();
         }
 
+        let pending_join = match join {
+            Some(join) => Some(PendingJoin::Server(join)),
+            None if self.launcher.should_auto_join() => Some(PendingJoin::LastSession),
+            None => None,
+        };
+        let joining = pending_join.is_some();
+        *self.pending_join.borrow_mut() = pending_join;
+
         self.mods.check_mod_updates();
         self.auth.check_auth_state();
 
         app::add_check(weak_cb!([this = self] => |_| this.background_loop()));
 
-        while self.main_window.window().shown() && !app::should_program_quit() {
+        if joining {
+            info!(self.logger, "Waiting for authentication to join the server");
+        }
+
+        while (self.main_window.window().shown() || gui::is_minimized_to_tray())
+            && !app::should_program_quit()
+        {
             self.app.wait();
         }
     }
 
+    fn show_whats_new(&self) {
+        let version = env!("CARGO_PKG_VERSION");
+        let last_run_version = self.config.get().last_run_version.clone();
+        if last_run_version.as_deref() == Some(version) {
+            return;
+        }
+
+        // Don't greet a fresh install with the changelog, only an update
+        let mut dont_show_again = false;
+        if last_run_version.is_some() && (self.config.get().whats_new == WhatsNew::Enabled) {
+            if let Some(dialog) = WhatsNewDialog::new(self.main_window.window(), version) {
+                dont_show_again = dialog.run();
+            }
+        }
+
+        self.config.update(|config| {
+            config.last_run_version = Some(version.to_string());
+            if dont_show_again {
+                config.whats_new = WhatsNew::Disabled;
+            }
+        });
+    }
+
     fn background_loop(&self) {
         loop {
             self.steam.run_callbacks();
@@ -169,10 +280,27 @@ impl LauncherApp {
     }
 }
 
+impl Drop for LauncherApp {
+    fn drop(&mut self) {
+        // Make sure no handler fires while the components are being torn down
+        self.bus.borrow_mut().clear();
+    }
+}
+
 #[tokio::main]
 async fn main() {
     let mut args = pico_args::Arguments::from_env();
+    let headless = args.contains("--headless");
     let disable_prefetch = args.contains("--no-prefetch");
+    let join_addr = args.opt_value_from_fn("--join", SocketAddr::from_str);
+    let game_path_override: Option<PathBuf> = args.opt_value_from_str("--game-path").ok().flatten();
+    let battleye = if args.contains("--battleye") {
+        Some(true)
+    } else if args.contains("--no-battleye") {
+        Some(false)
+    } else {
+        None
+    };
     let log_level_override = args
         .opt_value_from_fn(["-l", "--log-level"], |s| {
             FilterLevel::from_str(s).map_err(|_| "")
@@ -184,7 +312,11 @@ async fn main() {
             .unwrap_or(logger::DEFAULT_LOG_LEVEL)
             .as_usize(),
     ));
-    let (root_logger, log_guard) = create_root_logger(&log_level);
+    let RootLogger {
+        logger: root_logger,
+        guard: log_guard,
+        log_path,
+    } = create_root_logger(&log_level);
 
     let config_persister: Box<dyn ConfigPersister> = match IniConfigPersister::new() {
         Ok(persister) => {
@@ -214,29 +346,40 @@ async fn main() {
         );
     }
 
+    if headless {
+        #[cfg(windows)]
+        env::attach_parent_console();
+        let exit_code = cli::run_headless(&root_logger, &config, game_path_override, args);
+        drop(log_guard);
+        let _ = std::io::stdout().flush();
+        let _ = std::io::stderr().flush();
+        std::process::exit(exit_code);
+    }
+
     let app = App::default();
     Theme::from_config(config.get().theme).apply();
     gui::glyph::add_symbols();
 
-    let mut steam = match Steam::locate(&root_logger) {
-        Some(steam) => steam,
-        None => {
-            dialog::alert_default(
-                "Cannot locate Steam installation. Please verify that you have Steam installed and\
-                 try again.",
-            );
-            return;
-        }
+    let mut steam = Steam::locate(&root_logger);
+    if game_path_override.is_none() && !steam.is_installed() {
+        dialog::alert_default(
+            "Cannot locate Steam installation. Please verify that you have Steam installed and\
+             try again.",
+        );
+        return;
+    }
+    let can_switch_branch = game_path_override.is_none()
+        && steam
+            .locate_game(match config.get().branch {
+                Branch::Live => Branch::TestLive,
+                Branch::TestLive => Branch::Live,
+            })
+            .is_ok();
+    let location = match game_path_override {
+        Some(game_path) => steam.locate_game_at(game_path, config.get().branch),
+        None => steam.locate_game(config.get().branch),
     };
-    let can_switch_branch = steam
-        .locate_game(match config.get().branch {
-            Branch::Live => Branch::TestLive,
-            Branch::TestLive => Branch::Live,
-        })
-        .is_ok();
-    let game = steam
-        .locate_game(config.get().branch)
-        .and_then(|loc| steam.init_game(loc));
+    let game = location.and_then(|loc| steam.init_game(loc));
     let game = match game {
         Ok(game) => game,
         Err(err) => {
@@ -270,21 +413,30 @@ async fn main() {
                     }
                 }
             } else {
-                gui::alert_error(
-                    "There was a problem with your Conan Exiles installation.",
-                    &err,
-                );
+                alert_install_error(&err);
             }
             return;
         }
     };
 
     if game.needs_update() {
-        if gui::prompt_confirm(
+        let choice = dialog::choice2_default(
             "Conan Exiles needs to be updated. Do you want to close BUGLE?\n\
-                Note that closing BUGLE will not automatically start the update.",
-        ) {
-            return;
+                Choose \"Update Now\" to close BUGLE and start the update in Steam.",
+            "No",
+            "Yes",
+            "Update Now",
+        );
+        match choice {
+            Some(1) => return,
+            Some(2) => {
+                if let Err(err) = env::open_url(&game_update_url(game.branch())) {
+                    error!(root_logger, "Error starting the game update"; "error" => %err);
+                    gui::alert_error("There was a problem starting the update.", &err.into());
+                }
+                return;
+            }
+            _ => (),
         }
     }
 
@@ -302,14 +454,105 @@ async fn main() {
     let app = LauncherApp::new(
         root_logger.clone(),
         if log_level_override.is_none() { Some(log_level) } else { None },
+        log_path,
         can_switch_branch,
         app,
         steam,
         game,
         config,
     );
-    app.run(disable_prefetch);
+    let join = match join_addr {
+        Ok(addr) => addr.map(|addr| CliJoin { addr, battleye }),
+        Err(err) => {
+            warn!(root_logger, "Invalid server address to join"; "error" => %err);
+            gui::alert_error(ERR_INVALID_JOIN_ADDRESS, &err.into());
+            None
+        }
+    };
+    app.run(disable_prefetch, join);
 
     info!(root_logger, "Shutting down launcher");
     drop(log_guard);
 }
+
+fn check_dlc_ownership(game: &Game, steam: &SteamClient) {
+    if let Some(ownership) = steam.dlc_ownership(KNOWN_DLCS.iter().map(|dlc| dlc.app_id)) {
+        game.set_dlc_ownership(ownership);
+    }
+}
+
+fn join_when_ready(
+    logger: &Logger,
+    launcher: Rc<Launcher>,
+    join: PendingJoin,
+    capability: &Capability,
+) {
+    let capability = capability
+        .as_ref()
+        .map(|_| ())
+        .map_err(|err| anyhow!(err.to_string()));
+    let logger = logger.clone();
+    let err_msg = match join {
+        PendingJoin::Server(_) => ERR_JOINING_SERVER,
+        PendingJoin::LastSession => ERR_AUTO_JOINING,
+    };
+
+    // Launch outside of the event dispatch, since joining can show dialogs
+    app::add_timeout3(0.0, move |_| {
+        if let Err(err) = &capability {
+            error!(logger, "Cannot join the server"; "error" => %err);
+            gui::alert_error(err_msg, err);
+            return;
+        }
+        let result = match &join {
+            PendingJoin::Server(join) => {
+                let conn_info = ConnectionInfo {
+                    addr: join.addr,
+                    password: None,
+                    battleye_required: None,
+                };
+                launcher.join_server_with_battleye(conn_info, join.battleye)
+            }
+            PendingJoin::LastSession => launcher.auto_join_last_session(),
+        };
+        if let Err(err) = result {
+            error!(logger, "Error joining the server"; "error" => %err);
+            gui::alert_error(err_msg, &err);
+        }
+    });
+}
+
+fn alert_install_error(err: &anyhow::Error) {
+    let details = format!("{:#}", err);
+    let friendly = KNOWN_INSTALL_ERRORS
+        .iter()
+        .find(|(pattern, _)| details.contains(*pattern))
+        .map(|(_, message)| *message);
+    match friendly {
+        Some(message) => dialog::alert_default(&format!("{}\n{}", ERR_INSTALLATION, message)),
+        None => {
+            let choice = dialog::choice2_default(
+                &format!("{}\n{}", ERR_INSTALLATION, details),
+                "OK",
+                "Copy error to clipboard",
+                "",
+            );
+            if choice == Some(1) {
+                app::copy(&details);
+            }
+        }
+    }
+}
+
+const ERR_INSTALLATION: &str = "There was a problem with your Conan Exiles installation.";
+const ERR_AUTO_JOINING: &str = "Error while trying to rejoin the last server.";
+const ERR_JOINING_SERVER: &str = "Error while trying to launch the game to join the server.";
+const ERR_INVALID_JOIN_ADDRESS: &str = "The server address to join is not valid.";
+
+lazy_static! {
+    static ref KNOWN_INSTALL_ERRORS: HashMap<&'static str, &'static str> = HashMap::from([(
+        "missing field `name`",
+        "The game installation metadata is incomplete. This usually means the game needs to be \
+         repaired via Steam.",
+    )]);
+}
@@ -1,22 +1,30 @@
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
-use std::path::Path;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::Arc;
 
 use anyhow::Result;
 use bit_vec::BitVec;
+use dynabus::mpsc::BusSender;
 use dynabus::Bus;
-use lazy_static::lazy_static;
-use regex::Regex;
-use slog::{warn, Logger};
+use fltk::dialog::message_default;
+use slog::{info, warn, Logger};
 
-use crate::bus::AppBus;
+use crate::bus::{AppBus, AppSender};
 use crate::config::{ConfigManager, ModMismatchChecks};
-use crate::game::platform::steam::PlatformReady;
+use crate::game::platform::steam::{fetch_mod_stats, PlatformReady};
 use crate::game::platform::ModDirectory;
-use crate::game::{list_mod_controllers, Game, ModEntry, ModRef, Mods};
-use crate::gui::{prompt_confirm, ModUpdateProgressDialog, ModUpdateSelectionDialog};
+use crate::game::{
+    list_mod_controllers, mod_controller_folder, valid_mod_list_entries, Game, ModEntry,
+    ModProvenance, ModRef, ModStats, Mods,
+};
+use crate::gui::{alert_error, prompt_confirm, ModUpdateProgressDialog, ModUpdateSelectionDialog};
+use crate::launcher::GameLaunched;
+use crate::server_manager::ServerMods;
 use crate::util::weak_cb;
 
 pub struct ModManager {
@@ -24,6 +32,46 @@ pub struct ModManager {
     config: Rc<ConfigManager>,
     game: Arc<Game>,
     mod_directory: Rc<dyn ModDirectory>,
+    tx: BusSender<AppSender>,
+    stats_requested: RefCell<HashSet<usize>>,
+    activation_counts: RefCell<HashMap<String, u32>>,
+    notified_updates: RefCell<HashSet<PathBuf>>,
+}
+
+pub struct ServerModComparison {
+    pub missing: Vec<(u64, String)>,
+    pub extra: Vec<(ModRef, String)>,
+    pub matching: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct DependencySortReport {
+    pub circular: Vec<String>,
+    pub missing: Vec<(String, String)>,
+}
+
+#[derive(Default)]
+pub struct ModListFixReport {
+    pub fixed: Vec<(usize, PathBuf)>,
+    pub unfixed: Vec<(usize, PathBuf)>,
+}
+
+#[derive(dynabus::Event)]
+pub struct ModStatsLoaded(pub usize, pub ModStats);
+
+#[derive(dynabus::Event)]
+pub struct ModUpdatesAvailable(pub usize);
+
+#[derive(dynabus::Event)]
+pub struct InstalledModsChanged;
+
+#[derive(dynabus::Event)]
+pub struct ModNamesResolved(pub Vec<(u64, String)>);
+
+#[derive(dynabus::Event)]
+struct ModSubscribed {
+    mod_id: u64,
+    result: Result<PathBuf>,
 }
 
 impl ModManager {
@@ -35,27 +83,87 @@ impl ModManager {
         mod_directory: Rc<dyn ModDirectory>,
     ) -> Rc<Self> {
         let logger = logger.clone();
+        let tx = bus.borrow().sender().clone();
+
+        let activation_counts = match load_activation_counts(&mod_stats_path(&game)) {
+            Ok(counts) => counts,
+            Err(err) => {
+                warn!(logger, "Error loading mod activation counts"; "error" => %err);
+                HashMap::new()
+            }
+        };
 
         let this = Rc::new(Self {
             logger,
             config,
             game,
             mod_directory,
+            tx,
+            stats_requested: RefCell::new(HashSet::new()),
+            activation_counts: RefCell::new(activation_counts),
+            notified_updates: RefCell::new(HashSet::new()),
         });
 
         {
             let mut bus = bus.borrow_mut();
             bus.subscribe_observer(weak_cb!([this] => |&PlatformReady| this.check_mod_updates()));
+            bus.subscribe_consumer(weak_cb!(
+                [this] => |ModSubscribed { mod_id, result }| this.mod_subscribed(mod_id, result)
+            ));
+            bus.subscribe_observer(weak_cb!([this] => |_: &GameLaunched| this.game_launched()));
         }
 
         this
     }
 
+    pub fn activation_count(&self, entry: &ModEntry) -> u32 {
+        let counts = self.activation_counts.borrow();
+        activation_key(entry)
+            .and_then(|key| counts.get(key).copied())
+            .unwrap_or_default()
+    }
+
+    pub fn record_activation(&self, entry: &ModEntry) {
+        let Some(key) = activation_key(entry) else {
+            return;
+        };
+        let mut counts = self.activation_counts.borrow_mut();
+        *counts.entry(key.to_string()).or_default() += 1;
+
+        let stats_path = mod_stats_path(&self.game);
+        if let Err(err) = save_activation_counts(&stats_path, &counts) {
+            warn!(
+                self.logger,
+                "Error saving mod activation counts";
+                "path" => stats_path.display(),
+                "error" => %err,
+            );
+        }
+    }
+
+    fn game_launched(&self) {
+        let snapshot_path = self.game.last_launch_mod_list_path();
+        let result = self
+            .game
+            .load_mod_list()
+            .and_then(|mod_list| self.game.save_mod_list_to(&snapshot_path, &mod_list));
+        if let Err(err) = result {
+            warn!(
+                self.logger,
+                "Error saving the last launch mod list";
+                "path" => snapshot_path.display(),
+                "error" => %err,
+            );
+        }
+    }
+
     pub fn check_mod_updates(&self) {
         if !Rc::clone(&self.mod_directory).can_update() {
             return;
         }
 
+        let mut outdated_count = 0;
+        let mut notify = false;
         for entry in self.game.installed_mods().iter() {
             match Rc::clone(&self.mod_directory).needs_update(entry) {
                 Ok(needs_update) => entry.set_needs_update(needs_update),
@@ -67,11 +175,33 @@ impl ModManager {
                     "error" => %err,
                 ),
             }
+            if entry.needs_update() {
+                outdated_count += 1;
+                notify |= self
+                    .notified_updates
+                    .borrow_mut()
+                    .insert(entry.pak_path.clone());
+            }
+        }
+
+        if notify {
+            self.tx.send(ModUpdatesAvailable(outdated_count)).ok();
         }
     }
 
+    pub fn active_mod_names(&self) -> Result<Vec<String>> {
+        let installed_mods = self.game.installed_mods();
+        Ok(self
+            .game
+            .load_mod_list()?
+            .iter()
+            .filter(|mod_ref| !matches!(mod_ref, ModRef::Disabled(_)))
+            .map(|mod_ref| mod_ref_name(&installed_mods, mod_ref))
+            .collect())
+    }
+
     pub fn import_mod_list(&self, path: &Path) -> Result<Vec<ModRef>> {
-        let active_mods = self.game.load_mod_list_from(&path)?;
+        let active_mods = valid_mod_list_entries(self.game.load_mod_list_from(path)?);
         self.game.save_mod_list(&active_mods)?;
         Ok(active_mods)
     }
@@ -83,6 +213,9 @@ impl ModManager {
         let installed_mods = self.game.installed_mods();
         let mut outdated_mods = Vec::new();
         for mod_ref in mod_list {
+            if let ModRef::Disabled(_) = mod_ref {
+                continue;
+            }
             if let Some(entry) = installed_mods.get(&mod_ref) {
                 if entry.needs_update() {
                     outdated_mods.push(mod_ref);
@@ -102,7 +235,7 @@ impl ModManager {
 
         let dialog = ModUpdateSelectionDialog::new(
             fltk::app::first_window().as_ref().unwrap(),
-            installed_mods,
+            &installed_mods,
             outdated_mods,
         );
         let mods_to_update = match dialog.run() {
@@ -115,13 +248,47 @@ impl ModManager {
 
         let dialog = ModUpdateProgressDialog::new(
             fltk::app::first_window().as_ref().unwrap(),
-            installed_mods,
+            &installed_mods,
             mods_to_update,
             Rc::clone(&self.mod_directory),
         );
         dialog.run();
     }
 
+    pub fn subscribe_mod(&self, mod_id: u64) -> Result<()> {
+        info!(self.logger, "Subscribing to mod"; "mod_id" => mod_id);
+        let tx = self.tx.clone();
+        Rc::clone(&self.mod_directory).subscribe(
+            mod_id,
+            Rc::new(move |result| {
+                tx.send(ModSubscribed { mod_id, result }).ok();
+            }),
+        )
+    }
+
+    pub fn download_mod(&self, mod_id: u64) -> Result<()> {
+        info!(self.logger, "Downloading mod"; "mod_id" => mod_id);
+        let tx = self.tx.clone();
+        Rc::clone(&self.mod_directory).download(
+            mod_id,
+            Rc::new(move |result| {
+                tx.send(ModSubscribed { mod_id, result }).ok();
+            }),
+        )
+    }
+
+    pub fn pending_downloads(&self) -> Vec<(u64, Option<String>)> {
+        let mut mods: Vec<_> = Rc::clone(&self.mod_directory)
+            .pending_downloads()
+            .into_iter()
+            .map(|mod_id| (mod_id, None))
+            .collect();
+        if !mods.is_empty() {
+            Rc::clone(&self.mod_directory).resolve(&mut mods);
+        }
+        mods
+    }
+
     pub fn validate_single_player_mods(&self, map_id: usize) -> Result<bool> {
         if let ModMismatchChecks::Disabled = self.config.get().mod_mismatch_checks {
             return Ok(true);
@@ -134,8 +301,8 @@ impl ModManager {
             let prompt = format!(
                 "{}{}{}",
                 PROMPT_SP_MOD_MISMATCH,
-                join_mod_names(TXT_MISSING_MODS, installed_mods, mismatch.missing_mods),
-                join_mod_names(TXT_ADDED_MODS, installed_mods, mismatch.added_mods),
+                join_mod_names(TXT_MISSING_MODS, &installed_mods, mismatch.missing_mods),
+                join_mod_names(TXT_ADDED_MODS, &installed_mods, mismatch.added_mods),
             );
             Ok(prompt_confirm(&prompt))
         } else {
@@ -143,20 +310,20 @@ impl ModManager {
         }
     }
 
-    pub fn fix_mod_list(&self, mod_list: &mut Vec<ModRef>) -> bool {
+    pub fn fix_mod_list(&self, mod_list: &mut [(usize, ModRef)]) -> ModListFixReport {
         let installed_mods = self.game.installed_mods();
         let mut available_set = BitVec::from_elem(installed_mods.len(), true);
 
-        for mod_ref in mod_list.iter() {
-            if let ModRef::Installed(idx) = mod_ref {
+        for (_, mod_ref) in mod_list.iter() {
+            if let ModRef::Installed(idx) | ModRef::Disabled(idx) = mod_ref {
                 available_set.set(*idx, false);
             }
         }
 
-        let mut fixed_all = true;
-        for mod_ref in mod_list.iter_mut() {
+        let mut report = ModListFixReport::default();
+        for (line, mod_ref) in mod_list.iter_mut() {
             let pak_path = match mod_ref {
-                ModRef::UnknownPakPath(path) => path,
+                ModRef::UnknownPakPath(path) => path.clone(),
                 _ => continue,
             };
             let fixed_idx = installed_mods.iter().enumerate().find_map(|(idx, entry)| {
@@ -174,25 +341,352 @@ impl ModManager {
             if let Some(idx) = fixed_idx {
                 *mod_ref = ModRef::Installed(idx);
                 available_set.set(idx, false);
+                report.fixed.push((*line, pak_path));
             } else {
-                fixed_all = false;
+                report.unfixed.push((*line, pak_path));
+            }
+        }
+
+        report
+    }
+
+    pub fn sort_by_dependencies(&self, mod_list: &mut Vec<ModRef>) -> DependencySortReport {
+        let installed_mods = self.game.installed_mods();
+        let mut report = DependencySortReport::default();
+
+        let infos: Vec<_> = mod_list
+            .iter()
+            .map(|mod_ref| {
+                installed_mods
+                    .get(mod_ref)
+                    .and_then(|entry| entry.info.as_ref().ok())
+            })
+            .collect();
+        let by_folder: HashMap<&str, usize> = infos
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, info)| info.map(|info| (info.folder_name.as_str(), idx)))
+            .collect();
+
+        let mut dependents = vec![Vec::new(); mod_list.len()];
+        let mut in_degree = vec![0usize; mod_list.len()];
+        for (idx, info) in infos.iter().enumerate() {
+            let Some(info) = info else {
+                continue;
+            };
+            for folder in info.required_mods.iter() {
+                match by_folder.get(folder.as_str()) {
+                    Some(&dep_idx) if dep_idx == idx => (),
+                    Some(&dep_idx) => {
+                        dependents[dep_idx].push(idx);
+                        in_degree[idx] += 1;
+                    }
+                    None => report.missing.push((info.name.clone(), folder.clone())),
+                }
+            }
+        }
+
+        let mut ready: BinaryHeap<Reverse<usize>> = in_degree
+            .iter()
+            .enumerate()
+            .filter(|(_, &degree)| degree == 0)
+            .map(|(idx, _)| Reverse(idx))
+            .collect();
+        let mut order = Vec::with_capacity(mod_list.len());
+        while let Some(Reverse(idx)) = ready.pop() {
+            order.push(idx);
+            for &dependent in dependents[idx].iter() {
+                in_degree[dependent] -= 1;
+                if in_degree[dependent] == 0 {
+                    ready.push(Reverse(dependent));
+                }
+            }
+        }
+
+        for (idx, &degree) in in_degree.iter().enumerate() {
+            if degree > 0 {
+                order.push(idx);
+                report.circular.push(infos[idx].unwrap().name.clone());
+            }
+        }
+
+        let mut unsorted: Vec<Option<ModRef>> = mod_list.drain(..).map(Some).collect();
+        mod_list.extend(order.into_iter().map(|idx| unsorted[idx].take().unwrap()));
+
+        report
+    }
+
+    pub fn activate_server_mods(&self, server_mod_ids: &[u64]) -> Result<()> {
+        let mut mod_list = self.game.load_mod_list()?;
+        let installed_mods = self.game.installed_mods();
+        let branch = self.game.branch();
+
+        let activated: Vec<ModRef> = server_mod_ids
+            .iter()
+            .filter_map(|&id| installed_mods.by_steam_id(id, branch))
+            .filter(|mod_ref| !mod_list.contains(mod_ref))
+            .collect();
+        if activated.is_empty() {
+            return Ok(());
+        }
+
+        let prompt = format!(
+            "{}{}",
+            PROMPT_ACTIVATE_SERVER_MODS,
+            join_mod_names(
+                TXT_ACTIVATED_MODS,
+                &installed_mods,
+                activated.iter().cloned().collect()
+            ),
+        );
+        if !prompt_confirm(&prompt) {
+            return Ok(());
+        }
+
+        let backup_path = self.game.mod_list_backup_path();
+        self.game.save_mod_list_to(&backup_path, &mod_list)?;
+        info!(self.logger, "Activating server mods"; "backup_path" => backup_path.display());
+
+        mod_list.retain(|mod_ref| match mod_ref {
+            ModRef::Disabled(idx) => !activated.contains(&ModRef::Installed(*idx)),
+            _ => true,
+        });
+        mod_list.extend(activated);
+        self.game.save_mod_list(&mod_list)
+    }
+
+    pub fn match_server_mods(&self, server_mods: &ServerMods) -> Result<Option<Vec<ModRef>>> {
+        let mod_list = self.game.load_mod_list()?;
+        let installed_mods = self.game.installed_mods();
+        let branch = self.game.branch();
+
+        let mut server_list = Vec::with_capacity(server_mods.mod_ids.len());
+        let mut downloads = Vec::new();
+        for &mod_id in server_mods.mod_ids.iter() {
+            match installed_mods.by_steam_id(mod_id, branch) {
+                Some(mod_ref) => server_list.push(mod_ref),
+                None => downloads.push((mod_id, None)),
+            }
+        }
+
+        let activated: HashSet<ModRef> = server_list
+            .iter()
+            .filter(|mod_ref| !mod_list.contains(mod_ref))
+            .cloned()
+            .collect();
+        let deactivated: HashSet<ModRef> = mod_list
+            .iter()
+            .filter(|mod_ref| {
+                !matches!(mod_ref, ModRef::Disabled(_)) && !server_list.contains(mod_ref)
+            })
+            .cloned()
+            .collect();
+
+        // Disabled mods are kept, unless the server needs them
+        let disabled: Vec<ModRef> = mod_list
+            .iter()
+            .filter(|mod_ref| match mod_ref {
+                ModRef::Disabled(idx) => !server_list.contains(&ModRef::Installed(*idx)),
+                _ => false,
+            })
+            .cloned()
+            .collect();
+        let mut new_list = server_list;
+        new_list.extend(disabled);
+        if activated.is_empty()
+            && deactivated.is_empty()
+            && downloads.is_empty()
+            && mod_list == new_list
+        {
+            message_default(MSG_SERVER_MODS_MATCH);
+            return Ok(None);
+        }
+
+        if !downloads.is_empty() {
+            Rc::clone(&self.mod_directory).resolve(&mut downloads);
+        }
+        let mut prompt = format!(
+            "{} {}?{}{}",
+            PROMPT_MATCH_SERVER_MODS,
+            server_mods.server_name,
+            join_mod_names(TXT_ACTIVATED_MODS, &installed_mods, activated),
+            join_mod_names(TXT_DEACTIVATED_MODS, &installed_mods, deactivated),
+        );
+        if !downloads.is_empty() {
+            prompt.push_str("\n\n");
+            prompt.push_str(TXT_DOWNLOADED_MODS);
+            for (mod_id, name) in downloads.iter() {
+                prompt.push('\n');
+                match name {
+                    Some(name) => prompt.push_str(name),
+                    None => prompt.push_str(&format!("Steam Workshop item {}", mod_id)),
+                }
+            }
+        }
+        if !prompt_confirm(&prompt) {
+            return Ok(None);
+        }
+
+        let backup_path = self.game.mod_list_backup_path();
+        self.game.save_mod_list_to(&backup_path, &mod_list)?;
+        info!(
+            self.logger,
+            "Matching server mods";
+            "server" => &server_mods.server_name,
+            "backup_path" => backup_path.display(),
+        );
+        self.game.save_mod_list(&new_list)?;
+
+        for (mod_id, _) in downloads {
+            if let Err(err) = self.subscribe_mod(mod_id) {
+                warn!(self.logger, "Error subscribing to mod"; "mod_id" => mod_id, "error" => %err);
+                alert_error(ERR_SUBSCRIBING_TO_MOD, &err);
+            }
+        }
+
+        Ok(Some(new_list))
+    }
+
+    pub fn compare_server_mods(&self, server_mod_ids: &[u64]) -> Result<ServerModComparison> {
+        let mod_list = self.game.load_mod_list()?;
+        let installed_mods = self.game.installed_mods();
+        let branch = self.game.branch();
+
+        let mut matched_ids = HashSet::new();
+        let mut extra = Vec::new();
+        let mut matching = Vec::new();
+        for mod_ref in mod_list.iter() {
+            if matches!(mod_ref, ModRef::Disabled(_)) {
+                continue;
+            }
+            let steam_id = installed_mods
+                .get(mod_ref)
+                .and_then(|entry| entry.info.as_ref().ok())
+                .and_then(|info| info.steam_file_id(branch))
+                .filter(|id| server_mod_ids.contains(id));
+            let name = mod_ref_name(&installed_mods, mod_ref);
+            match steam_id {
+                Some(id) => {
+                    matched_ids.insert(id);
+                    matching.push(name);
+                }
+                None => extra.push((mod_ref.clone(), name)),
             }
         }
 
-        fixed_all
+        let mut missing: Vec<(u64, Option<String>)> = server_mod_ids
+            .iter()
+            .filter(|id| !matched_ids.contains(id))
+            .map(|&id| (id, None))
+            .collect();
+        if !missing.is_empty() {
+            Rc::clone(&self.mod_directory).resolve(&mut missing);
+        }
+        let missing = missing
+            .into_iter()
+            .map(|(id, name)| {
+                let name = name.unwrap_or_else(|| format!("Steam Workshop item {}", id));
+                (id, name)
+            })
+            .collect();
+
+        Ok(ServerModComparison {
+            missing,
+            extra,
+            matching,
+        })
+    }
+
+    pub fn deactivate_mods(&self, mods: &[ModRef]) -> Result<()> {
+        let mut mod_list = self.game.load_mod_list()?;
+        let backup_path = self.game.mod_list_backup_path();
+        self.game.save_mod_list_to(&backup_path, &mod_list)?;
+        info!(
+            self.logger,
+            "Deactivating mods";
+            "count" => mods.len(),
+            "backup_path" => backup_path.display(),
+        );
+
+        mod_list.retain(|mod_ref| !mods.contains(mod_ref));
+        self.game.save_mod_list(&mod_list)
+    }
+
+    pub fn load_mod_stats(&self, idx: usize) {
+        let installed_mods = self.game.installed_mods();
+        let entry = &installed_mods[idx];
+        if entry.stats().is_some() {
+            return;
+        }
+        let mod_id = match &entry.info {
+            Ok(info) => info.steam_file_id(self.game.branch()),
+            Err(_) => None,
+        };
+        let Some(mod_id) = mod_id else {
+            return;
+        };
+        if !self.stats_requested.borrow_mut().insert(idx) {
+            return;
+        }
+
+        let logger = self.logger.clone();
+        let game = Arc::clone(&self.game);
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            match fetch_mod_stats(&game, mod_id).await {
+                Ok(stats) => {
+                    tx.send(ModStatsLoaded(idx, stats)).ok();
+                }
+                Err(err) => warn!(
+                    logger,
+                    "Error fetching workshop stats";
+                    "mod_id" => mod_id,
+                    "error" => %err,
+                ),
+            }
+        });
     }
 
     pub fn resolve_mods(&self, mods: &mut [(u64, Option<String>)]) {
         Rc::clone(&self.mod_directory).resolve(mods);
     }
 
+    fn mod_subscribed(&self, mod_id: u64, result: Result<PathBuf>) {
+        let mod_dir = match result {
+            Ok(mod_dir) => mod_dir,
+            Err(err) => {
+                warn!(self.logger, "Error subscribing to mod"; "mod_id" => mod_id, "error" => %err);
+                alert_error(ERR_SUBSCRIBING_TO_MOD, &err);
+                return;
+            }
+        };
+
+        info!(
+            self.logger,
+            "Subscribed mod downloaded";
+            "mod_id" => mod_id,
+            "mod_dir" => mod_dir.display(),
+        );
+        if let Err(err) = self.game.add_installed_mods(&mod_dir, ModProvenance::Steam) {
+            warn!(self.logger, "Error adding subscribed mod"; "mod_id" => mod_id, "error" => %err);
+            alert_error(ERR_ADDING_SUBSCRIBED_MOD, &err);
+            return;
+        }
+        self.check_mod_updates();
+        self.tx.send(InstalledModsChanged).ok();
+        message_default(MSG_MOD_SUBSCRIBED);
+    }
+
     fn detect_single_player_mod_mismatch(
         &self,
         mod_list: Vec<ModRef>,
         map_id: usize,
     ) -> Result<Option<ModMismatch>> {
         let installed_mods = self.game.installed_mods();
-        let mut active_mods: HashSet<ModRef> = mod_list.into_iter().collect();
+        let active_mods: HashSet<ModRef> = mod_list
+            .into_iter()
+            .filter(|mod_ref| !matches!(mod_ref, ModRef::Disabled(_)))
+            .collect();
 
         let db_path = self.game.in_progress_game_path(map_id);
         let db_metadata = std::fs::metadata(&db_path)?;
@@ -201,47 +695,94 @@ impl ModManager {
 
         let mut required_folders = HashMap::new();
         for controller in mod_controllers {
-            if let Some(captures) = MOD_CTRL_FOLDER_REGEX.captures(&controller) {
-                let folder = captures.get(1).unwrap().as_str();
+            if let Some(folder) = mod_controller_folder(&controller) {
                 required_folders.insert(folder.to_string(), false);
             }
         }
 
-        let mut added_mods = HashSet::new();
-        for mod_ref in active_mods.drain() {
-            let info = installed_mods
-                .get(&mod_ref)
-                .and_then(|entry| entry.info.as_ref().ok());
-            if let Some(info) = info {
-                if let Some(active) = required_folders.get_mut(&info.folder_name) {
-                    *active = true;
-                    continue;
-                }
+        Ok(find_mod_mismatch(
+            &installed_mods,
+            active_mods,
+            required_folders,
+        ))
+    }
+}
+
+struct ModMismatch {
+    missing_mods: HashSet<ModRef>,
+    added_mods: HashSet<ModRef>,
+}
+
+fn find_mod_mismatch(
+    installed_mods: &Mods,
+    active_mods: HashSet<ModRef>,
+    mut required_folders: HashMap<String, bool>,
+) -> Option<ModMismatch> {
+    // Match by folder name first, so that the fallbacks can't steal an exact match
+    let mut unmatched_mods = Vec::new();
+    for mod_ref in active_mods {
+        let info = installed_mods
+            .get(&mod_ref)
+            .and_then(|entry| entry.info.as_ref().ok());
+        if let Some(info) = info {
+            if let Some(active) = required_folders.get_mut(&info.folder_name) {
+                *active = true;
+                continue;
             }
-            added_mods.insert(mod_ref);
         }
+        unmatched_mods.push(mod_ref);
+    }
 
-        let mut missing_mods = HashSet::new();
-        for (folder, active) in required_folders.drain() {
-            if !active {
-                missing_mods.insert(installed_mods.by_folder(folder));
+    let mut added_mods = HashSet::new();
+    for mod_ref in unmatched_mods {
+        let info = installed_mods
+            .get(&mod_ref)
+            .and_then(|entry| entry.info.as_ref().ok());
+        let fallback = info.and_then(|info| {
+            required_folders
+                .iter_mut()
+                .filter(|(_, active)| !**active)
+                .find(|(folder, _)| folder.eq_ignore_ascii_case(&info.name))
+                .map(|(_, active)| active)
+        });
+        match fallback {
+            Some(active) => *active = true,
+            None => {
+                added_mods.insert(mod_ref);
             }
         }
+    }
 
-        if added_mods.is_empty() && missing_mods.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(ModMismatch {
-                missing_mods,
-                added_mods,
-            }))
+    let mut missing_mods = HashSet::new();
+    for (folder, active) in required_folders {
+        if active {
+            continue;
+        }
+        // A renamed folder only counts as a match if the mod is active, otherwise the folder
+        // from the save is what's missing
+        let mod_ref = installed_mods.by_folder(folder);
+        let candidate = match &mod_ref {
+            ModRef::UnknownFolder(folder) => {
+                installed_mods.by_folder_fuzzy(folder, MAX_FOLDER_EDIT_DISTANCE)
+            }
+            mod_ref => Some(mod_ref.clone()),
+        };
+        if let Some(candidate) = candidate {
+            if added_mods.remove(&candidate) {
+                continue;
+            }
         }
+        missing_mods.insert(mod_ref);
     }
-}
 
-struct ModMismatch {
-    missing_mods: HashSet<ModRef>,
-    added_mods: HashSet<ModRef>,
+    if added_mods.is_empty() && missing_mods.is_empty() {
+        None
+    } else {
+        Some(ModMismatch {
+            missing_mods,
+            added_mods,
+        })
+    }
 }
 
 fn push_name(s: &mut String, entry: &ModEntry) {
@@ -262,21 +803,122 @@ fn join_mod_names(heading: &str, mods: &Mods, refs: HashSet<ModRef>) -> String {
     result.push_str(heading);
     for mod_ref in refs {
         result.push('\n');
-        match mod_ref {
-            ModRef::Installed(idx) => push_name(&mut result, &mods[idx]),
-            ModRef::Custom(entry) => push_name(&mut result, &entry),
-            ModRef::UnknownFolder(folder) => result.push_str(&format!("??? ({})", folder)),
-            ModRef::UnknownPakPath(path) => result.push_str(&format!("??? ({})", path.display())),
-        };
+        push_mod_ref_name(&mut result, mods, &mod_ref);
     }
     result
 }
 
+fn push_mod_ref_name(s: &mut String, mods: &Mods, mod_ref: &ModRef) {
+    match mod_ref {
+        ModRef::Installed(idx) | ModRef::Disabled(idx) => push_name(s, &mods[*idx]),
+        ModRef::Custom(entry) => push_name(s, entry),
+        ModRef::UnknownFolder(folder) => s.push_str(&format!("??? ({})", folder)),
+        ModRef::UnknownPakPath(path) => s.push_str(&format!("??? ({})", path.display())),
+    };
+}
+
+fn mod_ref_name(mods: &Mods, mod_ref: &ModRef) -> String {
+    let mut name = String::new();
+    push_mod_ref_name(&mut name, mods, mod_ref);
+    name
+}
+
+fn mod_stats_path(game: &Game) -> PathBuf {
+    game.save_path().join(MOD_STATS_FILENAME)
+}
+
+fn activation_key(entry: &ModEntry) -> Option<&str> {
+    entry.pak_path.file_stem().and_then(|stem| stem.to_str())
+}
+
+fn load_activation_counts(path: &Path) -> Result<HashMap<String, u32>> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+}
+
+fn save_activation_counts(path: &Path, counts: &HashMap<String, u32>) -> Result<()> {
+    serde_json::to_writer_pretty(BufWriter::new(File::create(path)?), counts)?;
+    Ok(())
+}
+
+const MAX_FOLDER_EDIT_DISTANCE: usize = 2;
+const MOD_STATS_FILENAME: &str = "mod_stats.json";
 const PROMPT_SP_MOD_MISMATCH: &str =
     "It looks like your mod list doesn't match this game. Launch anyway?";
 const TXT_MISSING_MODS: &str = "Missing mods:";
 const TXT_ADDED_MODS: &str = "Added mods:";
+const PROMPT_ACTIVATE_SERVER_MODS: &str = "This server requires mods that are not active. \
+    Activate them before joining? Your current mod list will be saved to modlist.auto.txt.";
+const TXT_ACTIVATED_MODS: &str = "Mods to activate:";
+const TXT_DEACTIVATED_MODS: &str = "Mods to deactivate:";
+const TXT_DOWNLOADED_MODS: &str = "Mods to download:";
+const PROMPT_MATCH_SERVER_MODS: &str =
+    "Do you want to change the mod list to match the mods used by";
+const MSG_SERVER_MODS_MATCH: &str = "The mod list already matches the server's mods.";
+const MSG_MOD_SUBSCRIBED: &str =
+    "The mod has been downloaded and added to the list of available mods.";
+const ERR_ADDING_SUBSCRIBED_MOD: &str =
+    "The mod has been downloaded, but there was an error while adding it to the list of available \
+    mods.";
+const ERR_SUBSCRIBING_TO_MOD: &str = "Error while subscribing to the mod.";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn required(folders: &[&str]) -> HashMap<String, bool> {
+        folders
+            .iter()
+            .map(|folder| (folder.to_string(), false))
+            .collect()
+    }
+
+    #[test]
+    fn mismatch_is_none_when_folders_match() {
+        let mods = Mods::for_test(&[("Alpha", "AlphaMod"), ("Beta", "BetaMod")]);
+        let active = HashSet::from([mods.by_folder("AlphaMod"), mods.by_folder("BetaMod")]);
+        assert!(find_mod_mismatch(&mods, active, required(&["AlphaMod", "BetaMod"])).is_none());
+    }
+
+    #[test]
+    fn mismatch_falls_back_to_mod_name() {
+        let mods = Mods::for_test(&[("Alpha", "AlphaMod")]);
+        let active = HashSet::from([mods.by_folder("AlphaMod")]);
+        assert!(find_mod_mismatch(&mods, active, required(&["alpha"])).is_none());
+    }
 
-lazy_static! {
-    static ref MOD_CTRL_FOLDER_REGEX: Regex = Regex::new("/Game/Mods/([^/]+)/.*").unwrap();
+    #[test]
+    fn mismatch_falls_back_to_similar_folder_of_active_mod() {
+        let mods = Mods::for_test(&[("Alpha", "AlphaMod")]);
+        let active = HashSet::from([mods.by_folder("AlphaMod")]);
+        assert!(find_mod_mismatch(&mods, active, required(&["Alpha_Mod"])).is_none());
+    }
+
+    #[test]
+    fn mismatch_reports_save_folder_when_similar_mod_is_inactive() {
+        let mods = Mods::for_test(&[("Alpha", "AlphaMod")]);
+        let mismatch = find_mod_mismatch(&mods, HashSet::new(), required(&["Alpha_Mod"])).unwrap();
+        assert_eq!(
+            mismatch.missing_mods,
+            HashSet::from([ModRef::UnknownFolder("Alpha_Mod".to_string())])
+        );
+        assert!(mismatch.added_mods.is_empty());
+    }
+
+    #[test]
+    fn mismatch_reports_added_and_missing_mods() {
+        let mods = Mods::for_test(&[("Alpha", "AlphaMod"), ("Beta", "BetaMod")]);
+        let active = HashSet::from([mods.by_folder("AlphaMod")]);
+        let mismatch = find_mod_mismatch(&mods, active, required(&["BetaMod"])).unwrap();
+        assert_eq!(
+            mismatch.missing_mods,
+            HashSet::from([mods.by_folder("BetaMod")])
+        );
+        assert_eq!(
+            mismatch.added_mods,
+            HashSet::from([mods.by_folder("AlphaMod")])
+        );
+    }
 }
@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::game::{Game, ModEntry};
+
+pub struct ModNotes {
+    path: PathBuf,
+    notes: BTreeMap<String, String>,
+}
+
+impl ModNotes {
+    pub fn new(game: &Game) -> Self {
+        Self {
+            path: game.save_path().join(MOD_NOTES_FILENAME),
+            notes: BTreeMap::new(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn load(&mut self) -> Result<()> {
+        if !self.path.exists() {
+            self.notes = BTreeMap::new();
+            return Ok(());
+        }
+        let file = File::open(&self.path)?;
+        self.notes = serde_json::from_reader(BufReader::new(file))?;
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let file = File::create(&self.path)?;
+        serde_json::to_writer_pretty(BufWriter::new(file), &self.notes)?;
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.notes.get(key).map(String::as_str)
+    }
+
+    pub fn set(&mut self, key: String, note: &str) -> bool {
+        let note = note.trim();
+        if note.is_empty() {
+            self.notes.remove(&key).is_some()
+        } else if self.get(&key) == Some(note) {
+            false
+        } else {
+            self.notes.insert(key, note.to_string());
+            true
+        }
+    }
+}
+
+pub fn mod_notes_key(mod_info: &ModEntry) -> Option<String> {
+    mod_info
+        .pak_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+}
+
+const MOD_NOTES_FILENAME: &str = "mod_notes.json";
@@ -1,10 +1,15 @@
 use std::io::{Error, Result};
 use std::net::{IpAddr, ToSocketAddrs, UdpSocket};
+use std::time::{Duration, Instant};
 
 use reqwest::{Client, ClientBuilder};
 
 use crate::game::Game;
 
+mod traceroute;
+
+pub use self::traceroute::{trace_route, Hop};
+
 pub fn is_valid_ip(ip: &IpAddr) -> bool {
     match ip {
         IpAddr::V4(ip) => {
@@ -30,6 +35,12 @@ pub fn http_client_builder(game: &Game) -> ClientBuilder {
     ))
 }
 
+pub async fn measure_head_request(client: &Client, url: &str) -> reqwest::Result<Duration> {
+    let start = Instant::now();
+    client.head(url).send().await?;
+    Ok(start.elapsed())
+}
+
 #[cfg(not(windows))]
 #[inline(always)]
 pub fn bind_udp_socket(addr: impl ToSocketAddrs) -> Result<UdpSocket> {
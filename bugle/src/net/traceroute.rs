@@ -0,0 +1,271 @@
+use std::io::Result;
+use std::net::Ipv4Addr;
+use std::time::Duration;
+
+pub struct Hop {
+    pub ttl: u8,
+    pub addr: Option<Ipv4Addr>,
+    pub latency: Option<Duration>,
+}
+
+pub fn trace_route(
+    dest: Ipv4Addr,
+    max_hops: u8,
+    timeout: Duration,
+    mut on_hop: impl FnMut(Hop) -> bool,
+) -> Result<()> {
+    let prober = Prober::new()?;
+    for ttl in 1..=max_hops {
+        let reply = prober.probe(dest, ttl, timeout)?;
+        let reached = reply.map(|(addr, _)| addr == dest).unwrap_or_default();
+        let hop = Hop {
+            ttl,
+            addr: reply.map(|(addr, _)| addr),
+            latency: reply.map(|(_, latency)| latency),
+        };
+        if !on_hop(hop) || reached {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+struct Prober {
+    handle: winapi::um::winnt::HANDLE,
+}
+
+#[cfg(windows)]
+impl Prober {
+    fn new() -> Result<Self> {
+        use winapi::um::handleapi::INVALID_HANDLE_VALUE;
+
+        let handle = unsafe { IcmpCreateFile() };
+        if handle == INVALID_HANDLE_VALUE {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(Self { handle })
+    }
+
+    fn probe(
+        &self,
+        dest: Ipv4Addr,
+        ttl: u8,
+        timeout: Duration,
+    ) -> Result<Option<(Ipv4Addr, Duration)>> {
+        use std::ptr::null_mut;
+
+        use winapi::um::ipexport::{ICMP_ECHO_REPLY, IP_OPTION_INFORMATION};
+        use winapi::um::winternl::IO_STATUS_BLOCK;
+
+        let mut options: IP_OPTION_INFORMATION = unsafe { std::mem::zeroed() };
+        options.Ttl = ttl;
+
+        let mut reply_buf = vec![
+            0u8;
+            std::mem::size_of::<ICMP_ECHO_REPLY>()
+                + PAYLOAD.len()
+                + ICMP_ERROR_SIZE
+                + std::mem::size_of::<IO_STATUS_BLOCK>()
+        ];
+        let num_replies = unsafe {
+            IcmpSendEcho2(
+                self.handle,
+                null_mut(),
+                null_mut(),
+                null_mut(),
+                u32::from_ne_bytes(dest.octets()),
+                PAYLOAD.as_ptr() as _,
+                PAYLOAD.len() as _,
+                &mut options,
+                reply_buf.as_mut_ptr() as _,
+                reply_buf.len() as _,
+                timeout.as_millis() as _,
+            )
+        };
+        if num_replies == 0 {
+            let err = std::io::Error::last_os_error();
+            return match err.raw_os_error() {
+                Some(IP_REQ_TIMED_OUT) => Ok(None),
+                _ => Err(err),
+            };
+        }
+
+        let reply: ICMP_ECHO_REPLY =
+            unsafe { std::ptr::read_unaligned(reply_buf.as_ptr() as *const _) };
+        match reply.Status {
+            IP_SUCCESS | IP_TTL_EXPIRED_TRANSIT => Ok(Some((
+                Ipv4Addr::from(reply.Address.to_ne_bytes()),
+                Duration::from_millis(reply.RoundTripTime as _),
+            ))),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Drop for Prober {
+    fn drop(&mut self) {
+        unsafe { IcmpCloseHandle(self.handle) };
+    }
+}
+
+// IP Helper ICMP functions, which winapi does not expose
+#[cfg(windows)]
+#[link(name = "iphlpapi")]
+extern "system" {
+    fn IcmpCreateFile() -> winapi::um::winnt::HANDLE;
+    fn IcmpCloseHandle(handle: winapi::um::winnt::HANDLE) -> winapi::shared::minwindef::BOOL;
+    fn IcmpSendEcho2(
+        handle: winapi::um::winnt::HANDLE,
+        event: winapi::um::winnt::HANDLE,
+        apc_routine: *mut winapi::ctypes::c_void,
+        apc_context: *mut winapi::ctypes::c_void,
+        dest: winapi::um::ipexport::IPAddr,
+        request_data: *mut winapi::ctypes::c_void,
+        request_size: winapi::shared::minwindef::WORD,
+        request_options: *mut winapi::um::ipexport::IP_OPTION_INFORMATION,
+        reply_buffer: *mut winapi::ctypes::c_void,
+        reply_size: winapi::shared::minwindef::DWORD,
+        timeout: winapi::shared::minwindef::DWORD,
+    ) -> winapi::shared::minwindef::DWORD;
+}
+
+#[cfg(not(windows))]
+struct Prober {
+    socket: socket2::Socket,
+    id: u16,
+}
+
+#[cfg(not(windows))]
+impl Prober {
+    fn new() -> Result<Self> {
+        use std::io::{Error, ErrorKind};
+
+        use socket2::{Domain, Protocol, Socket, Type};
+
+        let socket = match Socket::new(Domain::IPV4, Type::RAW, Some(Protocol::ICMPV4)) {
+            Ok(socket) => socket,
+            Err(err) if err.kind() == ErrorKind::PermissionDenied => {
+                return Err(Error::new(
+                    ErrorKind::PermissionDenied,
+                    ERR_RAW_SOCKET_DENIED,
+                ));
+            }
+            Err(err) => return Err(err),
+        };
+        Ok(Self {
+            socket,
+            id: std::process::id() as u16,
+        })
+    }
+
+    fn probe(
+        &self,
+        dest: Ipv4Addr,
+        ttl: u8,
+        timeout: Duration,
+    ) -> Result<Option<(Ipv4Addr, Duration)>> {
+        use std::io::ErrorKind;
+        use std::mem::MaybeUninit;
+        use std::net::SocketAddrV4;
+        use std::time::Instant;
+
+        let seq = ttl as u16;
+        let mut request = vec![ICMP_ECHO_REQUEST, 0, 0, 0];
+        request.extend_from_slice(&self.id.to_be_bytes());
+        request.extend_from_slice(&seq.to_be_bytes());
+        request.extend_from_slice(PAYLOAD);
+        let checksum = icmp_checksum(&request);
+        request[2..4].copy_from_slice(&checksum.to_be_bytes());
+
+        self.socket.set_ttl(ttl as _)?;
+        let start = Instant::now();
+        self.socket
+            .send_to(&request, &SocketAddrV4::new(dest, 0).into())?;
+
+        let deadline = start + timeout;
+        let mut buf = [MaybeUninit::<u8>::uninit(); 1500];
+        loop {
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            self.socket.set_read_timeout(Some(deadline - now))?;
+            let (len, from) = match self.socket.recv_from(&mut buf) {
+                Ok(received) => received,
+                Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                    return Ok(None)
+                }
+                Err(err) => return Err(err),
+            };
+            let latency = start.elapsed();
+            let packet: Vec<u8> = buf[..len]
+                .iter()
+                .map(|byte| unsafe { byte.assume_init() })
+                .collect();
+            let Some(from) = from.as_socket_ipv4() else {
+                continue;
+            };
+            if self.is_reply_to(&packet, seq) {
+                return Ok(Some((*from.ip(), latency)));
+            }
+        }
+    }
+
+    fn is_reply_to(&self, packet: &[u8], seq: u16) -> bool {
+        let Some(icmp) = strip_ip_header(packet) else {
+            return false;
+        };
+        let echo = match icmp.first() {
+            Some(&ICMP_ECHO_REPLY) => icmp,
+            Some(&ICMP_TIME_EXCEEDED) => match icmp.get(8..).and_then(strip_ip_header) {
+                Some(original) => original,
+                None => return false,
+            },
+            _ => return false,
+        };
+        (echo.len() >= 8)
+            && (echo[4..6] == self.id.to_be_bytes())
+            && (echo[6..8] == seq.to_be_bytes())
+    }
+}
+
+#[cfg(not(windows))]
+fn strip_ip_header(packet: &[u8]) -> Option<&[u8]> {
+    let header_len = ((*packet.first()? & 0x0f) as usize) * 4;
+    packet.get(header_len..)
+}
+
+#[cfg(not(windows))]
+fn icmp_checksum(data: &[u8]) -> u16 {
+    let mut sum = data
+        .chunks(2)
+        .map(|chunk| u16::from_be_bytes([chunk[0], *chunk.get(1).unwrap_or(&0)]) as u32)
+        .sum::<u32>();
+    while (sum >> 16) != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+const PAYLOAD: &[u8] = b"BUGLE traceroute";
+
+#[cfg(windows)]
+const ICMP_ERROR_SIZE: usize = 8;
+#[cfg(windows)]
+const IP_SUCCESS: u32 = 0;
+#[cfg(windows)]
+const IP_REQ_TIMED_OUT: i32 = 11010;
+#[cfg(windows)]
+const IP_TTL_EXPIRED_TRANSIT: u32 = 11013;
+
+#[cfg(not(windows))]
+const ERR_RAW_SOCKET_DENIED: &str =
+    "Tracing the route requires a raw ICMP socket. Run as root or grant the CAP_NET_RAW capability.";
+#[cfg(not(windows))]
+const ICMP_ECHO_REPLY: u8 = 0;
+#[cfg(not(windows))]
+const ICMP_ECHO_REQUEST: u8 = 8;
+#[cfg(not(windows))]
+const ICMP_TIME_EXCEEDED: u8 = 11;
@@ -8,14 +8,33 @@ use anyhow::Result;
 use fs_extra::file::{copy_with_progress, CopyOptions};
 
 use crate::bus::AppBus;
-use crate::game::{create_empty_db, Game};
-use crate::gui::{PopulateSinglePlayerGames, TaskProgressMonitor, TaskProgressUpdate};
+use crate::config::ConfigManager;
+use crate::game::{
+    create_empty_db, db_metadata, db_metadata_path, mod_controller_folder, write_db_metadata, Game,
+    GameDBMetadata,
+};
+use crate::gui::{
+    prompt_confirm, PopulateSinglePlayerGames, TaskProgressMonitor, TaskProgressUpdate,
+};
 
 pub struct SavedGamesManager {
     bus: Rc<RefCell<AppBus>>,
+    config: Rc<ConfigManager>,
     game: Arc<Game>,
 }
 
+#[derive(Default)]
+pub struct ModDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl ModDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
 pub enum SaveGame {
     InProgress { map_id: usize },
     Backup { name: PathBuf },
@@ -23,8 +42,24 @@ pub enum SaveGame {
 }
 
 impl SavedGamesManager {
-    pub fn new(bus: Rc<RefCell<AppBus>>, game: Arc<Game>) -> Rc<Self> {
-        Rc::new(Self { bus, game })
+    pub fn new(bus: Rc<RefCell<AppBus>>, config: Rc<ConfigManager>, game: Arc<Game>) -> Rc<Self> {
+        Rc::new(Self { bus, config, game })
+    }
+
+    pub fn confirm_free_space(&self) -> bool {
+        let min_free_gb = self.config.get().min_free_space_gb.0;
+        let Ok(available) = fs2::available_space(self.game.save_path()) else {
+            return true;
+        };
+        let available_gb = available as f64 / BYTES_PER_GB;
+        if available_gb >= min_free_gb as f64 {
+            return true;
+        }
+        prompt_confirm(&format!(
+            "Only {:.1} GB of disk space is left for saved games, which is less than the {:.1} GB \
+                you asked to be warned about. Do you want to continue anyway?",
+            available_gb, min_free_gb
+        ))
     }
 
     pub fn list_games(&self) {
@@ -48,6 +83,7 @@ impl SavedGamesManager {
         {
             let tx = self.bus.borrow().sender().clone();
             let result_cell = Arc::clone(&result_cell);
+            let dest_path = dest_path.clone();
             tokio::spawn(async move {
                 let opts = CopyOptions::new().overwrite(true);
                 let result = copy_with_progress(src_path, dest_path, &opts, |progress| {
@@ -72,6 +108,7 @@ impl SavedGamesManager {
 
         if result_cell.get().is_some() {
             Arc::into_inner(result_cell).unwrap().take().unwrap()?;
+            write_db_metadata(&dest_path).ok();
             return Ok(());
         }
 
@@ -84,9 +121,49 @@ impl SavedGamesManager {
         monitor.run();
 
         Arc::into_inner(result_cell).unwrap().take().unwrap()?;
+
+        // The metadata sidecar is just a cache, so failing to write it is not an error
+        write_db_metadata(&dest_path).ok();
         Ok(())
     }
 
+    pub fn save_metadata(&self, save: SaveGame) -> Result<GameDBMetadata> {
+        db_metadata(self.save_path(save))
+    }
+
+    pub fn compute_mod_diff(
+        &self,
+        in_progress_meta: &GameDBMetadata,
+        backup_meta: &GameDBMetadata,
+    ) -> ModDiff {
+        if in_progress_meta.mod_list_hash == backup_meta.mod_list_hash {
+            return ModDiff::default();
+        }
+
+        let diff = |lhs: &[String], rhs: &[String]| -> Vec<String> {
+            lhs.iter()
+                .filter(|controller| !rhs.contains(controller))
+                .map(|controller| self.mod_display_name(controller))
+                .collect()
+        };
+        ModDiff {
+            added: diff(&in_progress_meta.mod_list, &backup_meta.mod_list),
+            removed: diff(&backup_meta.mod_list, &in_progress_meta.mod_list),
+        }
+    }
+
+    fn mod_display_name(&self, controller: &str) -> String {
+        let Some(folder) = mod_controller_folder(controller) else {
+            return controller.to_string();
+        };
+        let installed_mods = self.game.installed_mods();
+        installed_mods
+            .get(&installed_mods.by_folder(folder))
+            .and_then(|entry| entry.info.as_ref().ok())
+            .map(|info| info.name.clone())
+            .unwrap_or_else(|| folder.to_string())
+    }
+
     fn save_path(&self, save_src: SaveGame) -> PathBuf {
         match save_src {
             SaveGame::InProgress { map_id } => self
@@ -99,7 +176,11 @@ impl SavedGamesManager {
     }
 
     pub fn delete_backup(&self, backup_name: PathBuf) -> Result<()> {
-        std::fs::remove_file(self.game.save_path().join(backup_name))?;
+        let db_path = self.game.save_path().join(backup_name);
+        std::fs::remove_file(&db_path)?;
+        std::fs::remove_file(db_metadata_path(&db_path)).ok();
         Ok(())
     }
 }
+
+const BYTES_PER_GB: f64 = (1u64 << 30) as f64;
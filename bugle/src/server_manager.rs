@@ -1,33 +1,64 @@
 use std::cell::{Cell, RefCell};
 use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::path::Path;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use chrono::Utc;
 use dynabus::Bus;
+use fltk::app;
 use slog::{debug, error, trace, warn, Logger};
 use uuid::Uuid;
 
 use crate::bus::AppBus;
-use crate::game::{Game, ServerRef, Session};
-use crate::gui::{PopulateServers, ProcessPongs, UpdateLastSession, UpdateServer};
-use crate::servers::{Confidence, PingRequest, PingResponse, SavedServers, Server, Similarity};
+use crate::config::ConfigManager;
+use crate::game::{Game, MapRef, ServerRef, Session};
+use crate::gui::{AppendServers, PopulateServers, ProcessPongs, UpdateLastSession, UpdateServer};
+use crate::launcher::GameLaunched;
+use crate::servers::{
+    Confidence, MaintenanceWindow, PeakHours, PingHistory, PingRequest, PingResponse, PingTrend,
+    PrivateServerList, SavedServers, Server, ServerStats, Similarity, Uptime,
+};
 use crate::util::weak_cb;
-use crate::workers::{PongReceived, ServerLoaderWorker, ServersLoaded};
+use crate::workers::{
+    OfflineServersLoaded, PongsReceived, ServerBatchLoaded, ServerLoaderWorker, ServersLoaded,
+};
 use crate::Idle;
 
 pub struct ServerManager {
     logger: Logger,
     bus: Rc<RefCell<AppBus>>,
     game: Arc<Game>,
+    config: Rc<ConfigManager>,
     saved_servers: Option<RefCell<SavedServers>>,
+    server_stats: Option<Rc<RefCell<ServerStats>>>,
+    stats_save_pending: Rc<Cell<bool>>,
     is_loading: Cell<bool>,
     pong_accumulator: RefCell<Vec<PingResponse>>,
+    offline_servers: RefCell<HashSet<SocketAddr>>,
+    ping_history: RefCell<PingHistory>,
     worker: Arc<ServerLoaderWorker>,
 }
 
+#[derive(dynabus::Event)]
+pub struct ServerModsSelected(pub Option<ServerMods>);
+
+#[derive(Clone)]
+pub struct ServerMods {
+    pub server_name: String,
+    pub mod_ids: Vec<u64>,
+}
+
 impl ServerManager {
-    pub fn new(logger: &Logger, bus: Rc<RefCell<AppBus>>, game: Arc<Game>) -> Rc<Self> {
+    pub fn new(
+        logger: &Logger,
+        bus: Rc<RefCell<AppBus>>,
+        game: Arc<Game>,
+        config: Rc<ConfigManager>,
+    ) -> Rc<Self> {
         let logger = logger.clone();
 
         let saved_servers = match SavedServers::new() {
@@ -48,6 +79,24 @@ impl ServerManager {
             }
         };
 
+        let server_stats = match ServerStats::new() {
+            Ok(mut stats) => {
+                if let Err(err) = stats.load() {
+                    warn!(
+                        logger,
+                        "Error loading the server stats";
+                        "path" => stats.path().display(),
+                        "error" => %err,
+                    );
+                }
+                Some(Rc::new(RefCell::new(stats)))
+            }
+            Err(err) => {
+                warn!(logger, "Error opening the server stats"; "error" => %err);
+                None
+            }
+        };
+
         let worker =
             ServerLoaderWorker::new(&logger, Arc::clone(&game), bus.borrow().sender().clone());
 
@@ -55,9 +104,14 @@ impl ServerManager {
             logger,
             bus,
             game,
+            config,
             saved_servers,
+            server_stats,
+            stats_save_pending: Rc::new(Cell::new(false)),
             is_loading: Cell::new(false),
             pong_accumulator: RefCell::new(Vec::new()),
+            offline_servers: RefCell::new(HashSet::new()),
+            ping_history: RefCell::new(PingHistory::default()),
             worker,
         });
 
@@ -67,26 +121,63 @@ impl ServerManager {
                 [this] => |ServersLoaded(payload)| this.servers_loaded(payload)
             ));
             bus.subscribe_consumer(weak_cb!(
-                [this] => |PongReceived(pong)| this.pong_received(pong)
+                [this] => |ServerBatchLoaded { servers, loaded_buckets, total_buckets }| {
+                    this.batch_loaded(servers, loaded_buckets, total_buckets)
+                }
             ));
+            bus.subscribe_observer(weak_cb!([this] => |&PongsReceived| this.pongs_received()));
             bus.subscribe_observer(weak_cb!([this] => |&Idle| this.process_pongs()));
+            bus.subscribe_consumer(weak_cb!(
+                [this] => |OfflineServersLoaded(offline)| this.offline_servers_loaded(offline)
+            ));
+            bus.subscribe_observer(weak_cb!(
+                [this] => |launched: &GameLaunched| this.game_launched(launched)
+            ));
+        }
+
+        {
+            let config = this.config.get();
+            let browser_config = &config.server_browser;
+            if let Some(url) = browser_config.maintenance_status_url.as_ref() {
+                let interval_mins = browser_config.maintenance_check_mins.0.max(1);
+                this.worker.monitor_offline_servers(
+                    url.clone(),
+                    Duration::from_secs(interval_mins as u64 * 60),
+                );
+            }
         }
 
         this
     }
 
     pub fn load_server_list(&self) {
-        if let Some(servers) = self.saved_servers.as_ref() {
-            let servers = servers.borrow();
-            if !servers.is_empty() {
-                self.bus.borrow().publish(PopulateServers {
-                    payload: Ok(servers.iter().cloned().collect()),
-                    done: false,
-                });
-            }
-        }
+        // The fetched batches are appended to this list, so it needs to be published even if empty
+        let saved_servers = match self.saved_servers.as_ref() {
+            Some(servers) => servers.borrow().iter().cloned().collect(),
+            None => Vec::new(),
+        };
+        self.bus.borrow().publish(PopulateServers {
+            payload: Ok(saved_servers),
+            done: false,
+        });
         self.is_loading.set(true);
-        self.worker.load_servers();
+        let config = self.config.get();
+        let browser_cfg = &config.server_browser;
+        let private_list = browser_cfg.server_list_url.as_ref().map(|url| {
+            let auth = match browser_cfg.server_list_auth() {
+                Ok(auth) => auth,
+                Err(err) => {
+                    warn!(self.logger, "Error reading server list credentials"; "error" => %err);
+                    None
+                }
+            };
+            PrivateServerList {
+                url: url.clone(),
+                auth,
+            }
+        });
+        self.worker
+            .load_servers(private_list, browser_cfg.auto_remove_after_timeouts);
     }
 
     pub fn is_loading(&self) -> bool {
@@ -101,6 +192,79 @@ impl ServerManager {
         self.worker.ping_server(request)
     }
 
+    pub fn is_offline(&self, addr: SocketAddr) -> bool {
+        self.offline_servers.borrow().contains(&addr)
+    }
+
+    pub fn record_ping(&self, addr: SocketAddr, round_trip: Duration) -> PingTrend {
+        self.ping_history.borrow_mut().record(addr, round_trip)
+    }
+
+    pub fn server_selected(&self, server: Option<&Server>) {
+        let mods = server
+            .filter(|server| server.is_modded())
+            .map(|server| ServerMods {
+                server_name: server.name.clone(),
+                mod_ids: server.steam_mod_ids(),
+            });
+        self.bus.borrow().publish(ServerModsSelected(mods));
+    }
+
+    pub fn record_server_status(&self, addr: SocketAddr, online: bool) {
+        if let Some(stats) = self.server_stats.as_ref() {
+            stats.borrow_mut().record(addr, online, Utc::now());
+        }
+    }
+
+    pub fn save_server_stats(&self) {
+        let Some(stats) = self.server_stats.as_ref() else {
+            return;
+        };
+        if self.stats_save_pending.replace(true) {
+            return;
+        }
+
+        let stats = Rc::clone(stats);
+        let pending = Rc::clone(&self.stats_save_pending);
+        let logger = self.logger.clone();
+        app::add_timeout3(STATS_SAVE_DELAY, move |_| {
+            pending.set(false);
+            let snapshot = stats.borrow().snapshot();
+            let logger = logger.clone();
+            tokio::task::spawn_blocking(move || {
+                if let Err(err) = snapshot.save() {
+                    warn!(logger, "Error saving the server stats"; "error" => %err);
+                }
+            });
+        });
+    }
+
+    pub fn record_player_count(&self, addr: SocketAddr, players: usize) {
+        if let Some(stats) = self.server_stats.as_ref() {
+            stats.borrow_mut().record_players(addr, players, Utc::now());
+        }
+    }
+
+    pub fn server_peak_hours(&self, addr: SocketAddr) -> Option<PeakHours> {
+        let stats = self.server_stats.as_ref()?;
+        let peak_hours = stats.borrow().peak_hours(addr);
+        peak_hours
+    }
+
+    pub fn server_maintenance_windows(&self, addr: SocketAddr) -> Vec<MaintenanceWindow> {
+        let Some(stats) = self.server_stats.as_ref() else {
+            return vec![];
+        };
+        let windows = stats.borrow().maintenance_windows(addr);
+        windows
+    }
+
+    pub fn server_uptime(&self, addr: SocketAddr) -> Option<Uptime> {
+        let stats = self.server_stats.as_ref()?;
+        let uptime = stats.borrow().uptime(addr, Utc::now());
+        uptime
+    }
+
     pub fn can_save_servers(&self) -> bool {
         self.saved_servers.is_some()
     }
@@ -139,6 +303,34 @@ impl ServerManager {
         Ok(())
     }
 
+    pub fn export_saved_servers(&self, path: &Path) -> Result<()> {
+        let servers = self.available_saved_servers()?;
+        servers.borrow().export(path)
+    }
+
+    pub fn import_saved_servers(&self, path: &Path) -> Result<usize> {
+        let servers = self.available_saved_servers()?;
+        let mut servers = servers.borrow_mut();
+        let imported = servers.import(path)?;
+        servers.save()?;
+        let bus = self.bus.borrow();
+        for id in imported.iter() {
+            bus.sender()
+                .send(UpdateServer {
+                    idx: None,
+                    server: servers[*id].clone(),
+                })
+                .map_err(|_| anyhow!("Error updating the server list"))?;
+        }
+        Ok(imported.len())
+    }
+
+    fn available_saved_servers(&self) -> Result<&RefCell<SavedServers>> {
+        self.saved_servers
+            .as_ref()
+            .ok_or_else(|| anyhow!("Saved servers are not available"))
+    }
+
     fn servers_loaded(&self, mut payload: Result<Vec<Server>>) {
         match payload.as_mut() {
             Ok(servers) => {
@@ -192,8 +384,56 @@ impl ServerManager {
         });
     }
 
-    fn pong_received(&self, pong: PingResponse) {
-        self.pong_accumulator.borrow_mut().push(pong);
+    fn batch_loaded(&self, mut servers: Vec<Server>, loaded_buckets: usize, total_buckets: usize) {
+        if !self.is_loading.get() {
+            return;
+        }
+
+        let build_id = self.game.build_id();
+        for server in servers.iter_mut() {
+            server.validate_build(build_id);
+        }
+
+        // Saved servers are only merged once the whole list is in, so until then the batches are
+        // shown alongside them
+        self.bus.borrow().publish(AppendServers {
+            servers,
+            loaded_buckets,
+            total_buckets,
+        });
+    }
+
+    fn pongs_received(&self) {
+        self.pong_accumulator
+            .borrow_mut()
+            .extend(self.worker.take_pongs());
+    }
+
+    fn game_launched(&self, launched: &GameLaunched) {
+        {
+            let mut last_session = self.game.last_session();
+            match (launched.server_addr, launched.map_id) {
+                (Some(addr), _) => {
+                    // Keep the server details if we're rejoining the same server
+                    if let Some(Session::Online(ServerRef::Known(server))) = &*last_session {
+                        if server.game_addr() == Some(addr) {
+                            return;
+                        }
+                    }
+                    *last_session = Some(Session::Online(ServerRef::Unknown(addr)));
+                }
+                (None, Some(map_id)) => {
+                    *last_session = Some(Session::SinglePlayer(MapRef::Known { map_id }));
+                }
+                (None, None) => return,
+            }
+        }
+        self.bus.borrow().publish(UpdateLastSession);
+    }
+
+    fn offline_servers_loaded(&self, offline: HashSet<SocketAddr>) {
+        debug!(self.logger, "Updated offline servers"; "num_offline" => offline.len());
+        *self.offline_servers.borrow_mut() = offline;
     }
 
     fn process_pongs(&self) {
@@ -286,3 +526,5 @@ impl ServerManager {
         servers.extend(saved_servers.iter().cloned());
     }
 }
+
+const STATS_SAVE_DELAY: f64 = 60.0;
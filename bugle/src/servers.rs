@@ -3,12 +3,18 @@ mod filter;
 pub mod model;
 mod net;
 mod saved;
+mod stats;
+mod wipe;
 
 pub use self::favorites::{FavoriteServer, FavoriteServers};
 pub use self::filter::{EnumFilter, Filter, RangeFilter, TypeFilter};
 pub use self::model::{
-    Confidence, Mode, Ownership, Region, Server, ServerData, Similarity, SortCriteria, SortKey,
-    Validity,
+    Confidence, Mode, Ownership, PingHistory, PingTrend, Region, Server, ServerData, Similarity,
+    SortCriteria, SortKey, Validity,
+};
+pub use self::net::{
+    fetch_offline_servers, fetch_server_list, PingClient, PingRequest, PingResponse, PingResult,
+    PrivateServerList,
 };
-pub use self::net::{fetch_server_list, PingClient, PingRequest, PingResponse, PingResult};
 pub use self::saved::SavedServers;
+pub use self::stats::{MaintenanceWindow, PeakHours, ServerStats, Uptime};
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+use std::net::IpAddr;
 use std::str::FromStr;
 
 use ini_persist::load::{LoadProperty, ParseProperty};
@@ -38,6 +40,9 @@ pub struct Filter {
     #[ini(rename = "Mods", ignore_errors)]
     pub mods: Option<bool>,
 
+    #[ini(rename = "ModCount", ignore_errors)]
+    pub mod_count: Option<RangeFilter<u8>>,
+
     #[ini(rename = "Community", ignore_errors)]
     pub community: Option<EnumFilter<Community>>,
 
@@ -108,6 +113,22 @@ pub struct Filter {
     pub thrall_crafting_time_mult: Option<RangeFilter<Multiplier>>,
 }
 
+impl Filter {
+    pub fn settings_match(&self, server: &Server) -> bool {
+        SETTINGS_CHECKS
+            .iter()
+            .all(|(_, matches)| matches(self, server))
+    }
+
+    pub fn mismatched_settings(&self, server: &Server) -> Vec<&'static str> {
+        SETTINGS_CHECKS
+            .iter()
+            .filter(|(_, matches)| !matches(self, server))
+            .map(|(name, _)| *name)
+            .collect()
+    }
+}
+
 #[derive(
     Clone,
     Copy,
@@ -129,6 +150,8 @@ pub enum TypeFilter {
     Official,
     Private,
     Favorite,
+    Visited,
+    Saved,
 }
 
 impl Default for TypeFilter {
@@ -138,12 +161,14 @@ impl Default for TypeFilter {
 }
 
 impl TypeFilter {
-    pub fn matches(&self, server: &Server) -> bool {
+    pub fn matches(&self, server: &Server, visited: &HashSet<IpAddr>) -> bool {
         match self {
             Self::All => true,
             Self::Official => server.is_official(),
             Self::Private => !server.is_official(),
             Self::Favorite => server.favorite,
+            Self::Visited => visited.contains(&server.ip),
+            Self::Saved => server.is_saved(),
         }
     }
 }
@@ -215,3 +240,147 @@ impl<T: FromStr + Into<&'static str> + Copy + Eq> DisplayProperty for EnumFilter
         format!("{}{}", negate, self.value.into())
     }
 }
+
+trait PropertyFilter<T> {
+    fn matches(&self, getter: impl FnOnce() -> T) -> bool;
+}
+
+impl<T, F: PropertyFilter<T>> PropertyFilter<T> for Option<F> {
+    fn matches(&self, getter: impl FnOnce() -> T) -> bool {
+        self.as_ref().map_or(true, |filter| filter.matches(getter))
+    }
+}
+
+impl PropertyFilter<bool> for bool {
+    fn matches(&self, getter: impl FnOnce() -> bool) -> bool {
+        *self == getter()
+    }
+}
+
+impl<T: ParseProperty + DisplayProperty + Copy + PartialOrd> PropertyFilter<T> for RangeFilter<T> {
+    fn matches(&self, getter: impl FnOnce() -> T) -> bool {
+        self.matches(getter())
+    }
+}
+
+impl<T: FromStr + Into<&'static str> + Copy + Eq> PropertyFilter<T> for EnumFilter<T> {
+    fn matches(&self, getter: impl FnOnce() -> T) -> bool {
+        self.matches(getter())
+    }
+}
+
+type SettingsCheck = (&'static str, fn(&Filter, &Server) -> bool);
+
+const SETTINGS_CHECKS: &[SettingsCheck] = &[
+    ("Community", |filter, server| {
+        filter.community.matches(|| server.general.community)
+    }),
+    ("Clan max size", |filter, server| {
+        filter
+            .max_clan_size
+            .matches(|| server.general.max_clan_size)
+    }),
+    ("PVP building damage enabled", |filter, server| {
+        filter.raid_enabled.matches(|| server.general.raid_enabled)
+    }),
+    ("Time restrict building damage", |filter, server| {
+        filter
+            .raid_restricted
+            .matches(|| server.general.raid_restricted)
+    }),
+    ("Player XP rate multiplier", |filter, server| {
+        filter
+            .xp_rate_mult
+            .matches(|| server.progression.xp_rate_mult)
+    }),
+    ("Day cycle speed", |filter, server| {
+        filter
+            .day_cycle_speed_mult
+            .matches(|| server.daylight.day_cycle_speed_mult)
+    }),
+    ("Dawn/dusk time speed", |filter, server| {
+        filter
+            .dawn_dusk_speed_mult
+            .matches(|| server.daylight.dawn_dusk_speed_mult)
+    }),
+    ("Use catch up time", |filter, server| {
+        filter
+            .use_catch_up_time
+            .matches(|| server.daylight.use_catch_up_time)
+    }),
+    ("Stamina cost multiplier", |filter, server| {
+        filter
+            .stamina_cost_mult
+            .matches(|| server.survival.stamina_cost_mult)
+    }),
+    ("Player idle thirst multiplier", |filter, server| {
+        filter
+            .idle_thirst_mult
+            .matches(|| server.survival.idle_thirst_mult)
+    }),
+    ("Player active thirst multiplier", |filter, server| {
+        filter
+            .active_thirst_mult
+            .matches(|| server.survival.active_thirst_mult)
+    }),
+    ("Player idle hunger multiplier", |filter, server| {
+        filter
+            .idle_hunger_mult
+            .matches(|| server.survival.idle_hunger_mult)
+    }),
+    ("Player active hunger multiplier", |filter, server| {
+        filter
+            .active_hunger_mult
+            .matches(|| server.survival.active_hunger_mult)
+    }),
+    ("Equipment dropped on death", |filter, server| {
+        filter
+            .drop_items_on_death
+            .matches(|| server.survival.drop_items_on_death)
+    }),
+    ("Everybody can loot corpse", |filter, server| {
+        filter
+            .anyone_can_loot_corpse
+            .matches(|| server.survival.anyone_can_loot_corpse)
+    }),
+    ("Offline characters stay in world", |filter, server| {
+        filter
+            .offline_chars_in_world
+            .matches(|| server.survival.offline_chars_in_world)
+    }),
+    ("Durability multiplier", |filter, server| {
+        filter
+            .durability_mult
+            .matches(|| server.combat.durability_mult)
+    }),
+    ("Thrall wakeup time (seconds)", |filter, server| {
+        filter
+            .thrall_wakeup_time_secs
+            .matches(|| server.combat.thrall_wakeup_time.num_seconds())
+    }),
+    ("Harvest amount multiplier", |filter, server| {
+        filter
+            .harvest_amount_mult
+            .matches(|| server.harvesting.harvest_amount_mult)
+    }),
+    ("Item spoil rate scale", |filter, server| {
+        filter
+            .item_spoil_rate_mult
+            .matches(|| server.harvesting.item_spoil_rate_mult)
+    }),
+    ("Resource respawn speed multiplier", |filter, server| {
+        filter
+            .rsrc_respawn_speed_mult
+            .matches(|| server.harvesting.rsrc_respawn_speed_mult)
+    }),
+    ("Crafting time multiplier", |filter, server| {
+        filter
+            .crafting_time_mult
+            .matches(|| server.crafting.crafting_time_mult)
+    }),
+    ("Thrall crafting time multiplier", |filter, server| {
+        filter
+            .thrall_crafting_time_mult
+            .matches(|| server.crafting.thrall_crafting_time_mult)
+    }),
+];
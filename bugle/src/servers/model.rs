@@ -1,4 +1,5 @@
 use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
 use std::net::{IpAddr, SocketAddr};
 use std::ops::{Deref, DerefMut};
 use std::time::Duration;
@@ -18,6 +19,7 @@ use crate::game::settings::server::{
 };
 use crate::net::{is_valid_ip, is_valid_port};
 
+use super::wipe::parse_wipe_schedule;
 use super::FavoriteServers;
 
 #[derive(Clone, Debug)]
@@ -27,12 +29,16 @@ pub struct Server {
     pub connected_players: Option<usize>,
     pub age: Option<Duration>,
     pub ping: Option<Duration>,
+    pub ping_trend: PingTrend,
+    pub consecutive_timeouts: usize,
     pub waiting_for_pong: bool,
+    pub under_maintenance: bool,
     pub favorite: bool,
     pub saved_id: Option<Uuid>,
     pub validity: Validity,
     pub merged: bool,
     pub tombstone: bool,
+    pub wipe_schedule: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -75,6 +81,10 @@ pub struct ServerData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mods: Option<String>,
 
+    #[serde(rename = "BugleNotes", default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+
     #[serde(flatten)]
     pub general: PublicGeneralSettings,
 
@@ -117,6 +127,7 @@ impl Server {
         } else {
             data.observed_ip.unwrap()
         };
+        let wipe_schedule = parse_wipe_schedule(&data.name);
 
         let mut server = Server {
             data,
@@ -124,12 +135,16 @@ impl Server {
             connected_players: None,
             age: None,
             ping: None,
+            ping_trend: PingTrend::Stable,
+            consecutive_timeouts: 0,
             waiting_for_pong: false,
+            under_maintenance: false,
             favorite: false,
             saved_id: None,
             validity: Validity::VALID,
             merged: false,
             tombstone: false,
+            wipe_schedule,
         };
 
         if server.name.is_empty() {
@@ -160,6 +175,47 @@ impl Server {
         self.waiting_for_pong = self.is_valid();
     }
 
+    pub fn record_ping(&mut self, ping_trend: PingTrend) {
+        self.ping_trend = ping_trend;
+        self.consecutive_timeouts = 0;
+    }
+
+    pub fn record_timeout(&mut self) {
+        self.consecutive_timeouts += 1;
+    }
+
+    pub fn health_score(&self) -> Option<u8> {
+        if self.ping.is_none() && (self.consecutive_timeouts == 0) {
+            return None;
+        }
+
+        let ping_score = match self.ping {
+            Some(ping) => {
+                let ping_ms = ping.as_millis() as f64;
+                let ratio =
+                    (HEALTH_MAX_PING_MS - ping_ms) / (HEALTH_MAX_PING_MS - HEALTH_MIN_PING_MS);
+                HEALTH_PING_WEIGHT * ratio.clamp(0.0, 1.0)
+            }
+            None => 0.0,
+        };
+
+        // Busy but not full servers are the healthiest; empty ones are likely dead and full ones
+        // can't be joined
+        let population_score = match self.connected_players {
+            Some(0) | None => 0.0,
+            Some(players) if players >= self.max_players => HEALTH_POPULATION_WEIGHT / 3.0,
+            Some(players) => {
+                let ratio = players as f64 / self.max_players as f64;
+                HEALTH_POPULATION_WEIGHT * (1.0 - (ratio - 0.5).abs())
+            }
+        };
+
+        let timeout_score = HEALTH_TIMEOUT_WEIGHT
+            * (1.0 - self.consecutive_timeouts as f64 / HEALTH_MAX_TIMEOUTS).max(0.0);
+
+        Some((ping_score + population_score + timeout_score).round() as u8)
+    }
+
     pub fn host(&self) -> String {
         format!("{}:{}", self.ip, self.port)
     }
@@ -214,8 +270,10 @@ impl Server {
 
     pub fn merge_from(&mut self, source: &mut Self) {
         let saved_id = self.saved_id;
+        let notes = self.notes.take();
         self.clone_from(source);
         self.saved_id = saved_id;
+        self.notes = notes;
         self.merged = true;
         source.tombstone = true;
     }
@@ -234,6 +292,28 @@ impl Serialize for Server {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PingTrend {
+    Stable,
+    Improving,
+    Degrading,
+}
+
+// Kept outside of the server list, so that the history survives refreshing the list
+#[derive(Debug, Default)]
+pub struct PingHistory(HashMap<SocketAddr, VecDeque<Duration>>);
+
+impl PingHistory {
+    pub fn record(&mut self, addr: SocketAddr, round_trip: Duration) -> PingTrend {
+        let samples = self.0.entry(addr).or_default();
+        if samples.len() == PING_TREND_SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back(round_trip);
+        ping_trend(samples)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Similarity(isize);
 
@@ -274,6 +354,38 @@ impl ServerData {
     pub fn is_modded(&self) -> bool {
         self.mods.is_some()
     }
+
+    pub fn mod_count(&self) -> usize {
+        let Some(mods) = &self.mods else {
+            return 0;
+        };
+        let Some((steam_mods, other_mods)) = mods
+            .lines()
+            .next()
+            .and_then(|counts| counts.split_once(':'))
+        else {
+            return 0;
+        };
+        let steam_mods: usize = steam_mods.trim().parse().unwrap_or_default();
+        let other_mods: usize = other_mods.trim().parse().unwrap_or_default();
+        steam_mods + other_mods
+    }
+
+    pub fn steam_mod_ids(&self) -> Vec<u64> {
+        let Some(mods) = &self.mods else {
+            return Vec::new();
+        };
+        let mut lines = mods.lines();
+        let steam_mods = lines
+            .next()
+            .and_then(|counts| counts.split_once(':'))
+            .and_then(|(steam_mods, _)| steam_mods.parse().ok())
+            .unwrap_or_default();
+        lines
+            .take(steam_mods)
+            .map_while(|id| id.trim().parse().ok())
+            .collect()
+    }
 }
 
 #[derive(
@@ -362,28 +474,46 @@ pub enum SortKey {
     Players,
     Age,
     Ping,
+    LastVisited,
+    Health,
+}
+
+impl SortKey {
+    pub fn secondary(self) -> Option<Self> {
+        match self {
+            Self::Players => Some(Self::Ping),
+            Self::Ping => Some(Self::Players),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct SortCriteria {
     pub key: SortKey,
     pub ascending: bool,
+    pub secondary_key: Option<SortKey>,
 }
 
 impl Default for SortCriteria {
     fn default() -> Self {
-        Self {
-            key: SortKey::Name,
-            ascending: true,
-        }
+        Self::new(SortKey::Name, true)
     }
 }
 
 impl SortCriteria {
+    pub fn new(key: SortKey, ascending: bool) -> Self {
+        Self {
+            key,
+            ascending,
+            secondary_key: key.secondary(),
+        }
+    }
+
     pub fn reversed(&self) -> Self {
         Self {
-            key: self.key,
             ascending: !self.ascending,
+            ..*self
         }
     }
 }
@@ -395,7 +525,7 @@ impl ParseProperty for SortCriteria {
             if text.starts_with('-') { (false, &text[1..]) } else { (true, text) };
         Ok(SortKey::from_str(key)
             .ok()
-            .map(|key| SortCriteria { key, ascending })
+            .map(|key| SortCriteria::new(key, ascending))
             .unwrap_or_default())
     }
 }
@@ -406,3 +536,97 @@ impl DisplayProperty for SortCriteria {
         format!("{}{}", prefix, self.key.as_ref())
     }
 }
+
+fn ping_trend(history: &VecDeque<Duration>) -> PingTrend {
+    if history.len() < PING_TREND_SAMPLES {
+        return PingTrend::Stable;
+    }
+
+    // Slope of the least squares line through the samples, in milliseconds per sample
+    let n = history.len() as f64;
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = history.iter().map(|ping| ping.as_secs_f64()).sum::<f64>() * 1000.0 / n;
+    let (mut num, mut den) = (0.0, 0.0);
+    for (x, ping) in history.iter().enumerate() {
+        let dx = x as f64 - mean_x;
+        num += dx * (ping.as_secs_f64() * 1000.0 - mean_y);
+        den += dx * dx;
+    }
+    let slope = num / den;
+
+    if slope > PING_TREND_THRESHOLD_MS {
+        PingTrend::Degrading
+    } else if slope < -PING_TREND_THRESHOLD_MS {
+        PingTrend::Improving
+    } else {
+        PingTrend::Stable
+    }
+}
+
+const PING_TREND_SAMPLES: usize = 5;
+const PING_TREND_THRESHOLD_MS: f64 = 5.0;
+
+const HEALTH_PING_WEIGHT: f64 = 50.0;
+const HEALTH_POPULATION_WEIGHT: f64 = 30.0;
+const HEALTH_TIMEOUT_WEIGHT: f64 = 20.0;
+const HEALTH_MIN_PING_MS: f64 = 50.0;
+const HEALTH_MAX_PING_MS: f64 = 300.0;
+const HEALTH_MAX_TIMEOUTS: f64 = 3.0;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(pings_ms: &[u64]) -> VecDeque<Duration> {
+        pings_ms
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect()
+    }
+
+    #[test]
+    fn rising_pings_are_degrading() {
+        assert_eq!(
+            ping_trend(&history(&[50, 60, 70, 80, 90])),
+            PingTrend::Degrading
+        );
+    }
+
+    #[test]
+    fn falling_pings_are_improving() {
+        assert_eq!(
+            ping_trend(&history(&[90, 80, 70, 60, 50])),
+            PingTrend::Improving
+        );
+    }
+
+    #[test]
+    fn flat_pings_are_stable() {
+        assert_eq!(
+            ping_trend(&history(&[70, 72, 69, 71, 70])),
+            PingTrend::Stable
+        );
+    }
+
+    #[test]
+    fn short_history_is_stable() {
+        assert_eq!(ping_trend(&history(&[])), PingTrend::Stable);
+        assert_eq!(
+            ping_trend(&history(&[50, 100, 150, 200])),
+            PingTrend::Stable
+        );
+    }
+
+    #[test]
+    fn ping_history_keeps_the_latest_samples() {
+        let addr = SocketAddr::from(([127, 0, 0, 1], 7777));
+        let mut history = PingHistory::default();
+        for ms in [200, 200, 200, 50, 60, 70, 80] {
+            history.record(addr, Duration::from_millis(ms));
+        }
+        assert_eq!(
+            history.record(addr, Duration::from_millis(90)),
+            PingTrend::Degrading
+        );
+    }
+}
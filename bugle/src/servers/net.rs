@@ -1,5 +1,5 @@
 mod http;
 mod ping;
 
-pub use self::http::fetch_server_list;
+pub use self::http::{fetch_offline_servers, fetch_server_list, PrivateServerList};
 pub use self::ping::{PingClient, PingRequest, PingResponse, PingResult};
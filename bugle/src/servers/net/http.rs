@@ -1,7 +1,10 @@
+use std::collections::HashSet;
+use std::net::SocketAddr;
+
 use anyhow::anyhow;
-use futures::future::try_join_all;
+use futures::stream::{FuturesUnordered, TryStreamExt};
 use reqwest::header::{HeaderMap, HeaderValue};
-use reqwest::{Client, Response, Result};
+use reqwest::{Client, RequestBuilder, Response, Result};
 use serde::Deserialize;
 use slog::{debug, info, warn, Logger};
 
@@ -9,41 +12,56 @@ use crate::game::{Branch, Game};
 use crate::net::http_client_builder;
 use crate::servers::Server;
 
-pub async fn fetch_server_list<'dc>(logger: &Logger, game: &Game) -> anyhow::Result<Vec<Server>> {
-    let url = directory_url(game.branch());
+#[derive(Debug, Clone)]
+pub struct PrivateServerList {
+    pub url: String,
+    pub auth: Option<(String, String)>,
+}
+
+pub async fn fetch_server_list<'dc>(
+    logger: &Logger,
+    game: &Game,
+    private_list: Option<&PrivateServerList>,
+    mut on_batch: impl FnMut(&[Server], usize, usize),
+) -> anyhow::Result<Vec<Server>> {
+    // Credentials are only ever sent to the private list they were configured for
+    let (url, auth) = match private_list {
+        Some(list) => (list.url.trim_end_matches('/'), list.auth.as_ref()),
+        None => (directory_url(game.branch()), None),
+    };
 
-    debug!(logger, "Fetching server list");
-    let client = make_client(game)?;
-    let bucket_list = client
-        .get(format!("{}/buckets/index_Windows.json", url))
+    debug!(logger, "Fetching server list"; "url" => url);
+    let client = &make_client(game, private_list.is_none())?;
+    let bucket_list = get_with_auth(client, format!("{}/buckets/index_Windows.json", url), auth)
         .send()
         .await?
         .json::<BucketList>()
         .await?;
 
+    let num_buckets = bucket_list.buckets.len();
     debug!(
         logger,
         "Fetching servers from buckets";
-        "num_buckets" => bucket_list.buckets.len()
+        "num_buckets" => num_buckets,
     );
-    let responses = try_join_all(
-        bucket_list
-            .buckets
-            .iter()
-            .map(|bucket| client.get(format!("{}/buckets/{}", url, bucket)).send()),
-    )
-    .await?;
-
-    debug!(logger, "Parsing servers from responses");
-    let servers = try_join_all(
-        responses
-            .into_iter()
-            .map(|response| parse_servers(&logger, response)),
-    )
-    .await?
-    .into_iter()
-    .flatten()
-    .collect::<Vec<Server>>();
+    let mut batches = bucket_list
+        .buckets
+        .iter()
+        .map(|bucket| async move {
+            let response = get_with_auth(client, format!("{}/buckets/{}", url, bucket), auth)
+                .send()
+                .await?;
+            parse_servers(logger, response).await
+        })
+        .collect::<FuturesUnordered<_>>();
+
+    let mut servers = Vec::new();
+    let mut num_loaded = 0;
+    while let Some(batch) = batches.try_next().await? {
+        num_loaded += 1;
+        on_batch(&batch, num_loaded, num_buckets);
+        servers.extend(batch);
+    }
 
     info!(
         logger,
@@ -54,6 +72,32 @@ pub async fn fetch_server_list<'dc>(logger: &Logger, game: &Game) -> anyhow::Res
     Ok(servers)
 }
 
+pub async fn fetch_offline_servers(
+    logger: &Logger,
+    game: &Game,
+    url: &str,
+) -> anyhow::Result<HashSet<SocketAddr>> {
+    debug!(logger, "Fetching server maintenance status"; "url" => url);
+    let client = http_client_builder(game).gzip(true).build()?;
+    let offline = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<Vec<SocketAddr>>()
+        .await?
+        .into_iter()
+        .collect::<HashSet<SocketAddr>>();
+
+    info!(
+        logger,
+        "Fetched server maintenance status";
+        "num_offline" => offline.len()
+    );
+
+    Ok(offline)
+}
+
 #[derive(Debug, Deserialize)]
 struct BucketList {
     buckets: Vec<String>,
@@ -66,14 +110,16 @@ fn directory_url(branch: Branch) -> &'static str {
     }
 }
 
-fn make_client(game: &Game) -> Result<Client> {
+fn make_client(game: &Game, with_api_key: bool) -> Result<Client> {
     let mut default_headers = HeaderMap::new();
-    default_headers.insert(
-        "X-API-Key",
-        HeaderValue::from_static(
-            "aWAWirTCDr49G569tL8Cgv5D7WyvfCzFTHMcCGvbXeHY08i3G64uv1TWKkiHMFDE",
-        ),
-    );
+    if with_api_key {
+        default_headers.insert(
+            "X-API-Key",
+            HeaderValue::from_static(
+                "aWAWirTCDr49G569tL8Cgv5D7WyvfCzFTHMcCGvbXeHY08i3G64uv1TWKkiHMFDE",
+            ),
+        );
+    }
 
     http_client_builder(game)
         .default_headers(default_headers)
@@ -81,6 +127,14 @@ fn make_client(game: &Game) -> Result<Client> {
         .build()
 }
 
+fn get_with_auth(client: &Client, url: String, auth: Option<&(String, String)>) -> RequestBuilder {
+    let request = client.get(url);
+    match auth {
+        Some((user, password)) => request.basic_auth(user, Some(password)),
+        None => request,
+    }
+}
+
 async fn parse_servers<'dc>(logger: &Logger, response: Response) -> anyhow::Result<Vec<Server>> {
     let json = response.json::<serde_json::Value>().await?;
     let json = json
@@ -33,6 +33,7 @@ impl PingRequest {
 #[derive(Debug)]
 pub struct PingResponse {
     pub server_idx: usize,
+    pub addr: SocketAddr,
     pub result: PingResult,
 }
 
@@ -44,6 +45,7 @@ pub enum PingResult {
         round_trip: Duration,
     },
     Timeout,
+    Unreachable,
 }
 
 pub struct PingClient {
@@ -306,6 +308,7 @@ impl<F: Fn(PingResponse) + Send> Receiver<F> {
 
         let response = PingResponse {
             server_idx: request.idx,
+            addr,
             result: PingResult::Pong {
                 connected_players: players as _,
                 age,
@@ -332,6 +335,7 @@ impl<F: Fn(PingResponse) + Send> Receiver<F> {
                 } else {
                     let response = PingResponse {
                         server_idx: entry.get().idx,
+                        addr: *entry.key(),
                         result: PingResult::Timeout,
                     };
                     (self.on_response)(response);
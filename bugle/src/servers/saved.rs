@@ -6,8 +6,9 @@ use std::ops::{Index, IndexMut};
 use std::path::{Path, PathBuf};
 
 use anyhow::Result;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::ser::PrettyFormatter;
+use serde_json::{json, Value};
 use uuid::Uuid;
 
 use crate::env::current_exe_dir;
@@ -20,6 +21,18 @@ pub struct SavedServers {
     indexes: Indexes,
 }
 
+#[derive(Serialize)]
+struct SavedServersExport<'s> {
+    description: &'static str,
+    schema: Value,
+    servers: Vec<&'s Server>,
+}
+
+#[derive(Deserialize)]
+struct SavedServersImport {
+    servers: Vec<Server>,
+}
+
 #[derive(Default)]
 struct Indexes {
     by_id: HashMap<String, HashSet<Uuid>>,
@@ -60,6 +73,36 @@ impl SavedServers {
         Ok(self.servers.serialize(&mut serializer)?)
     }
 
+    pub fn export(&self, path: &Path) -> Result<()> {
+        let file = File::options()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path)?;
+        let fmt = PrettyFormatter::with_indent(b"  ");
+        let mut serializer = serde_json::Serializer::with_formatter(file, fmt);
+        let export = SavedServersExport {
+            description: EXPORT_DESCRIPTION,
+            schema: export_schema(),
+            servers: self.servers.values().collect(),
+        };
+        Ok(export.serialize(&mut serializer)?)
+    }
+
+    pub fn import(&mut self, path: &Path) -> Result<Vec<Uuid>> {
+        let json = std::fs::read_to_string(path)?;
+        let SavedServersImport { servers } = serde_json::from_str(&json)?;
+
+        let mut imported = Vec::new();
+        for server in servers {
+            if self.by_addr(server.ip, server.port).next().is_some() {
+                continue;
+            }
+            imported.push(self.add(server));
+        }
+        Ok(imported)
+    }
+
     pub fn add(&mut self, mut server: Server) -> Uuid {
         let id = Uuid::new_v4();
         server.saved_id = Some(id);
@@ -210,3 +253,26 @@ impl Indexes {
             .remove(&id);
     }
 }
+
+fn export_schema() -> Value {
+    json!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "properties": {
+                "ip": { "type": "string", "description": "IP address of the server" },
+                "Port": { "type": "integer", "description": "Game port of the server" },
+                "Name": { "type": "string", "description": "Name of the server" },
+                "BugleNotes": { "type": "string", "description": "User's notes about the server" },
+                "private": {
+                    "type": "boolean",
+                    "description": "Whether the server needs a password"
+                }
+            },
+            "additionalProperties": true
+        }
+    })
+}
+
+const EXPORT_DESCRIPTION: &str =
+    "BUGLE saved servers export. The servers field matches the schema. Passwords are not exported.";
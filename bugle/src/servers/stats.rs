@@ -0,0 +1,281 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Timelike, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use crate::env::current_exe_dir;
+
+pub struct ServerStats {
+    path: PathBuf,
+    history: HashMap<SocketAddr, Vec<(DateTime<Utc>, bool)>>,
+    players: HashMap<SocketAddr, Vec<(DateTime<Utc>, usize)>>,
+}
+
+pub struct ServerStatsSnapshot {
+    path: PathBuf,
+    history: HashMap<SocketAddr, Vec<(DateTime<Utc>, bool)>>,
+}
+
+pub struct Uptime {
+    pub percentage: f64,
+    pub hourly: Vec<Option<bool>>,
+}
+
+pub struct PeakHours {
+    pub start_hour: u32,
+    pub end_hour: u32,
+}
+
+pub struct MaintenanceWindow {
+    pub start_minute: u32,
+    pub end_minute: u32,
+}
+
+impl ServerStats {
+    #[cfg(not(windows))]
+    pub fn new() -> Result<Self> {
+        Self::for_current_exe()
+    }
+
+    #[cfg(windows)]
+    pub fn new() -> Result<Self> {
+        Self::for_current_exe().or_else(|_| Self::in_appdata())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn load(&mut self) -> Result<()> {
+        let file = File::open(&self.path)?;
+        if file.metadata()?.len() == 0 {
+            self.history = HashMap::new();
+            return Ok(());
+        }
+        let decoder = GzDecoder::new(BufReader::new(file));
+        self.history = serde_json::from_reader(decoder)?;
+        self.prune(Utc::now());
+        Ok(())
+    }
+
+    pub fn snapshot(&self) -> ServerStatsSnapshot {
+        ServerStatsSnapshot {
+            path: self.path.clone(),
+            history: self.history.clone(),
+        }
+    }
+
+    pub fn record(&mut self, addr: SocketAddr, online: bool, now: DateTime<Utc>) {
+        let history = self.history.entry(addr).or_default();
+        history.push((now, online));
+        let cutoff = now - Duration::days(HISTORY_DAYS);
+        history.retain(|(timestamp, _)| *timestamp >= cutoff);
+    }
+
+    pub fn record_players(&mut self, addr: SocketAddr, players: usize, now: DateTime<Utc>) {
+        let samples = self.players.entry(addr).or_default();
+        samples.push((now, players));
+        let cutoff = now - Duration::days(HISTORY_DAYS);
+        samples.retain(|(timestamp, _)| *timestamp >= cutoff);
+        if samples.len() > MAX_PLAYER_SAMPLES {
+            samples.drain(..(samples.len() - MAX_PLAYER_SAMPLES));
+        }
+    }
+
+    pub fn peak_hours(&self, addr: SocketAddr) -> Option<PeakHours> {
+        let samples = self.players.get(&addr)?;
+        if samples.len() < MIN_PEAK_SAMPLES {
+            return None;
+        }
+
+        let mut buckets = [(0usize, 0usize); 24];
+        for (timestamp, players) in samples.iter() {
+            let bucket = &mut buckets[timestamp.hour() as usize];
+            bucket.0 += players;
+            bucket.1 += 1;
+        }
+        if buckets.iter().filter(|(_, count)| *count > 0).count() < MIN_PEAK_HOURS {
+            return None;
+        }
+        let averages: Vec<f64> = buckets
+            .iter()
+            .map(|&(total, count)| if count > 0 { total as f64 / count as f64 } else { 0.0 })
+            .collect();
+
+        let window_average = |start: usize| -> f64 {
+            (0..PEAK_WINDOW_HOURS)
+                .map(|offset| averages[(start + offset) % 24])
+                .sum()
+        };
+        let start_hour = (0..24)
+            .max_by(|&lhs, &rhs| window_average(lhs).total_cmp(&window_average(rhs)))
+            .unwrap();
+        if window_average(start_hour) == 0.0 {
+            return None;
+        }
+
+        Some(PeakHours {
+            start_hour: start_hour as u32,
+            end_hour: ((start_hour + PEAK_WINDOW_HOURS) % 24) as u32,
+        })
+    }
+
+    pub fn maintenance_windows(&self, addr: SocketAddr) -> Vec<MaintenanceWindow> {
+        let Some(history) = self.history.get(&addr) else {
+            return vec![];
+        };
+
+        // (timeouts, total) per day, for each 30-minute bucket of the day
+        let mut buckets: Vec<BTreeMap<NaiveDate, (usize, usize)>> =
+            vec![BTreeMap::new(); MAINTENANCE_BUCKETS];
+        for (timestamp, online) in history.iter() {
+            let bucket =
+                (timestamp.num_seconds_from_midnight() / 60 / MAINTENANCE_BUCKET_MINUTES) as usize;
+            let counts = buckets[bucket].entry(timestamp.date_naive()).or_default();
+            if !online {
+                counts.0 += 1;
+            }
+            counts.1 += 1;
+        }
+
+        let flagged: Vec<bool> = buckets.iter().map(is_maintenance_bucket).collect();
+        if flagged.iter().all(|flag| *flag) {
+            return vec![];
+        }
+
+        // Start scanning after an unflagged bucket, so that windows spanning midnight are merged
+        let offset = flagged.iter().position(|flag| !flag).unwrap() + 1;
+        let mut windows = vec![];
+        let mut start = None;
+        for idx in offset..(offset + MAINTENANCE_BUCKETS) {
+            let bucket = idx % MAINTENANCE_BUCKETS;
+            match (flagged[bucket], start) {
+                (true, None) => start = Some(bucket),
+                (false, Some(start_bucket)) => {
+                    windows.push(MaintenanceWindow {
+                        start_minute: start_bucket as u32 * MAINTENANCE_BUCKET_MINUTES,
+                        end_minute: bucket as u32 * MAINTENANCE_BUCKET_MINUTES,
+                    });
+                    start = None;
+                }
+                _ => (),
+            }
+        }
+        windows.sort_by_key(|window| window.start_minute);
+        windows
+    }
+
+    pub fn uptime(&self, addr: SocketAddr, now: DateTime<Utc>) -> Option<Uptime> {
+        let history = self.history.get(&addr)?;
+
+        let num_hours = (HISTORY_DAYS * 24) as usize;
+        let start = now - Duration::hours(num_hours as i64);
+        let mut hourly = vec![None; num_hours];
+        for (timestamp, online) in history.iter() {
+            let Ok(hour) = usize::try_from((*timestamp - start).num_hours()) else {
+                continue;
+            };
+            let status = &mut hourly[hour.min(num_hours - 1)];
+            *status = Some(status.unwrap_or_default() || *online);
+        }
+
+        // Measured over the observed hours, so that bursts of pings don't skew the percentage
+        let num_observed = hourly.iter().flatten().count();
+        if num_observed == 0 {
+            return None;
+        }
+        let num_online = hourly.iter().flatten().filter(|online| **online).count();
+        let percentage = 100.0 * num_online as f64 / num_observed as f64;
+
+        Some(Uptime { percentage, hourly })
+    }
+
+    fn prune(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - Duration::days(HISTORY_DAYS);
+        self.history.retain(|_, history| {
+            history.retain(|(timestamp, _)| *timestamp >= cutoff);
+            !history.is_empty()
+        });
+    }
+
+    fn for_current_exe() -> Result<Self> {
+        Self::open(current_exe_dir()?)
+    }
+
+    #[cfg(windows)]
+    fn in_appdata() -> Result<Self> {
+        use crate::env::{appdata_dir, AppDataFolder};
+
+        let mut path = appdata_dir(AppDataFolder::Roaming)?;
+        path.push("bugle");
+        std::fs::create_dir_all(&path)?;
+
+        Self::open(path)
+    }
+
+    fn open(mut path: PathBuf) -> Result<Self> {
+        path.push("server_stats.json.gz");
+        let _ = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(&path)?;
+        Ok(Self {
+            path,
+            history: HashMap::new(),
+            players: HashMap::new(),
+        })
+    }
+}
+
+impl ServerStatsSnapshot {
+    pub fn save(&self) -> Result<()> {
+        let file = File::options()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        let mut encoder = GzEncoder::new(BufWriter::new(file), Compression::default());
+        serde_json::to_writer(&mut encoder, &self.history)?;
+        encoder.finish()?;
+        Ok(())
+    }
+}
+
+fn is_maintenance_bucket(days: &BTreeMap<NaiveDate, (usize, usize)>) -> bool {
+    let mut streak = 0;
+    let mut last_day: Option<NaiveDate> = None;
+    for (day, &(timeouts, total)) in days.iter() {
+        if (timeouts as f64) < (total as f64) * MAINTENANCE_TIMEOUT_RATIO {
+            streak = 0;
+            last_day = None;
+            continue;
+        }
+        streak = match last_day {
+            Some(last_day) if *day - last_day == Duration::days(1) => streak + 1,
+            _ => 1,
+        };
+        if streak >= MAINTENANCE_MIN_DAYS {
+            return true;
+        }
+        last_day = Some(*day);
+    }
+    false
+}
+
+const HISTORY_DAYS: i64 = 7;
+const MIN_PEAK_SAMPLES: usize = 30;
+const MIN_PEAK_HOURS: usize = 8;
+const MAX_PLAYER_SAMPLES: usize = 1000;
+const PEAK_WINDOW_HOURS: usize = 4;
+const MAINTENANCE_BUCKET_MINUTES: u32 = 30;
+const MAINTENANCE_BUCKETS: usize = (24 * 60 / MAINTENANCE_BUCKET_MINUTES) as usize;
+const MAINTENANCE_TIMEOUT_RATIO: f64 = 0.8;
+const MAINTENANCE_MIN_DAYS: usize = 3;
@@ -0,0 +1,21 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+pub(super) fn parse_wipe_schedule(text: &str) -> Option<String> {
+    WIPE_PATTERNS.iter().find_map(|pattern| {
+        let captures = pattern.captures(text)?;
+        let schedule = captures.get(1).or_else(|| captures.get(0))?;
+        Some(schedule.as_str().trim().to_string())
+    })
+}
+
+lazy_static! {
+    static ref WIPE_PATTERNS: Vec<Regex> = WIPE_PATTERNS_SRC
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| Regex::new(line).unwrap())
+        .collect();
+}
+
+const WIPE_PATTERNS_SRC: &str = include_str!("wipe_patterns.txt");
@@ -18,6 +18,21 @@ pub fn weekday_iter() -> impl Iterator<Item = Weekday> {
     (0..7u8).map(|day| day.try_into().unwrap())
 }
 
+pub fn edit_distance(lhs: &str, rhs: &str) -> usize {
+    let rhs: Vec<char> = rhs.chars().collect();
+    let mut prev_row: Vec<usize> = (0..=rhs.len()).collect();
+    for (i, lhs_char) in lhs.chars().enumerate() {
+        let mut row = Vec::with_capacity(rhs.len() + 1);
+        row.push(i + 1);
+        for (j, rhs_char) in rhs.iter().enumerate() {
+            let substitution = prev_row[j] + if lhs_char == *rhs_char { 0 } else { 1 };
+            row.push(substitution.min(prev_row[j + 1] + 1).min(row[j] + 1));
+        }
+        prev_row = row;
+    }
+    prev_row[rhs.len()]
+}
+
 macro_rules! weak_cb {
     (@munch_args [$this:ident = $from:expr] [$($args:tt)*] $arg:pat_param , $($tail:tt)+) => {
         weak_cb!(@munch_args [$this = $from] [$($args)* $arg,] $($tail)+)
@@ -59,3 +74,33 @@ macro_rules! weak_cb {
     };
 }
 pub(super) use weak_cb;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_distance_of_equal_strings_is_zero() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("CoolMod", "CoolMod"), 0);
+    }
+
+    #[test]
+    fn edit_distance_counts_insertions_and_deletions() {
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("CoolMod", "Cool_Mod"), 1);
+        assert_eq!(edit_distance("Cool_Mod", "CoolMod"), 1);
+    }
+
+    #[test]
+    fn edit_distance_counts_substitutions() {
+        assert_eq!(edit_distance("CoolMod", "CoolMud"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn edit_distance_is_case_sensitive() {
+        assert_eq!(edit_distance("coolmod", "CoolMod"), 2);
+    }
+}
@@ -2,7 +2,9 @@ mod fls;
 mod server_loader;
 
 pub use fls::{FlsWorker, LoginComplete};
-pub use server_loader::{PongReceived, ServerLoaderWorker, ServersLoaded};
+pub use server_loader::{
+    OfflineServersLoaded, PongsReceived, ServerBatchLoaded, ServerLoaderWorker, ServersLoaded,
+};
 
 #[derive(Debug)]
 pub enum TaskState<T> {
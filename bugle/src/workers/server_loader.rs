@@ -1,32 +1,52 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::SocketAddr;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use anyhow::Result;
 use dynabus::mpsc::BusSender;
-use slog::{o, Logger};
+use slog::{info, o, warn, Logger};
 use tokio::task::JoinHandle;
 
 use crate::bus::AppSender;
 use crate::game::Game;
-use crate::servers::{fetch_server_list, PingClient, PingRequest, PingResponse, Server};
+use crate::servers::{
+    fetch_offline_servers, fetch_server_list, PingClient, PingRequest, PingResponse, PingResult,
+    PrivateServerList, Server,
+};
 
 pub struct ServerLoaderWorker {
     logger: Logger,
     game: Arc<Game>,
     tx: BusSender<AppSender>,
     server_loader: Mutex<ServerLoader>,
+    status_monitor: Mutex<Option<JoinHandle<()>>>,
+    pongs: Mutex<VecDeque<PingResponse>>,
+    timeouts: Mutex<HashMap<SocketAddr, usize>>,
 }
 
 #[derive(dynabus::Event)]
 pub struct ServersLoaded(pub Result<Vec<Server>>);
 
 #[derive(dynabus::Event)]
-pub struct PongReceived(pub PingResponse);
+pub struct ServerBatchLoaded {
+    pub servers: Vec<Server>,
+    pub loaded_buckets: usize,
+    pub total_buckets: usize,
+}
+
+#[derive(dynabus::Event)]
+pub struct PongsReceived;
+
+#[derive(dynabus::Event)]
+pub struct OfflineServersLoaded(pub HashSet<SocketAddr>);
 
 #[derive(Default)]
 struct ServerLoader {
     generation: u32,
     fetcher: Option<JoinHandle<()>>,
     pinger: Option<PingClient>,
+    auto_remove_after_timeouts: Option<usize>,
 }
 
 impl ServerLoaderWorker {
@@ -36,10 +56,17 @@ impl ServerLoaderWorker {
             game,
             tx,
             server_loader: Mutex::new(Default::default()),
+            status_monitor: Mutex::new(None),
+            pongs: Mutex::new(VecDeque::new()),
+            timeouts: Mutex::new(HashMap::new()),
         })
     }
 
-    pub fn load_servers(self: &Arc<Self>) {
+    pub fn load_servers(
+        self: &Arc<Self>,
+        private_list: Option<PrivateServerList>,
+        auto_remove_after_timeouts: Option<usize>,
+    ) {
         let mut server_loader = self.server_loader.lock().unwrap();
         if server_loader.fetcher.is_some() {
             return;
@@ -47,8 +74,10 @@ impl ServerLoaderWorker {
 
         let generation = server_loader.generation.wrapping_add(1);
         server_loader.generation = generation;
-        server_loader.fetcher = Some(Arc::clone(self).spawn_fetcher(generation));
+        server_loader.fetcher = Some(Arc::clone(self).spawn_fetcher(generation, private_list));
         server_loader.pinger = None;
+        server_loader.auto_remove_after_timeouts = auto_remove_after_timeouts;
+        self.pongs.lock().unwrap().clear();
     }
 
     pub fn ping_servers(self: &Arc<Self>, requests: Vec<PingRequest>) -> Result<()> {
@@ -59,9 +88,42 @@ impl ServerLoaderWorker {
         self.with_ping_client(|client| client.priority_send(request))
     }
 
-    fn spawn_fetcher(self: Arc<Self>, generation: u32) -> JoinHandle<()> {
+    pub fn take_pongs(&self) -> Vec<PingResponse> {
+        self.pongs.lock().unwrap().drain(..).collect()
+    }
+
+    pub fn monitor_offline_servers(self: &Arc<Self>, url: String, interval: Duration) {
+        let mut status_monitor = self.status_monitor.lock().unwrap();
+        if let Some(monitor) = status_monitor.take() {
+            monitor.abort();
+        }
+
+        let this = Arc::clone(self);
+        *status_monitor = Some(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match fetch_offline_servers(&this.logger, &*this.game, &url).await {
+                    Ok(offline) => {
+                        this.tx.send(OfflineServersLoaded(offline)).ok();
+                    }
+                    Err(err) => warn!(
+                        this.logger,
+                        "Error fetching server maintenance status";
+                        "error" => %err
+                    ),
+                }
+            }
+        }));
+    }
+
+    fn spawn_fetcher(
+        self: Arc<Self>,
+        generation: u32,
+        private_list: Option<PrivateServerList>,
+    ) -> JoinHandle<()> {
         tokio::spawn(async move {
-            let servers = self.fetch_servers().await;
+            let servers = self.fetch_servers(generation, private_list.as_ref()).await;
 
             let mut server_loader = self.server_loader.lock().unwrap();
             if server_loader.generation != generation {
@@ -77,28 +139,94 @@ impl ServerLoaderWorker {
     fn with_ping_client<R, F: FnOnce(&PingClient) -> R>(self: &Arc<Self>, cb: F) -> Result<R> {
         let mut server_loader = self.server_loader.lock().unwrap();
         if let None = &server_loader.pinger {
-            let pinger = Arc::clone(self).make_ping_client(server_loader.generation)?;
+            let pinger = Arc::clone(self).make_ping_client(
+                server_loader.generation,
+                server_loader.auto_remove_after_timeouts,
+            )?;
             server_loader.pinger = Some(pinger);
         };
         Ok(cb(server_loader.pinger.as_ref().unwrap()))
     }
 
-    fn make_ping_client(self: Arc<Self>, generation: u32) -> Result<PingClient> {
+    fn make_ping_client(
+        self: Arc<Self>,
+        generation: u32,
+        auto_remove_after_timeouts: Option<usize>,
+    ) -> Result<PingClient> {
         let ping_logger = self.logger.new(o!("ping_generation" => generation));
         Ok(PingClient::new(
             &ping_logger,
             self.game.build_id(),
-            move |response| {
+            move |mut response| {
                 // TODO: Improve generation handling
                 if self.server_loader.lock().unwrap().generation != generation {
                     return;
                 }
-                self.tx.send(PongReceived(response)).ok();
+                self.count_timeouts(&mut response, auto_remove_after_timeouts);
+                let mut pongs = self.pongs.lock().unwrap();
+                if pongs.len() >= MAX_PENDING_PONGS {
+                    pongs.pop_front();
+                }
+                pongs.push_back(response);
+                if pongs.len() == 1 {
+                    self.tx.send(PongsReceived).ok();
+                }
             },
         )?)
     }
 
-    async fn fetch_servers(&self) -> Result<Vec<Server>> {
-        Ok(fetch_server_list(&self.logger, &*self.game).await?)
+    // The ping client is replaced on every refresh, so the counts are kept here
+    fn count_timeouts(
+        &self,
+        response: &mut PingResponse,
+        auto_remove_after_timeouts: Option<usize>,
+    ) {
+        let mut timeouts = self.timeouts.lock().unwrap();
+        if !matches!(response.result, PingResult::Timeout) {
+            timeouts.remove(&response.addr);
+            return;
+        }
+
+        let count = timeouts.entry(response.addr).or_default();
+        *count += 1;
+        match auto_remove_after_timeouts {
+            Some(limit) if *count >= limit => {
+                info!(
+                    self.logger,
+                    "Giving up on unreachable server";
+                    "addr" => response.addr,
+                    "timeouts" => *count,
+                );
+                response.result = PingResult::Unreachable;
+            }
+            _ => (),
+        }
+    }
+
+    async fn fetch_servers(
+        &self,
+        generation: u32,
+        private_list: Option<&PrivateServerList>,
+    ) -> Result<Vec<Server>> {
+        Ok(fetch_server_list(
+            &self.logger,
+            &*self.game,
+            private_list,
+            |batch, loaded, total| {
+                if self.server_loader.lock().unwrap().generation != generation {
+                    return;
+                }
+                self.tx
+                    .send(ServerBatchLoaded {
+                        servers: batch.to_vec(),
+                        loaded_buckets: loaded,
+                        total_buckets: total,
+                    })
+                    .ok();
+            },
+        )
+        .await?)
     }
 }
+
+const MAX_PENDING_PONGS: usize = 10000;
@@ -1,8 +1,31 @@
+//! Channel bus backed by `crossbeam-channel`.
+//!
+//! Thread safety: the bus itself stays on the thread that created it, because handlers are not
+//! required to be `Send`. Subscribing, unsubscribing and dispatching all happen on that thread.
+//! [`BusSender`](crate::mpsc::BusSender) obtained via [`ChannelBus::sender`] is `Send + Sync` and
+//! can be cloned into any number of worker threads. Events sent through it are queued in the
+//! channel and delivered to the handlers, in the order they were sent, when the owning thread
+//! calls [`ChannelBus::recv`], [`ChannelBus::recv_deadline`] or [`ChannelBus::drain_pending`].
+
 use std::time::Instant;
 
 use crossbeam_channel::{RecvTimeoutError, TryRecvError, TrySendError};
 
-use crate::mpsc::{BlockingReceiver, Message, Receiver, Sender};
+use crate::local::LocalBus;
+use crate::mpsc::{BlockingReceiver, ChannelBus, Message, Receiver, Sender};
+
+pub type CrossbeamBus<B = LocalBus> =
+    ChannelBus<crossbeam_channel::Sender<Message>, crossbeam_channel::Receiver<Message>, B>;
+
+impl CrossbeamBus {
+    pub fn unbounded() -> Self {
+        Self::new(crossbeam_channel::unbounded(), LocalBus::new())
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new(crossbeam_channel::bounded(capacity), LocalBus::new())
+    }
+}
 
 impl Sender for crossbeam_channel::Sender<Message> {
     type Error = TrySendError<Message>;
@@ -36,3 +59,27 @@ impl BlockingReceiver for crossbeam_channel::Receiver<Message> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crossbeam_channel::TrySendError;
+
+    use super::*;
+    use crate::Event;
+
+    struct Ping;
+    impl Event for Ping {}
+
+    #[test]
+    fn with_capacity_rejects_when_full() {
+        let bus = CrossbeamBus::with_capacity(1);
+        bus.sender().send(Ping).unwrap();
+        assert!(matches!(
+            bus.sender().send(Ping),
+            Err(TrySendError::Full(_))
+        ));
+
+        bus.drain_pending().unwrap();
+        assert!(bus.sender().send(Ping).is_ok());
+    }
+}
@@ -5,7 +5,7 @@ pub mod mpsc;
 pub use dynabus_derive::Event;
 
 #[cfg(feature = "crossbeam")]
-mod crossbeam;
+pub mod crossbeam;
 
 pub trait Event {}
 
@@ -40,4 +40,8 @@ pub trait Bus {
     }
 
     fn unsubscribe<E: Event + 'static>(&mut self, subscription: Self::Subscription<E>);
+
+    fn unsubscribe_all<E: Event + 'static>(&mut self);
+
+    fn clear(&mut self);
 }
@@ -71,6 +71,14 @@ impl Bus for LocalBus {
         let handlers = erased.downcast_mut::<Handlers<E>>().unwrap();
         handlers.remove(subscription.key);
     }
+
+    fn unsubscribe_all<E: Event + 'static>(&mut self) {
+        self.dispatch_map.remove(&TypeId::of::<E>());
+    }
+
+    fn clear(&mut self) {
+        self.dispatch_map.clear();
+    }
 }
 
 impl<E: Event> Handlers<E> {
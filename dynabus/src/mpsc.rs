@@ -69,6 +69,15 @@ impl<S: Sender, R: Receiver, B: Bus> ChannelBus<S, R, B> {
         self.recv_deadline(Instant::now() + timeout)
     }
 
+    pub fn drain_pending(&self) -> Result<usize, R::Error> {
+        let mut count = 0;
+        while let Some(message) = self.rx.try_recv()? {
+            self.dispatch_message(message);
+            count += 1;
+        }
+        Ok(count)
+    }
+
     fn ensure_dispatch<E: Event + 'static>(&mut self) {
         self.dispatch_map
             .entry(TypeId::of::<E>())
@@ -119,6 +128,16 @@ impl<S: Sender, R: Receiver, B: Bus> Bus for ChannelBus<S, R, B> {
     fn unsubscribe<E: Event + 'static>(&mut self, subscription: Self::Subscription<E>) {
         self.backer.unsubscribe(subscription)
     }
+
+    fn unsubscribe_all<E: Event + 'static>(&mut self) {
+        self.dispatch_map.remove(&TypeId::of::<E>());
+        self.backer.unsubscribe_all::<E>()
+    }
+
+    fn clear(&mut self) {
+        self.dispatch_map.clear();
+        self.backer.clear()
+    }
 }
 
 impl<S: Sender> BusSender<S> {
@@ -155,3 +174,134 @@ impl BlockingReceiver for std::sync::mpsc::Receiver<Message> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::mpsc;
+
+    use super::*;
+
+    struct Ping(u32);
+    impl Event for Ping {}
+
+    struct Pong;
+    impl Event for Pong {}
+
+    type StdBus = ChannelBus<mpsc::Sender<Message>, mpsc::Receiver<Message>>;
+
+    fn std_bus() -> StdBus {
+        ChannelBus::new(mpsc::channel(), LocalBus::new())
+    }
+
+    fn record_pings(bus: &mut StdBus) -> Rc<RefCell<Vec<u32>>> {
+        let received = Rc::new(RefCell::new(Vec::new()));
+        bus.subscribe_consumer({
+            let received = Rc::clone(&received);
+            move |Ping(n)| received.borrow_mut().push(n)
+        });
+        received
+    }
+
+    #[test]
+    fn publish_without_subscribers() {
+        let bus = std_bus();
+        assert!(!bus.publish(Ping(1)));
+    }
+
+    #[test]
+    fn publish_to_subscribers() {
+        let mut bus = std_bus();
+        let observed = Rc::new(RefCell::new(Vec::new()));
+        bus.subscribe_observer({
+            let observed = Rc::clone(&observed);
+            move |Ping(n): &Ping| observed.borrow_mut().push(*n)
+        });
+        let consumed = record_pings(&mut bus);
+
+        assert!(bus.publish(Ping(1)));
+        assert_eq!(*observed.borrow(), vec![1]);
+        assert_eq!(*consumed.borrow(), vec![1]);
+    }
+
+    #[test]
+    fn consumer_stops_dispatch() {
+        let mut bus = std_bus();
+        let first = record_pings(&mut bus);
+        let second = record_pings(&mut bus);
+
+        bus.publish(Ping(1));
+        assert_eq!(*first.borrow(), vec![1]);
+        assert!(second.borrow().is_empty());
+    }
+
+    #[test]
+    fn unsubscribe_removes_handler() {
+        let mut bus = std_bus();
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let subscription = bus.subscribe_observer({
+            let received = Rc::clone(&received);
+            move |Ping(n): &Ping| received.borrow_mut().push(*n)
+        });
+
+        bus.publish(Ping(1));
+        bus.unsubscribe(subscription);
+        assert!(!bus.publish(Ping(2)));
+        assert_eq!(*received.borrow(), vec![1]);
+    }
+
+    #[test]
+    fn unsubscribe_all_removes_handlers_for_event_type() {
+        let mut bus = std_bus();
+        let received = record_pings(&mut bus);
+        record_pings(&mut bus);
+        bus.subscribe_consumer(|Pong| ());
+
+        bus.unsubscribe_all::<Ping>();
+        assert!(!bus.publish(Ping(1)));
+        assert!(received.borrow().is_empty());
+        assert!(bus.publish(Pong));
+    }
+
+    #[test]
+    fn clear_removes_all_handlers() {
+        let mut bus = std_bus();
+        let received = record_pings(&mut bus);
+        bus.subscribe_consumer(|Pong| ());
+
+        bus.clear();
+        assert!(!bus.publish(Ping(1)));
+        assert!(!bus.publish(Pong));
+        assert!(received.borrow().is_empty());
+
+        let received = record_pings(&mut bus);
+        assert!(bus.publish(Ping(2)));
+        assert_eq!(*received.borrow(), vec![2]);
+    }
+
+    #[test]
+    fn sent_events_wait_for_recv() {
+        let mut bus = std_bus();
+        let received = record_pings(&mut bus);
+
+        bus.sender().send(Ping(1)).unwrap();
+        assert!(received.borrow().is_empty());
+        assert_eq!(bus.recv().unwrap(), Some(true));
+        assert_eq!(*received.borrow(), vec![1]);
+        assert_eq!(bus.recv().unwrap(), None);
+    }
+
+    #[test]
+    fn drain_pending_dispatches_in_order() {
+        let mut bus = std_bus();
+        let received = record_pings(&mut bus);
+
+        for n in 0..3 {
+            bus.sender().send(Ping(n)).unwrap();
+        }
+        assert_eq!(bus.drain_pending().unwrap(), 3);
+        assert_eq!(*received.borrow(), vec![0, 1, 2]);
+        assert_eq!(bus.drain_pending().unwrap(), 0);
+    }
+}
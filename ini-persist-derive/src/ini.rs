@@ -11,7 +11,8 @@ pub mod save;
 
 use self::attr::FieldAttr;
 
-type FieldExpander = fn(name: &Ident, typ: &Type, section: TokenStream, span: Span) -> TokenStream;
+type FieldExpander =
+    fn(name: &Ident, typ: &Type, section: TokenStream, map: bool, span: Span) -> TokenStream;
 type TraitExpander = fn(struct_name: &Ident, field_expansions: Vec<TokenStream>) -> TokenStream;
 
 fn expand_ini_impl(
@@ -43,7 +44,9 @@ fn expand_ini_impl(
                 None => quote!(None::<String>),
             };
 
-            Ok(field_expander(field_name, &field.ty, section, span))
+            Ok(field_expander(
+                field_name, &field.ty, section, attr.map, span,
+            ))
         })
         .map(|result| result.unwrap_or_else(Error::into_compile_error))
         .collect();
@@ -5,6 +5,7 @@ use crate::attr::{extract_str, unknown_attr, IniAttr};
 #[derive(Default)]
 pub struct FieldAttr {
     pub section: Option<Option<String>>,
+    pub map: bool,
 }
 
 impl IniAttr for FieldAttr {
@@ -25,6 +26,10 @@ impl IniAttr for FieldAttr {
                 self.section = Some(None);
                 return Ok(());
             }
+            if meta.path.is_ident("map") {
+                self.map = true;
+                return Ok(());
+            }
             unknown_attr(meta)
         })?;
         Ok(())
@@ -8,7 +8,25 @@ pub fn expand_ini_load(input: DeriveInput) -> Result<TokenStream> {
     expand_ini_impl(input, expand_field, expand_trait)
 }
 
-fn expand_field(name: &Ident, _typ: &Type, section: TokenStream, span: Span) -> TokenStream {
+fn expand_field(
+    name: &Ident,
+    _typ: &Type,
+    section: TokenStream,
+    map: bool,
+    span: Span,
+) -> TokenStream {
+    if map {
+        return quote_spanned! { span =>
+            if let Some(section) = ini.section(#section) {
+                for (key, value) in section.iter() {
+                    self.#name.insert(
+                        key.to_string(),
+                        ini_persist::load::ParseProperty::parse(value)?,
+                    );
+                }
+            }
+        };
+    }
     quote_spanned! { span =>
         if let Some(section) = ini.section(#section) {
             self.#name.load_in(section, "")?;
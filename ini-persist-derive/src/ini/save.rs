@@ -8,7 +8,30 @@ pub fn expand_ini_save(input: DeriveInput) -> Result<TokenStream> {
     expand_ini_impl(input, expand_field, expand_trait)
 }
 
-fn expand_field(name: &Ident, typ: &Type, section: TokenStream, span: Span) -> TokenStream {
+fn expand_field(
+    name: &Ident,
+    typ: &Type,
+    section: TokenStream,
+    map: bool,
+    span: Span,
+) -> TokenStream {
+    if map {
+        // The whole section belongs to the map, and entries are sorted to keep the output stable
+        return quote_spanned! { span =>
+            {
+                let section = ini.entry(#section).or_insert_with(ini::Properties::default);
+                *section = ini::Properties::default();
+                let mut entries: Vec<_> = self.#name.iter().collect();
+                entries.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+                for (key, value) in entries {
+                    section.append(
+                        key.as_str(),
+                        ini_persist::save::DisplayProperty::display(value),
+                    );
+                }
+            }
+        };
+    }
     quote_spanned! { span =>
         {
             let section = ini.entry(#section).or_insert_with(ini::Properties::default);
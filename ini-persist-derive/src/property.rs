@@ -7,8 +7,8 @@ use regex::Regex;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::{
-    Attribute, Data, DataEnum, DataStruct, DeriveInput, Error, Field, Fields, Ident, Meta, Result,
-    Token, Type, Variant,
+    Attribute, Data, DataEnum, DataStruct, DeriveInput, Error, Field, Fields, GenericArgument,
+    Ident, Meta, PathArguments, Result, Token, Type, Variant,
 };
 
 use crate::attr::{IniAttr, NoAttrSupport};
@@ -23,7 +23,9 @@ type FieldExpander<F> =
     fn(name: &Ident, typ: &Type, key: TokenStream, attr: FieldAttr, span: Span) -> F;
 type StructTraitExpander<F> =
     fn(struct_name: &Ident, field_expansions: Vec<Result<F>>) -> TokenStream;
-type VariantExpander = fn(name: &Ident, enum_name: &Ident, span: Span) -> TokenStream;
+type VariantExpander =
+    fn(name: &Ident, enum_name: &Ident, data_type: Option<&Type>, span: Span) -> TokenStream;
+type NewtypeTraitExpander = fn(struct_name: &Ident, inner_type: &Type, span: Span) -> TokenStream;
 type EnumTraitExpander = fn(
     enum_name: &Ident,
     enum_attr: &EnumAttr,
@@ -32,14 +34,19 @@ type EnumTraitExpander = fn(
     match_arms: Vec<TokenStream>,
 ) -> TokenStream;
 
+struct PropertyExpanders<F> {
+    field: FieldExpander<F>,
+    struct_trait: StructTraitExpander<F>,
+    newtype_trait: NewtypeTraitExpander,
+    repr_variant: VariantExpander,
+    named_variant: VariantExpander,
+    caseless_variant: VariantExpander,
+    enum_trait: EnumTraitExpander,
+}
+
 fn expand_property_impl<F>(
     input: DeriveInput,
-    field_expander: FieldExpander<F>,
-    struct_trait_expander: StructTraitExpander<F>,
-    repr_variant_expander: VariantExpander,
-    named_variant_expander: VariantExpander,
-    caseless_variant_expander: VariantExpander,
-    enum_trait_expander: EnumTraitExpander,
+    expanders: PropertyExpanders<F>,
 ) -> Result<TokenStream> {
     match input.data {
         Data::Struct(DataStruct {
@@ -49,19 +56,27 @@ fn expand_property_impl<F>(
             input.ident,
             fields.named.into_iter().collect(),
             input.attrs,
-            field_expander,
-            struct_trait_expander,
+            expanders.field,
+            expanders.struct_trait,
+        ),
+        Data::Struct(DataStruct {
+            fields: Fields::Unnamed(fields),
+            ..
+        }) if fields.unnamed.len() == 1 => expand_newtype_impl(
+            input.ident,
+            fields.unnamed.into_iter().next().unwrap(),
+            input.attrs,
+            expanders.newtype_trait,
         ),
         Data::Enum(DataEnum { variants, .. }) => expand_enum_impl(
             input.ident,
             variants.into_iter().collect(),
             input.attrs,
-            repr_variant_expander,
-            named_variant_expander,
-            caseless_variant_expander,
-            enum_trait_expander,
+            &expanders,
+        ),
+        _ => panic!(
+            "Property can only be derived on an enum, a struct with named fields, or a newtype"
         ),
-        _ => panic!("Property can only be derived on an enum or a struct with named fields"),
     }
 }
 
@@ -110,6 +125,17 @@ fn expand_struct_impl<F>(
     Ok(trait_expander(struct_name, field_expansions))
 }
 
+fn expand_newtype_impl(
+    ident: Ident,
+    field: Field,
+    attrs: Vec<Attribute>,
+    trait_expander: NewtypeTraitExpander,
+) -> Result<TokenStream> {
+    NoAttrSupport::from_ast(attrs.iter())?;
+    NoAttrSupport::from_ast(field.attrs.iter())?;
+    Ok(trait_expander(&ident, &field.ty, field.span()))
+}
+
 fn expand_key(format: &str, name: &str) -> TokenStream {
     let uses_prefix = PREFIX_REGEX.is_match(format);
     let uses_name = NAME_REGEX.is_match(format);
@@ -121,23 +147,28 @@ fn expand_key(format: &str, name: &str) -> TokenStream {
     }
 }
 
-fn expand_enum_impl(
+fn expand_enum_impl<F>(
     ident: Ident,
     variants: Vec<Variant>,
     attrs: Vec<Attribute>,
-    repr_variant_expander: VariantExpander,
-    named_variant_expander: VariantExpander,
-    caseless_variant_expander: VariantExpander,
-    trait_expander: EnumTraitExpander,
+    expanders: &PropertyExpanders<F>,
 ) -> Result<TokenStream> {
+    let attr = EnumAttr::from_ast(attrs.iter())?;
+
     for variant in variants.iter() {
         NoAttrSupport::from_ast(variant.attrs.iter())?;
-        if is_non_unit_variant(variant) {
-            panic!("Only unit variants are supported");
+        match variant_data_type(variant) {
+            Ok(Some(_)) if attr.repr.is_some() => {
+                return Err(Error::new_spanned(
+                    &variant.fields,
+                    "variants with data are not supported with `repr`",
+                ));
+            }
+            Ok(_) => (),
+            Err(()) => panic!("Only unit variants and single-field tuple variants are supported"),
         }
     }
 
-    let attr = EnumAttr::from_ast(attrs.iter())?;
     let enum_name = &ident;
     let repr_type = enum_repr(&attrs)?.unwrap_or_else(|| Ident::new("isize", ident.span()));
 
@@ -159,20 +190,26 @@ fn expand_enum_impl(
         .unwrap_or_default();
 
     let match_expander = match (attr.repr, attr.ignore_case) {
-        (Some(()), _) => repr_variant_expander,
-        (None, None) => named_variant_expander,
-        (None, Some(())) => caseless_variant_expander,
+        (Some(()), _) => expanders.repr_variant,
+        (None, None) => expanders.named_variant,
+        (None, Some(())) => expanders.caseless_variant,
     };
     let match_arms = variants
         .iter()
         .map(|variant| {
             NoAttrSupport::from_ast(variant.attrs.iter())?;
-            Ok(match_expander(&variant.ident, enum_name, variant.span()))
+            let data_type = variant_data_type(variant).unwrap();
+            Ok(match_expander(
+                &variant.ident,
+                enum_name,
+                data_type,
+                variant.span(),
+            ))
         })
         .map(|result| result.unwrap_or_else(Error::into_compile_error))
         .collect();
 
-    Ok(trait_expander(
+    Ok((expanders.enum_trait)(
         enum_name,
         &attr,
         attr.repr.map(|_| &repr_type),
@@ -181,11 +218,35 @@ fn expand_enum_impl(
     ))
 }
 
-fn is_non_unit_variant(variant: &Variant) -> bool {
-    if let Fields::Unit = variant.fields {
+fn variant_data_type(variant: &Variant) -> std::result::Result<Option<&Type>, ()> {
+    match &variant.fields {
+        Fields::Unit => Ok(None),
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            Ok(Some(&fields.unnamed.first().unwrap().ty))
+        }
+        _ => Err(()),
+    }
+}
+
+fn is_option_type(typ: &Type) -> bool {
+    let Type::Path(path) = typ else {
         return false;
+    };
+    if path.qself.is_some() {
+        return false;
+    }
+    let Some(segment) = path.path.segments.last() else {
+        return false;
+    };
+    if segment.ident != "Option" {
+        return false;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => {
+            args.args.len() == 1 && matches!(args.args.first(), Some(GenericArgument::Type(_)))
+        }
+        _ => false,
     }
-    true
 }
 
 fn enum_repr(attrs: &[Attribute]) -> Result<Option<Ident>> {
@@ -3,27 +3,54 @@ use quote::{quote, quote_spanned};
 use syn::{DeriveInput, Error, Ident, Result, Type};
 
 use super::attr::{EnumAttr, FieldAttr, LoadFn};
-use super::expand_property_impl;
+use super::{expand_property_impl, is_option_type, PropertyExpanders};
 
 pub fn expand_load_property(input: DeriveInput) -> Result<TokenStream> {
     expand_property_impl(
         input,
-        expand_field,
-        expand_struct_trait,
-        expand_repr_variant_match,
-        expand_named_variant_match,
-        expand_caseless_variant_match,
-        expand_enum_trait,
+        PropertyExpanders {
+            field: expand_field,
+            struct_trait: expand_struct_trait,
+            newtype_trait: expand_newtype_trait,
+            repr_variant: expand_repr_variant_match,
+            named_variant: expand_named_variant_match,
+            caseless_variant: expand_caseless_variant_match,
+            enum_trait: expand_enum_trait,
+        },
     )
 }
 
 fn expand_field(
     name: &Ident,
-    _typ: &Type,
+    typ: &Type,
     key: TokenStream,
     attr: FieldAttr,
     span: Span,
 ) -> TokenStream {
+    // For optional fields, the parse function parses the inner value, and an absent key
+    // clears the field
+    if let (true, Some(LoadFn::Parsed(path))) = (is_option_type(typ), &attr.load_fn) {
+        return if attr.ignore_errors.is_some() {
+            quote_spanned! { span =>
+                match section.get(#key) {
+                    Some(value) => {
+                        if let Ok(value) = #path(value) {
+                            self.#name = Some(value);
+                        }
+                    }
+                    None => self.#name = None,
+                }
+            }
+        } else {
+            quote_spanned! { span =>
+                self.#name = match section.get(#key) {
+                    Some(value) => Some(#path(value)?),
+                    None => None,
+                };
+            }
+        };
+    }
+
     if attr.ignore_errors.is_some() {
         match attr.load_fn {
             None => quote_spanned! { span =>
@@ -85,17 +112,66 @@ fn expand_struct_trait(
     }
 }
 
-fn expand_named_variant_match(name: &Ident, enum_name: &Ident, span: Span) -> TokenStream {
-    let name_str = name.to_string();
-    quote_spanned!(span => #name_str => #enum_name::#name)
+fn expand_newtype_trait(struct_name: &Ident, inner_type: &Type, span: Span) -> TokenStream {
+    quote_spanned! { span =>
+        #[automatically_derived]
+        impl ini_persist::load::ParseProperty for #struct_name {
+            fn parse(value: &str) -> ini_persist::Result<Self> {
+                ini_persist::Result::Ok(Self(
+                    <#inner_type as ini_persist::load::ParseProperty>::parse(value)?
+                ))
+            }
+        }
+    }
+}
+
+fn expand_named_variant_match(
+    name: &Ident,
+    enum_name: &Ident,
+    data_type: Option<&Type>,
+    span: Span,
+) -> TokenStream {
+    expand_variant_match(name.to_string(), name, enum_name, data_type, span)
+}
+
+fn expand_caseless_variant_match(
+    name: &Ident,
+    enum_name: &Ident,
+    data_type: Option<&Type>,
+    span: Span,
+) -> TokenStream {
+    expand_variant_match(
+        name.to_string().to_lowercase(),
+        name,
+        enum_name,
+        data_type,
+        span,
+    )
 }
 
-fn expand_caseless_variant_match(name: &Ident, enum_name: &Ident, span: Span) -> TokenStream {
-    let name_str = name.to_string().to_lowercase();
-    quote_spanned!(span => #name_str => #enum_name::#name)
+fn expand_variant_match(
+    name_str: String,
+    name: &Ident,
+    enum_name: &Ident,
+    data_type: Option<&Type>,
+    span: Span,
+) -> TokenStream {
+    match data_type {
+        None => quote_spanned!(span => (#name_str, None) => #enum_name::#name),
+        Some(data_type) => quote_spanned! { span =>
+            (#name_str, Some(data)) => #enum_name::#name(
+                <#data_type as ini_persist::load::ParseProperty>::parse(data)?
+            )
+        },
+    }
 }
 
-fn expand_repr_variant_match(name: &Ident, enum_name: &Ident, span: Span) -> TokenStream {
+fn expand_repr_variant_match(
+    name: &Ident,
+    enum_name: &Ident,
+    _data_type: Option<&Type>,
+    span: Span,
+) -> TokenStream {
     quote_spanned!(span => Discriminants::#name => #enum_name::#name)
 }
 
@@ -106,23 +182,38 @@ fn expand_enum_trait(
     prelude: TokenStream,
     match_arms: Vec<TokenStream>,
 ) -> TokenStream {
-    let parse_repr = repr_type
-        .map(|repr_type| {
+    let (parse_value, value_expr) = match repr_type {
+        Some(repr_type) => (
             quote! {
                 let value = #repr_type::parse(value)?;
-            }
-        })
-        .unwrap_or_default();
-    let value_expr = match enum_attr.ignore_case {
-        Some(()) => quote!(value.to_lowercase().as_str()),
-        None => quote!(value),
+            },
+            quote!(value),
+        ),
+        None => {
+            let name_expr = match enum_attr.ignore_case {
+                Some(()) => quote!(name.to_lowercase().as_str()),
+                None => quote!(name),
+            };
+            (
+                quote! {
+                    let (name, data) = match value
+                        .strip_suffix(')')
+                        .and_then(|value| value.split_once('('))
+                    {
+                        Some((name, data)) => (name, Some(data)),
+                        None => (value, None),
+                    };
+                },
+                quote!((#name_expr, data)),
+            )
+        }
     };
     quote! {
         #[automatically_derived]
         impl ini_persist::load::ParseProperty for #enum_name {
             fn parse(value: &str) -> ini_persist::Result<Self> {
                 #prelude
-                #parse_repr
+                #parse_value
                 ini_persist::Result::Ok(match #value_expr {
                     #(#match_arms,)*
                     _ => return ini_persist::Result::Err(ini_persist::Error::invalid_value(
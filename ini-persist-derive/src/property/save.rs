@@ -3,17 +3,20 @@ use quote::{quote, quote_spanned};
 use syn::{DeriveInput, Ident, Result, Type};
 
 use super::attr::{AppendFn, EnumAttr, FieldAttr};
-use super::expand_property_impl;
+use super::{expand_property_impl, is_option_type, PropertyExpanders};
 
 pub fn expand_save_property(input: DeriveInput) -> Result<TokenStream> {
     expand_property_impl(
         input,
-        expand_field,
-        expand_struct_trait,
-        expand_repr_variant_match,
-        expand_named_variant_match,
-        expand_named_variant_match,
-        expand_enum_trait,
+        PropertyExpanders {
+            field: expand_field,
+            struct_trait: expand_struct_trait,
+            newtype_trait: expand_newtype_trait,
+            repr_variant: expand_repr_variant_match,
+            named_variant: expand_named_variant_match,
+            caseless_variant: expand_named_variant_match,
+            enum_trait: expand_enum_trait,
+        },
     )
 }
 
@@ -39,6 +42,11 @@ fn expand_field(
         Some(AppendFn::Append(path)) => quote_spanned! { span =>
             #path(&self.#name, section, #key);
         },
+        Some(AppendFn::Display(path)) if is_option_type(typ) => quote_spanned! { span =>
+            if let Some(value) = self.#name.as_ref() {
+                section.append(#key, #path(value));
+            }
+        },
         Some(AppendFn::Display(path)) => quote_spanned! { span =>
             section.append(#key, #path(&self.#name));
         },
@@ -73,11 +81,41 @@ fn expand_struct_trait(
     }
 }
 
-fn expand_named_variant_match(name: &Ident, enum_name: &Ident, span: Span) -> TokenStream {
-    quote_spanned!(span => #enum_name::#name => stringify!(#name).to_string())
+fn expand_newtype_trait(struct_name: &Ident, inner_type: &Type, span: Span) -> TokenStream {
+    quote_spanned! { span =>
+        #[automatically_derived]
+        impl ini_persist::save::DisplayProperty for #struct_name {
+            fn display(&self) -> String {
+                <#inner_type as ini_persist::save::DisplayProperty>::display(&self.0)
+            }
+        }
+    }
+}
+
+fn expand_named_variant_match(
+    name: &Ident,
+    enum_name: &Ident,
+    data_type: Option<&Type>,
+    span: Span,
+) -> TokenStream {
+    match data_type {
+        None => quote_spanned!(span => #enum_name::#name => stringify!(#name).to_string()),
+        Some(data_type) => quote_spanned! { span =>
+            #enum_name::#name(data) => format!(
+                "{}({})",
+                stringify!(#name),
+                <#data_type as ini_persist::save::DisplayProperty>::display(data),
+            )
+        },
+    }
 }
 
-fn expand_repr_variant_match(name: &Ident, enum_name: &Ident, span: Span) -> TokenStream {
+fn expand_repr_variant_match(
+    name: &Ident,
+    enum_name: &Ident,
+    _data_type: Option<&Type>,
+    span: Span,
+) -> TokenStream {
     quote_spanned!(span => #enum_name::#name => format!("{}", Discriminants::#name))
 }
 
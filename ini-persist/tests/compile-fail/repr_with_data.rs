@@ -0,0 +1,11 @@
+use ini_persist::load::LoadProperty;
+
+#[derive(LoadProperty)]
+#[ini(repr)]
+#[repr(u8)]
+enum Foo {
+    Arglebargle,
+    GlopGlyf(u8),
+}
+
+fn main() {}
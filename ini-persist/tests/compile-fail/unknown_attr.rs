@@ -37,5 +37,11 @@ enum Snop {
     Snyf,
 }
 
-fn main() {
-}
+#[derive(LoadProperty)]
+#[ini(bogus)]
+struct Zork(u8);
+
+#[derive(LoadProperty)]
+struct Frob(#[ini(bogus)] u8);
+
+fn main() {}
@@ -1,5 +1,7 @@
+use std::collections::{BTreeMap, HashMap};
+
 use ini::{Ini, WriteOption};
-use ini_persist::load::{IniLoad, LoadProperty};
+use ini_persist::load::{IniLoad, LoadProperty, ParseProperty};
 use ini_persist::save::{IniSave, SaveProperty};
 
 #[derive(Debug, Default, PartialEq, IniLoad, IniSave)]
@@ -41,6 +43,8 @@ struct Section {
 
     bolle: Option<CaselessEnum>,
     snop: Option<EnumByRepr>,
+    snyf: Option<EnumWithData>,
+    zork: Option<CaselessEnumWithData>,
 }
 
 #[derive(Debug, Default, PartialEq, LoadProperty, SaveProperty)]
@@ -61,6 +65,52 @@ struct RenamedSection {
     quux: u8,
 }
 
+#[derive(Debug, PartialEq, IniLoad, IniSave)]
+struct OptionalRoot {
+    #[ini(general)]
+    general: Optional,
+}
+
+#[derive(Debug, PartialEq, LoadProperty, SaveProperty)]
+struct Optional {
+    name: Option<String>,
+    count: Option<u32>,
+    mode: Option<EnumWithData>,
+    delay: Option<Newtype>,
+
+    #[ini(parse_with = helpers::my_parse, display_with = helpers::my_display_i16)]
+    offset: Option<i16>,
+
+    #[ini(ignore_errors, parse_with = helpers::err_parse)]
+    baz: Option<Baz>,
+}
+
+impl Default for Optional {
+    fn default() -> Self {
+        Self {
+            name: Some("default".to_string()),
+            count: Some(7),
+            mode: Some(EnumWithData::Auto),
+            delay: Some(Newtype(1)),
+            offset: Some(3),
+            baz: Some(Baz::default()),
+        }
+    }
+}
+
+impl Optional {
+    fn empty() -> Self {
+        Self {
+            name: None,
+            count: None,
+            mode: None,
+            delay: None,
+            offset: None,
+            baz: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, SaveProperty)]
 struct Foo {
     value: u16,
@@ -94,6 +144,24 @@ impl Default for Baz {
     }
 }
 
+#[derive(Debug, LoadProperty, SaveProperty, PartialEq, Eq)]
+struct Newtype(u16);
+
+#[derive(Debug, Default, PartialEq, IniLoad, IniSave)]
+struct MapRoot {
+    #[ini(map, section = "log_levels")]
+    levels: HashMap<String, Level>,
+
+    #[ini(map)]
+    counts: BTreeMap<String, u32>,
+}
+
+#[derive(Debug, LoadProperty, SaveProperty, PartialEq, Eq)]
+enum Level {
+    Trace,
+    Info,
+}
+
 #[derive(Debug, LoadProperty, SaveProperty, PartialEq, Eq)]
 enum EnumByName {
     Argle,
@@ -115,6 +183,19 @@ enum CaselessEnum {
     SnopSnyf,
 }
 
+#[derive(Debug, LoadProperty, SaveProperty, PartialEq, Eq)]
+enum EnumWithData {
+    Auto,
+    Always(bool),
+}
+
+#[derive(Debug, LoadProperty, SaveProperty, PartialEq, Eq)]
+#[ini(ignore_case)]
+enum CaselessEnumWithData {
+    Unlimited,
+    LimitedTo(u8),
+}
+
 mod helpers {
     use super::{Bar, Baz, Foo};
     use ini::Properties;
@@ -210,6 +291,168 @@ fn comprehensive_saving_test() {
     assert_eq!(saved, TEST_INI_SAVE);
 }
 
+#[test]
+fn optional_absent_keys_load_as_none() {
+    let ini = Ini::load_from_str("unrelated=1\n").unwrap();
+    let mut loaded = OptionalRoot {
+        general: Default::default(),
+    };
+    loaded.load_from_ini(&ini).unwrap();
+
+    assert_eq!(loaded.general, Optional::empty());
+}
+
+#[test]
+fn optional_present_keys_load_as_some() {
+    let ini =
+        Ini::load_from_str("name=Conan\ncount=42\nmode=Always(false)\noffset=5\nbaz=whatever\n")
+            .unwrap();
+    let mut loaded = OptionalRoot {
+        general: Optional::empty(),
+    };
+    loaded.load_from_ini(&ini).unwrap();
+
+    let expected = Optional {
+        name: Some("Conan".to_string()),
+        count: Some(42),
+        mode: Some(EnumWithData::Always(false)),
+        delay: None,
+        offset: Some(-5),
+        baz: None,
+    };
+    assert_eq!(loaded.general, expected);
+}
+
+#[test]
+fn optional_invalid_value_fails_to_load() {
+    let ini = Ini::load_from_str("count=many\n").unwrap();
+    let mut loaded = OptionalRoot {
+        general: Default::default(),
+    };
+    assert!(loaded.load_from_ini(&ini).is_err());
+
+    let ini = Ini::load_from_str("offset=many\n").unwrap();
+    let mut loaded = OptionalRoot {
+        general: Default::default(),
+    };
+    assert!(loaded.load_from_ini(&ini).is_err());
+}
+
+#[test]
+fn optional_ignored_error_keeps_value() {
+    let ini = Ini::load_from_str("baz=whatever\n").unwrap();
+    let mut loaded = OptionalRoot {
+        general: Default::default(),
+    };
+    loaded.load_from_ini(&ini).unwrap();
+    assert_eq!(loaded.general.baz, Some(Baz::default()));
+}
+
+#[test]
+fn optional_none_is_not_saved() {
+    let mut ini = Ini::new();
+    ini.with_section(None::<String>)
+        .set("name", "stale")
+        .set("count", "13");
+
+    let to_save = OptionalRoot {
+        general: Optional {
+            count: Some(42),
+            ..Optional::empty()
+        },
+    };
+    to_save.save_to_ini(&mut ini);
+
+    let section = ini.general_section();
+    assert_eq!(section.get("name"), None);
+    assert_eq!(section.get("count"), Some("42"));
+    assert_eq!(section.get("mode"), None);
+    assert_eq!(section.get("offset"), None);
+}
+
+#[test]
+fn optional_round_trip() {
+    let original = OptionalRoot {
+        general: Optional {
+            name: Some("Conan".to_string()),
+            mode: Some(EnumWithData::Always(true)),
+            delay: Some(Newtype(12)),
+            offset: Some(-8),
+            ..Optional::empty()
+        },
+    };
+    let mut ini = Ini::new();
+    original.save_to_ini(&mut ini);
+
+    let mut loaded = OptionalRoot {
+        general: Default::default(),
+    };
+    loaded.load_from_ini(&ini).unwrap();
+    assert_eq!(loaded, original);
+}
+
+#[test]
+fn newtype_loading_and_saving() {
+    let mut section = ini::Properties::new();
+    section.insert("delay", "386");
+
+    let mut loaded = Newtype(0);
+    loaded.load_in(&section, "delay").unwrap();
+    assert_eq!(loaded, Newtype(386));
+    assert!(Newtype::parse("-1").is_err());
+
+    let mut saved = ini::Properties::new();
+    Newtype(17).append(&mut saved, "delay");
+    assert_eq!(saved.get("delay"), Some("17"));
+}
+
+#[test]
+fn map_round_trip() {
+    let original = MapRoot {
+        levels: HashMap::from([
+            ("servers::net".to_string(), Level::Trace),
+            ("gui".to_string(), Level::Info),
+        ]),
+        counts: BTreeMap::from([("argle".to_string(), 17)]),
+    };
+    let mut ini = Ini::new();
+    original.save_to_ini(&mut ini);
+
+    let levels = ini.section(Some("log_levels")).unwrap();
+    assert_eq!(levels.get("servers::net"), Some("Trace"));
+    assert_eq!(levels.get("gui"), Some("Info"));
+    assert_eq!(ini.section(Some("counts")).unwrap().get("argle"), Some("17"));
+
+    let mut loaded = MapRoot::default();
+    loaded.load_from_ini(&ini).unwrap();
+    assert_eq!(loaded, original);
+}
+
+#[test]
+fn map_save_removes_stale_entries() {
+    let mut ini = Ini::new();
+    ini.with_section(Some("log_levels")).set("stale", "Trace");
+
+    let to_save = MapRoot {
+        levels: HashMap::from([("gui".to_string(), Level::Info)]),
+        ..Default::default()
+    };
+    to_save.save_to_ini(&mut ini);
+
+    let levels = ini.section(Some("log_levels")).unwrap();
+    assert_eq!(levels.get("stale"), None);
+    assert_eq!(levels.get("gui"), Some("Info"));
+}
+
+#[test]
+fn map_invalid_value_fails_to_load() {
+    let mut ini = Ini::new();
+    ini.with_section(Some("log_levels")).set("gui", "Verbose");
+
+    let mut loaded = MapRoot::default();
+    assert!(loaded.load_from_ini(&ini).is_err());
+}
+
 fn make_test_data() -> Root {
     Root {
         general: General {
@@ -228,6 +471,8 @@ fn make_test_data() -> Root {
             olle: 42.0,
             bolle: Some(CaselessEnum::OlleBolle),
             snop: Some(EnumByRepr::Glyf),
+            snyf: Some(EnumWithData::Always(true)),
+            zork: Some(CaselessEnumWithData::LimitedTo(42)),
         },
         renamed: RenamedSection {
             snyf: 42,
@@ -251,6 +496,8 @@ prefixedglyf=Argle
 olle=84.00
 bolle=oLLEbOLLE
 snop=17
+snyf=Always(true)
+zork=limitedTO(42)
 
 [SomethingElse]
 snyf=-42
@@ -272,6 +519,8 @@ prefixedglyf=Argle
 olle=84.00
 bolle=OlleBolle
 snop=17
+snyf=Always(true)
+zork=LimitedTo(42)
 
 [SomethingElse]
 snyf=-42